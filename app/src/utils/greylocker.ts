@@ -72,8 +72,15 @@ export interface DataPoolInfo {
   dataType: DataType;
   rewardRate: number;
   description: string;
+  maxParticipants?: number; // Omit for no cap
+  expiresAt?: number;       // Unix seconds; omit for no expiry
+  epsilonBudget: number;    // Differential-privacy budget per participant
+  maxQueryEpsilon: number;  // Most any one query may spend
 }
 
+// The program stores epsilons in thousandths
+const toMilliEpsilon = (epsilon: number) => new BN(Math.round(epsilon * 1000));
+
 // Main Greylocker client class—your uplink to the neon grid
 export class GreylockerClient {
   program: Program;
@@ -117,7 +124,11 @@ export class GreylockerClient {
   async initialize(): Promise<string> {
     this.ensureWalletConnected();
 
-    const treasuryAccount = await this.getOrCreateTokenAccount(this.wallet.publicKey);
+    // The treasury is a PDA token account owned by the state PDA—no wallet holds it
+    const [treasuryAccount] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('treasury')],
+      GREYLOCKER_PROGRAM_ID
+    );
     const tx = await this.program.methods
       .initialize(this.greylockerStateBump)
       .accounts({
@@ -190,34 +201,84 @@ export class GreylockerClient {
     return tx;
   }
 
-  // Unstake GREY—reclaim power from the grid
-  async unstake(amount: number): Promise<string> {
+  // Request an unstake—start the cooldown before reclaiming power from the grid
+  async requestUnstake(amount: number, positionId: number): Promise<string> {
     this.ensureWalletConnected();
 
     const amountBN = new BN(amount * 10 ** 9);
+    const [stakeAccountPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('stake-account'), this.wallet.publicKey.toBuffer(), new BN(positionId).toArrayLike(Buffer, 'le', 8)],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [stakeVaultPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('stake-vault'), stakeAccountPda.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const stakeAccount = await this.program.account.stakeAccount.fetch(stakeAccountPda);
+    const [unbondingRequestPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('unbonding'), stakeAccountPda.toBuffer(), (stakeAccount.nextUnbondingId as BN).toArrayLike(Buffer, 'le', 8)],
+      GREYLOCKER_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .requestUnstake(amountBN)
+      .accounts({
+        greylockerState: this.greylockerState,
+        user: this.wallet.publicKey,
+        stakeAccount: stakeAccountPda,
+        unbondingRequest: unbondingRequestPda,
+        stakeVault: stakeVaultPda,
+        greyMint: GREY_TOKEN_MINT,
+        systemProgram: web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    console.log(`Requested unstake of ${amount} GREY | Tx: ${tx}`);
+    return tx;
+  }
+
+  // Withdraw unbonded GREY—cooldown served, power reclaimed
+  async withdrawUnbonded(positionId: number, requestId: number): Promise<string> {
+    this.ensureWalletConnected();
+
     const userTokenAccount = await this.getOrCreateTokenAccount(this.wallet.publicKey);
     const [stakeAccountPda] = web3.PublicKey.findProgramAddressSync(
-      [Buffer.from('stake-account'), this.wallet.publicKey.toBuffer()],
+      [Buffer.from('stake-account'), this.wallet.publicKey.toBuffer(), new BN(positionId).toArrayLike(Buffer, 'le', 8)],
       GREYLOCKER_PROGRAM_ID
     );
     const [stakeVaultPda] = web3.PublicKey.findProgramAddressSync(
       [Buffer.from('stake-vault'), stakeAccountPda.toBuffer()],
       GREYLOCKER_PROGRAM_ID
     );
+    const [unbondingRequestPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('unbonding'), stakeAccountPda.toBuffer(), new BN(requestId).toArrayLike(Buffer, 'le', 8)],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [serviceProviderPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('service-provider'), this.wallet.publicKey.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [stakeRegistryPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('stake-registry'), this.wallet.publicKey.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
 
     const tx = await this.program.methods
-      .unstake(amountBN)
+      .withdrawUnbonded()
       .accounts({
         greylockerState: this.greylockerState,
         user: this.wallet.publicKey,
+        stakeRegistry: stakeRegistryPda,
         stakeAccount: stakeAccountPda,
+        unbondingRequest: unbondingRequestPda,
         stakeVault: stakeVaultPda,
+        serviceProvider: serviceProviderPda,
         userTokenAccount,
         greyMint: GREY_TOKEN_MINT,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc();
-    console.log(`Unstaked ${amount} GREY | Tx: ${tx}`);
+    console.log(`Withdrew unbonded GREY | Tx: ${tx}`);
     return tx;
   }
 
@@ -296,14 +357,23 @@ export class GreylockerClient {
       [Buffer.from('service-provider'), this.wallet.publicKey.toBuffer()],
       GREYLOCKER_PROGRAM_ID
     );
+    // Every grant gets its own record, keyed by the provider's next grant id
+    const serviceProvider = await this.program.account.serviceProvider.fetch(serviceProviderPda);
     const [accessRecordPda] = web3.PublicKey.findProgramAddressSync(
-      [Buffer.from('access-record'), serviceProviderPda.toBuffer(), userAddress.toBuffer()],
+      [
+        Buffer.from('access-record'),
+        serviceProviderPda.toBuffer(),
+        userAddress.toBuffer(),
+        (serviceProvider.accessGrantsIssued as BN).toArrayLike(Buffer, 'le', 8),
+      ],
       GREYLOCKER_PROGRAM_ID
     );
 
+    const [treasuryPda] = web3.PublicKey.findProgramAddressSync([Buffer.from('treasury')], GREYLOCKER_PROGRAM_ID);
+
     const dataTypeObj: any = { [dataType]: {} };
     const tx = await this.program.methods
-      .payAccessFee(amountBN, dataTypeObj)
+      .payAccessFee(amountBN, dataTypeObj, duration)
       .accounts({
         greylockerState: this.greylockerState,
         serviceProvider: serviceProviderPda,
@@ -313,6 +383,7 @@ export class GreylockerClient {
         userTokenAccount,
         accessRecord: accessRecordPda,
         greyMint: GREY_TOKEN_MINT,
+        treasury: treasuryPda, // Receives the unburned share of the protocol fee
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
         rent: web3.SYSVAR_RENT_PUBKEY,
@@ -322,13 +393,18 @@ export class GreylockerClient {
     return tx;
   }
 
-  // Create a data pool—spawn a neon stream of rewards (governance only)
-  async createDataPool(poolInfo: DataPoolInfo, poolIndex: number): Promise<string> {
+  // Create a data pool—spawn a neon stream of rewards (governance only, on a council proposal past its timelock)
+  async createDataPool(poolInfo: DataPoolInfo, proposalId: number): Promise<string> {
     this.ensureWalletConnected();
 
     const rewardRateBN = new BN(poolInfo.rewardRate * 10 ** 9);
     const [dataPoolPda] = web3.PublicKey.findProgramAddressSync(
-      [Buffer.from('data-pool'), Buffer.from([poolIndex])],
+      [Buffer.from('data-pool'), this.wallet.publicKey.toBuffer(), Buffer.from(poolInfo.name)],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [councilPda] = web3.PublicKey.findProgramAddressSync([Buffer.from('governance-council')], GREYLOCKER_PROGRAM_ID);
+    const [proposalPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('proposal'), new BN(proposalId).toArrayLike(Buffer, 'le', 8)],
       GREYLOCKER_PROGRAM_ID
     );
 
@@ -339,11 +415,17 @@ export class GreylockerClient {
         dataType: dataTypeObj,
         rewardRate: rewardRateBN,
         description: poolInfo.description,
+        maxParticipants: new BN(poolInfo.maxParticipants ?? 0),
+        expiresAt: new BN(poolInfo.expiresAt ?? 0),
+        epsilonBudget: toMilliEpsilon(poolInfo.epsilonBudget),
+        maxQueryEpsilon: toMilliEpsilon(poolInfo.maxQueryEpsilon),
       })
       .accounts({
         greylockerState: this.greylockerState,
         governanceAuthority: this.wallet.publicKey,
         dataPool: dataPoolPda,
+        governanceCouncil: councilPda,
+        proposal: proposalPda,
         systemProgram: web3.SystemProgram.programId,
       })
       .rpc();
@@ -351,17 +433,73 @@ export class GreylockerClient {
     return tx;
   }
 
+  // Open a provider data pool—buy data straight from users out of your own escrow
+  async createProviderDataPool(
+    poolInfo: DataPoolInfo,
+    initialBudget: number,
+    serviceStakeAccount: web3.PublicKey
+  ): Promise<string> {
+    this.ensureWalletConnected();
+
+    const serviceTokenAccount = await this.getOrCreateTokenAccount(this.wallet.publicKey);
+    const [dataPoolPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('data-pool'), this.wallet.publicKey.toBuffer(), Buffer.from(poolInfo.name)],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [budgetVaultPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('data-pool-vault'), dataPoolPda.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [serviceProviderPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('service-provider'), this.wallet.publicKey.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .createProviderDataPool(
+        {
+          name: poolInfo.name,
+          dataType: poolInfo.dataType,
+          rewardRate: new BN(poolInfo.rewardRate * 10 ** 9),
+          description: poolInfo.description,
+          maxParticipants: new BN(poolInfo.maxParticipants ?? 0),
+          expiresAt: new BN(poolInfo.expiresAt ?? 0),
+          epsilonBudget: toMilliEpsilon(poolInfo.epsilonBudget),
+          maxQueryEpsilon: toMilliEpsilon(poolInfo.maxQueryEpsilon),
+        },
+        new BN(initialBudget * 10 ** 9)
+      )
+      .accounts({
+        greylockerState: this.greylockerState,
+        greyMint: GREY_TOKEN_MINT,
+        dataPool: dataPoolPda,
+        budgetVault: budgetVaultPda,
+        serviceProvider: serviceProviderPda,
+        serviceStakeAccount,
+        serviceTokenAccount,
+        serviceOwner: this.wallet.publicKey,
+        systemProgram: web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    console.log(`Provider pool "${poolInfo.name}" opened with ${initialBudget} GREY escrowed | Tx: ${tx}`);
+    return tx;
+  }
+
   // Join a data pool—plug into the reward stream
-  async joinDataPool(dataPoolAddress: web3.PublicKey): Promise<string> {
+  async joinDataPool(dataPoolAddress: web3.PublicKey, consentTerms: string): Promise<string> {
     this.ensureWalletConnected();
 
     const [participantPda] = web3.PublicKey.findProgramAddressSync(
       [Buffer.from('participant'), this.wallet.publicKey.toBuffer(), dataPoolAddress.toBuffer()],
       GREYLOCKER_PROGRAM_ID
     );
+    // The consent record commits to the exact terms the user agreed to
+    const consentHash = Array.from(Buffer.from(anchor.utils.sha256.hash(consentTerms), 'hex'));
 
     const tx = await this.program.methods
-      .joinDataPool()
+      .joinDataPool(consentHash)
       .accounts({
         greylockerState: this.greylockerState,
         user: this.wallet.publicKey,
@@ -374,36 +512,239 @@ export class GreylockerClient {
     return tx;
   }
 
-  // Pay data reward—feed the grid’s contributors (vault role only)
+  // Record a query—spend a participant’s privacy budget and pay them for the answer (pool operator only)
+  async recordQuery(
+    dataPoolAddress: web3.PublicKey,
+    dataPoolCreator: web3.PublicKey,
+    userAddress: web3.PublicKey,
+    queryHash: Uint8Array,
+    epsilon: number,
+    fee: number
+  ): Promise<string> {
+    this.ensureWalletConnected();
+
+    const operatorTokenAccount = await this.getOrCreateTokenAccount(this.wallet.publicKey);
+    const userTokenAccount = await this.getOrCreateTokenAccount(userAddress);
+    const [participantPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('participant'), userAddress.toBuffer(), dataPoolAddress.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [privacyLedgerPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('privacy-ledger'), dataPoolAddress.toBuffer(), userAddress.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [treasuryPda] = web3.PublicKey.findProgramAddressSync([Buffer.from('treasury')], GREYLOCKER_PROGRAM_ID);
+
+    const tx = await this.program.methods
+      .recordQuery(Array.from(queryHash), toMilliEpsilon(epsilon), new BN(fee * 10 ** 9))
+      .accounts({
+        greylockerState: this.greylockerState,
+        greyMint: GREY_TOKEN_MINT,
+        treasury: treasuryPda,
+        dataPool: dataPoolAddress,
+        participant: participantPda,
+        privacyLedger: privacyLedgerPda,
+        userTokenAccount,
+        operatorTokenAccount,
+        user: userAddress,
+        authority: this.wallet.publicKey,
+        dataPoolCreator,
+        systemProgram: web3.SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    console.log(`Recorded ε=${epsilon} query against ${userAddress.toBase58()} | Tx: ${tx}`);
+    return tx;
+  }
+
+  // Leave a data pool—withdraw consent and reclaim rent; anything owed moves to a settlement record
+  async leaveDataPool(dataPoolAddress: web3.PublicKey, dataPoolCreator: web3.PublicKey): Promise<string> {
+    this.ensureWalletConnected();
+
+    const [participantPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('participant'), this.wallet.publicKey.toBuffer(), dataPoolAddress.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [settlementPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('pool-settlement'), dataPoolAddress.toBuffer(), this.wallet.publicKey.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .leaveDataPool()
+      .accounts({
+        dataPool: dataPoolAddress,
+        participant: participantPda,
+        poolSettlement: settlementPda,
+        user: this.wallet.publicKey,
+        dataPoolCreator,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    console.log(`Left data pool ${dataPoolAddress.toBase58()}—consent withdrawn | Tx: ${tx}`);
+    return tx;
+  }
+
+  // Settle a departed contributor's accepted work into their settlement record (anyone may call)
+  async settleContribution(contributionAddress: web3.PublicKey): Promise<string> {
+    this.ensureWalletConnected();
+
+    const contribution: any = await this.program.account.contribution.fetch(contributionAddress);
+    const [settlementPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('pool-settlement'), contribution.pool.toBuffer(), contribution.contributor.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .settleContribution()
+      .accounts({
+        dataPool: contribution.pool,
+        contribution: contributionAddress,
+        participant: contribution.participant,
+        poolSettlement: settlementPda,
+        payer: this.wallet.publicKey,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    console.log(`Settled contribution ${contributionAddress.toBase58()} | Tx: ${tx}`);
+    return tx;
+  }
+
+  // Claim what a pool still owes after leaving; the record closes once it's paid off
+  async claimPoolSettlement(
+    dataPoolAddress: web3.PublicKey,
+    dataPoolCreator: web3.PublicKey,
+    budgetVault: web3.PublicKey | null = null
+  ): Promise<string> {
+    this.ensureWalletConnected();
+
+    const userTokenAccount = await this.getOrCreateTokenAccount(this.wallet.publicKey);
+    const [settlementPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('pool-settlement'), dataPoolAddress.toBuffer(), this.wallet.publicKey.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [treasuryPda] = web3.PublicKey.findProgramAddressSync([Buffer.from('treasury')], GREYLOCKER_PROGRAM_ID);
+    const [rewardReservePda] = web3.PublicKey.findProgramAddressSync([Buffer.from('reward-reserve')], GREYLOCKER_PROGRAM_ID);
+    const rewardReserve: any = await this.program.account.rewardReserve.fetch(rewardReservePda);
+
+    const tx = await this.program.methods
+      .claimPoolSettlement()
+      .accounts({
+        greylockerState: this.greylockerState,
+        greyMint: GREY_TOKEN_MINT,
+        treasury: treasuryPda,
+        dataPool: dataPoolAddress,
+        poolSettlement: settlementPda,
+        rewardReserve: rewardReservePda,
+        reserveVault: rewardReserve.vault,
+        budgetVault,
+        userTokenAccount,
+        user: this.wallet.publicKey,
+        dataPoolCreator,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    console.log(`Claimed settlement from data pool ${dataPoolAddress.toBase58()} | Tx: ${tx}`);
+    return tx;
+  }
+
+  // Submit a contribution—commit this epoch’s data hash for validators to check
+  async submitContribution(
+    poolAddress: web3.PublicKey,
+    dataPoolCreator: web3.PublicKey,
+    epoch: number,
+    dataHash: Uint8Array,
+    dataPoints: number
+  ): Promise<string> {
+    this.ensureWalletConnected();
+
+    const [participantPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('participant'), this.wallet.publicKey.toBuffer(), poolAddress.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+    const [contributionPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('contribution'), participantPda.toBuffer(), new BN(epoch).toArrayLike(Buffer, 'le', 8)],
+      GREYLOCKER_PROGRAM_ID
+    );
+
+    const tx = await this.program.methods
+      .submitContribution(new BN(epoch), Array.from(dataHash), new BN(dataPoints))
+      .accounts({
+        greylockerState: this.greylockerState,
+        dataPool: poolAddress,
+        participant: participantPda,
+        contribution: contributionPda,
+        user: this.wallet.publicKey,
+        dataPoolCreator,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    console.log(`Submitted ${dataPoints} data points for epoch ${epoch} | Tx: ${tx}`);
+    return tx;
+  }
+
+  // Attest to a contribution—DataValidators vouch for the data or reject it
+  async attestContribution(
+    contributionAddress: web3.PublicKey,
+    validatorStakeAccount: web3.PublicKey,
+    approve: boolean
+  ): Promise<string> {
+    this.ensureWalletConnected();
+
+    const [attestationPda] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('attestation'), contributionAddress.toBuffer(), this.wallet.publicKey.toBuffer()],
+      GREYLOCKER_PROGRAM_ID
+    );
+
+    const contribution: any = await this.program.account.contribution.fetch(contributionAddress);
+
+    const tx = await this.program.methods
+      .attestContribution(approve)
+      .accounts({
+        greylockerState: this.greylockerState,
+        contribution: contributionAddress,
+        dataPool: contribution.pool,
+        validatorStakeAccount,
+        attestation: attestationPda,
+        validator: this.wallet.publicKey,
+        systemProgram: web3.SystemProgram.programId,
+      })
+      .rpc();
+    console.log(`${approve ? 'Attested to' : 'Rejected'} contribution ${contributionAddress.toBase58()} | Tx: ${tx}`);
+    return tx;
+  }
+
+  // Pay data reward—settle an accepted contribution once its challenge period ends (anyone may call)
   async payDataReward(
     userAddress: web3.PublicKey,
     poolAddress: web3.PublicKey,
-    amount: number
+    contributionAddress: web3.PublicKey
   ): Promise<string> {
     this.ensureWalletConnected();
 
-    const amountBN = new BN(amount * 10 ** 9);
     const userTokenAccount = await this.getOrCreateTokenAccount(userAddress);
     const [participantPda] = web3.PublicKey.findProgramAddressSync(
       [Buffer.from('participant'), userAddress.toBuffer(), poolAddress.toBuffer()],
       GREYLOCKER_PROGRAM_ID
     );
 
+    const [treasuryPda] = web3.PublicKey.findProgramAddressSync([Buffer.from('treasury')], GREYLOCKER_PROGRAM_ID);
+
     const tx = await this.program.methods
-      .payDataReward(amountBN)
+      .payDataReward()
       .accounts({
         greylockerState: this.greylockerState,
-        caller: this.wallet.publicKey,
-        vaultAuthority: this.wallet.publicKey, // Replace with actual vault authority in production
         user: userAddress,
         userTokenAccount,
         dataPool: poolAddress,
         participant: participantPda,
+        contribution: contributionAddress,
         greyMint: GREY_TOKEN_MINT,
+        treasury: treasuryPda,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
       .rpc();
-    console.log(`Paid ${amount} GREY to ${userAddress.toBase58()} for pool ${poolAddress.toBase58()} | Tx: ${tx}`);
+    console.log(`Paid contribution ${contributionAddress.toBase58()} to ${userAddress.toBase58()} | Tx: ${tx}`);
     return tx;
   }
 
@@ -3,6 +3,7 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use greylocker::program::Greylocker;
 use solana_program::{
     program::{invoke, invoke_signed},
     system_instruction,
@@ -252,6 +253,7 @@ pub mod greylocker_vault {
         data_type_indices: Vec<u8>,
         duration: i64,
         access_fee: u64,
+        min_service_reputation: u8,
     ) -> Result<()> {
         let vault = &mut ctx.accounts.vault;
         let access_grant = &mut ctx.accounts.access_grant;
@@ -263,7 +265,34 @@ pub mod greylocker_vault {
         let clock = Clock::get()?;
         let current_time = clock.unix_timestamp;
 
-        // TODO: CPI to main Greylocker program to validate service_provider and transfer access_fee
+        // CPI to main Greylocker program: validates the staked service provider (and its
+        // reputation floor), moves access_fee (less the protocol fee) to the vault owner and writes the linked AccessRecord
+        greylocker::cpi::settle_vault_access(
+            CpiContext::new(
+                ctx.accounts.greylocker_program.to_account_info(),
+                greylocker::cpi::accounts::SettleVaultAccess {
+                    greylocker_state: ctx.accounts.greylocker_state.to_account_info(),
+                    grey_mint: ctx.accounts.grey_mint.to_account_info(),
+                    treasury: ctx.accounts.treasury.to_account_info(),
+                    service_provider: ctx.accounts.service_provider.to_account_info(),
+                    service_stake_account: ctx.accounts.service_stake_account.to_account_info(),
+                    access_record: ctx.accounts.access_record.to_account_info(),
+                    service_token_account: ctx.accounts.service_token_account.to_account_info(),
+                    user_token_account: ctx.accounts.user_token_account.to_account_info(),
+                    service_owner: ctx.accounts.service_owner.to_account_info(),
+                    user: ctx.accounts.user.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+            ),
+            access_grant.key(),
+            access_fee,
+            greylocker::DataType::Custom(String::from("vault")),
+            duration,
+            min_service_reputation,
+        )?;
+
         access_grant.vault = vault.key();
         access_grant.service_provider = ctx.accounts.service_provider.key();
         access_grant.granted_at = current_time;
@@ -272,6 +301,7 @@ pub mod greylocker_vault {
         access_grant.revoked = false;
         access_grant.data_type_indices = data_type_indices;
         access_grant.access_fee = access_fee;
+        access_grant.access_record = ctx.accounts.access_record.key();
         access_grant.bump = *ctx.bumps.get("access_grant").unwrap();
 
         emit!(AccessGrantedEvent {
@@ -429,12 +459,13 @@ pub struct AccessGrant {
     pub revoked: bool,                // Revocation state (1 byte)
     pub data_type_indices: Vec<u8>,   // Allowed types (4 + 32 bytes = 36 bytes)
     pub access_fee: u64,              // GREY paid (8 bytes)
+    pub access_record: Pubkey,        // Linked Greylocker AccessRecord (32 bytes)
     pub bump: u8,                     // PDA bump seed (1 byte)
 }
 
 impl AccessGrant {
-    // Space: 8 (discriminator) + 32 + 32 + 8 + 8 + 1 + 36 + 8 + 1 = 134 bytes
-    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 1 + 36 + 8 + 1;
+    // Space: 8 (discriminator) + 32 + 32 + 8 + 8 + 1 + 36 + 8 + 32 + 1 = 166 bytes
+    pub const SPACE: usize = 8 + 32 + 32 + 8 + 8 + 1 + 36 + 8 + 32 + 1;
 }
 
 // ----- ENUM TYPES -----
@@ -617,8 +648,13 @@ pub struct GrantAccess<'info> {
         constraint = vault.owner == user.key() @ GreylockerVaultError::NotVaultOwner
     )]
     pub vault: Account<'info, Vault>,
-    /// CHECK: To be validated via CPI to main Greylocker program
+    /// CHECK: Validated as a registered ServiceProvider PDA by the Greylocker CPI
+    #[account(mut)]
     pub service_provider: AccountInfo<'info>,
+    /// CHECK: Validated as the provider's Service stake by the Greylocker CPI
+    pub service_stake_account: AccountInfo<'info>,
+    #[account(mut)]
+    pub service_owner: Signer<'info>,
     #[account(
         init,
         payer = user,
@@ -627,7 +663,26 @@ pub struct GrantAccess<'info> {
         bump
     )]
     pub access_grant: Account<'info, AccessGrant>,
+    /// CHECK: Created by the Greylocker CPI, seeded by the provider's next grant id
+    #[account(mut)]
+    pub access_record: AccountInfo<'info>,
+    /// CHECK: Greylocker global state, validated by the Greylocker CPI
+    #[account(mut)]
+    pub greylocker_state: AccountInfo<'info>,
+    /// CHECK: GREY mint, validated by the Greylocker CPI (protocol fee burns reduce its supply)
+    #[account(mut)]
+    pub grey_mint: AccountInfo<'info>,
+    /// CHECK: Protocol treasury, validated by the Greylocker CPI
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+    #[account(mut)]
+    pub service_token_account: Account<'info, TokenAccount>,
+    #[account(mut, constraint = user_token_account.owner == user.key() @ GreylockerVaultError::NotVaultOwner)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    pub greylocker_program: Program<'info, Greylocker>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
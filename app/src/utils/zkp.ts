@@ -53,6 +53,8 @@ pub mod greylocker_zkp {
         require!(verification_key.len() <= 1024, GreylockerZKPError::VerificationKeyTooLarge);
         require!(description.len() <= 200, GreylockerZKPError::DescriptionTooLong);
         require!(registry.proof_count < u16::MAX, GreylockerZKPError::TooManyProofs);
+        groth16::public_input_count(&verification_key)
+            .map_err(|_| GreylockerZKPError::InvalidVerificationKey)?;
 
         let clock = Clock::get()?;
         registry.last_updated = clock.unix_timestamp;
@@ -141,12 +143,13 @@ pub mod greylocker_zkp {
         let clock = Clock::get()?;
         let current_time = clock.unix_timestamp;
 
-        // Placeholder for real ZKP verification (e.g., Groth16)
-        let is_valid = mock_verify_proof(
+        // Groth16 over BN254 via the alt_bn128 syscalls
+        let is_valid = groth16::verify(
             &vk_account.verification_key,
             &proof_submission.proof_data,
             &proof_submission.public_inputs,
-        );
+        )
+        .map_err(GreylockerZKPError::from)?;
         require!(is_valid, GreylockerZKPError::InvalidProof);
 
         proof_submission.verified = true;
@@ -303,12 +306,6 @@ pub mod greylocker_zkp {
 
         Ok(())
     }
-
-    // Mock verification function—placeholder for real ZKP logic
-    fn mock_verify_proof(_vk: &[u8], _proof: &[u8], _inputs: &[Vec<u8>]) -> bool {
-        // TODO: Replace with real Groth16 or SNARK verification (e.g., via arkworks)
-        true
-    }
 }
 
 // ----- ACCOUNT STRUCTURES -----
@@ -657,6 +654,26 @@ pub enum GreylockerZKPError {
     TooManyProofs,
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
+    #[msg("Proof data is not a compressed Groth16 proof")]
+    MalformedProof,
+    #[msg("Public inputs do not match the verification key")]
+    PublicInputMismatch,
+    #[msg("Public input is not a canonical field element")]
+    InvalidPublicInput,
+    #[msg("alt_bn128 syscall failed")]
+    CurveOperationFailed,
+}
+
+impl From<groth16::Groth16Error> for GreylockerZKPError {
+    fn from(err: groth16::Groth16Error) -> Self {
+        match err {
+            groth16::Groth16Error::MalformedVerificationKey => GreylockerZKPError::InvalidVerificationKey,
+            groth16::Groth16Error::MalformedProof => GreylockerZKPError::MalformedProof,
+            groth16::Groth16Error::PublicInputCountMismatch => GreylockerZKPError::PublicInputMismatch,
+            groth16::Groth16Error::InvalidPublicInput => GreylockerZKPError::InvalidPublicInput,
+            groth16::Groth16Error::SyscallFailed => GreylockerZKPError::CurveOperationFailed,
+        }
+    }
 }
 
 // ----- EVENTS -----
@@ -725,3 +742,285 @@ pub struct VerificationKeyReactivatedEvent {
     pub verification_key_account: Pubkey,
     pub reactivated_at: i64,
 }
+
+// ----- GROTH16 VERIFIER -----
+
+/// Groth16 verification over BN254, powered by Solana's alt_bn128 syscalls.
+///
+/// Keys, proofs and public inputs use the arkworks `serialize_compressed`
+/// layout (the same bytes snarkjs artifacts produce once run through
+/// ark-circom): little-endian coordinates with the point flags packed into
+/// the top bits of the final byte. The syscalls want big-endian,
+/// uncompressed points, so everything is flipped and decompressed first.
+pub mod groth16 {
+    use solana_program::alt_bn128::compression::prelude::{
+        alt_bn128_g1_decompress, alt_bn128_g2_decompress,
+    };
+    use solana_program::alt_bn128::prelude::{
+        alt_bn128_addition, alt_bn128_multiplication, alt_bn128_pairing,
+    };
+
+    pub const G1_COMPRESSED_LEN: usize = 32;
+    pub const G2_COMPRESSED_LEN: usize = 64;
+    pub const SCALAR_LEN: usize = 32;
+    pub const PROOF_LEN: usize = G1_COMPRESSED_LEN + G2_COMPRESSED_LEN + G1_COMPRESSED_LEN;
+    // alpha_g1 + beta_g2 + gamma_g2 + delta_g2 + u64 length prefix of the IC vector
+    pub const VK_HEADER_LEN: usize = G1_COMPRESSED_LEN + 3 * G2_COMPRESSED_LEN + 8;
+
+    const G1_LEN: usize = 64;
+    const G2_LEN: usize = 128;
+
+    /// BN254 base field modulus q (big-endian), used to negate proof.A.
+    const BASE_FIELD_MODULUS: [u8; 32] = [
+        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+        0x97, 0x81, 0x6a, 0x91, 0x68, 0x71, 0xca, 0x8d, 0x3c, 0x20, 0x8c, 0x16, 0xd8, 0x7c, 0xfd, 0x47,
+    ];
+
+    /// BN254 scalar field modulus r (big-endian); public inputs must be below it.
+    const SCALAR_FIELD_MODULUS: [u8; 32] = [
+        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+        0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+    ];
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Groth16Error {
+        MalformedVerificationKey,
+        MalformedProof,
+        PublicInputCountMismatch,
+        InvalidPublicInput,
+        SyscallFailed,
+    }
+
+    /// Decompressed verifying key, every point in syscall (big-endian) form.
+    pub struct VerifyingKey {
+        pub alpha_g1: [u8; G1_LEN],
+        pub beta_g2: [u8; G2_LEN],
+        pub gamma_g2: [u8; G2_LEN],
+        pub delta_g2: [u8; G2_LEN],
+        pub ic: Vec<[u8; G1_LEN]>,
+    }
+
+    /// Decompressed proof, every point in syscall (big-endian) form.
+    pub struct Proof {
+        pub a: [u8; G1_LEN],
+        pub b: [u8; G2_LEN],
+        pub c: [u8; G1_LEN],
+    }
+
+    /// Number of public inputs a serialized key expects, checking only its shape.
+    /// Cheap enough to run when a key is registered.
+    pub fn public_input_count(vk_bytes: &[u8]) -> Result<usize, Groth16Error> {
+        if vk_bytes.len() < VK_HEADER_LEN {
+            return Err(Groth16Error::MalformedVerificationKey);
+        }
+        let mut len_bytes = [0u8; 8];
+        len_bytes.copy_from_slice(&vk_bytes[VK_HEADER_LEN - 8..VK_HEADER_LEN]);
+        let ic_len = usize::try_from(u64::from_le_bytes(len_bytes))
+            .map_err(|_| Groth16Error::MalformedVerificationKey)?;
+        let expected_len = ic_len
+            .checked_mul(G1_COMPRESSED_LEN)
+            .and_then(|len| len.checked_add(VK_HEADER_LEN))
+            .ok_or(Groth16Error::MalformedVerificationKey)?;
+        if ic_len == 0 || vk_bytes.len() != expected_len {
+            return Err(Groth16Error::MalformedVerificationKey);
+        }
+        Ok(ic_len - 1)
+    }
+
+    pub fn parse_verifying_key(vk_bytes: &[u8]) -> Result<VerifyingKey, Groth16Error> {
+        let input_count = public_input_count(vk_bytes)?;
+        let err = Groth16Error::MalformedVerificationKey;
+
+        let mut offset = 0;
+        let alpha_g1 = decompress_g1(&vk_bytes[offset..offset + G1_COMPRESSED_LEN]).map_err(|_| err)?;
+        offset += G1_COMPRESSED_LEN;
+        let beta_g2 = decompress_g2(&vk_bytes[offset..offset + G2_COMPRESSED_LEN]).map_err(|_| err)?;
+        offset += G2_COMPRESSED_LEN;
+        let gamma_g2 = decompress_g2(&vk_bytes[offset..offset + G2_COMPRESSED_LEN]).map_err(|_| err)?;
+        offset += G2_COMPRESSED_LEN;
+        let delta_g2 = decompress_g2(&vk_bytes[offset..offset + G2_COMPRESSED_LEN]).map_err(|_| err)?;
+        offset = VK_HEADER_LEN;
+
+        let mut ic = Vec::with_capacity(input_count + 1);
+        for _ in 0..=input_count {
+            ic.push(decompress_g1(&vk_bytes[offset..offset + G1_COMPRESSED_LEN]).map_err(|_| err)?);
+            offset += G1_COMPRESSED_LEN;
+        }
+
+        Ok(VerifyingKey { alpha_g1, beta_g2, gamma_g2, delta_g2, ic })
+    }
+
+    pub fn parse_proof(proof_bytes: &[u8]) -> Result<Proof, Groth16Error> {
+        let err = Groth16Error::MalformedProof;
+        if proof_bytes.len() != PROOF_LEN {
+            return Err(err);
+        }
+        let a = decompress_g1(&proof_bytes[..G1_COMPRESSED_LEN]).map_err(|_| err)?;
+        let b = decompress_g2(&proof_bytes[G1_COMPRESSED_LEN..G1_COMPRESSED_LEN + G2_COMPRESSED_LEN])
+            .map_err(|_| err)?;
+        let c = decompress_g1(&proof_bytes[G1_COMPRESSED_LEN + G2_COMPRESSED_LEN..]).map_err(|_| err)?;
+        Ok(Proof { a, b, c })
+    }
+
+    /// Convert a little-endian scalar into the big-endian form the syscalls take,
+    /// rejecting anything outside the scalar field.
+    pub fn prepare_public_input(input: &[u8]) -> Result<[u8; SCALAR_LEN], Groth16Error> {
+        if input.len() != SCALAR_LEN {
+            return Err(Groth16Error::InvalidPublicInput);
+        }
+        let mut scalar = [0u8; SCALAR_LEN];
+        scalar.copy_from_slice(input);
+        scalar.reverse();
+        if scalar >= SCALAR_FIELD_MODULUS {
+            return Err(Groth16Error::InvalidPublicInput);
+        }
+        Ok(scalar)
+    }
+
+    /// Check a serialized proof against a serialized key and public inputs.
+    /// Returns `Ok(false)` for a well-formed proof that does not verify.
+    pub fn verify(vk_bytes: &[u8], proof_bytes: &[u8], public_inputs: &[Vec<u8>]) -> Result<bool, Groth16Error> {
+        let vk = parse_verifying_key(vk_bytes)?;
+        let proof = parse_proof(proof_bytes)?;
+        if public_inputs.len() + 1 != vk.ic.len() {
+            return Err(Groth16Error::PublicInputCountMismatch);
+        }
+
+        // vk_x = IC[0] + sum(input_i * IC[i + 1])
+        let mut vk_x = vk.ic[0];
+        for (input, ic_point) in public_inputs.iter().zip(vk.ic.iter().skip(1)) {
+            let scalar = prepare_public_input(input)?;
+            let mut mul_input = [0u8; G1_LEN + SCALAR_LEN];
+            mul_input[..G1_LEN].copy_from_slice(ic_point);
+            mul_input[G1_LEN..].copy_from_slice(&scalar);
+            let term = alt_bn128_multiplication(&mul_input).map_err(|_| Groth16Error::SyscallFailed)?;
+
+            let mut add_input = [0u8; G1_LEN * 2];
+            add_input[..G1_LEN].copy_from_slice(&vk_x);
+            add_input[G1_LEN..].copy_from_slice(&term);
+            let sum = alt_bn128_addition(&add_input).map_err(|_| Groth16Error::SyscallFailed)?;
+            vk_x.copy_from_slice(&sum);
+        }
+
+        // e(-A, B) * e(alpha, beta) * e(vk_x, gamma) * e(C, delta) == 1
+        let pairs: [(&[u8; G1_LEN], &[u8; G2_LEN]); 4] = [
+            (&negate_g1(&proof.a), &proof.b),
+            (&vk.alpha_g1, &vk.beta_g2),
+            (&vk_x, &vk.gamma_g2),
+            (&proof.c, &vk.delta_g2),
+        ];
+        let mut pairing_input = Vec::with_capacity(pairs.len() * (G1_LEN + G2_LEN));
+        for (g1, g2) in pairs.iter() {
+            pairing_input.extend_from_slice(&g1[..]);
+            pairing_input.extend_from_slice(&g2[..]);
+        }
+        let result = alt_bn128_pairing(&pairing_input).map_err(|_| Groth16Error::SyscallFailed)?;
+
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        Ok(result.as_slice() == one)
+    }
+
+    fn decompress_g1(compressed: &[u8]) -> Result<[u8; G1_LEN], Groth16Error> {
+        let mut be = [0u8; G1_COMPRESSED_LEN];
+        be.copy_from_slice(compressed);
+        be.reverse();
+        alt_bn128_g1_decompress(&be).map_err(|_| Groth16Error::SyscallFailed)
+    }
+
+    fn decompress_g2(compressed: &[u8]) -> Result<[u8; G2_LEN], Groth16Error> {
+        let mut be = [0u8; G2_COMPRESSED_LEN];
+        be.copy_from_slice(compressed);
+        be.reverse();
+        alt_bn128_g2_decompress(&be).map_err(|_| Groth16Error::SyscallFailed)
+    }
+
+    /// Negate a G1 point by replacing y with q - y (the identity stays put).
+    fn negate_g1(point: &[u8; G1_LEN]) -> [u8; G1_LEN] {
+        let mut negated = *point;
+        if point[32..].iter().all(|byte| *byte == 0) {
+            return negated;
+        }
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let mut diff = BASE_FIELD_MODULUS[i] as i16 - point[32 + i] as i16 - borrow;
+            borrow = if diff < 0 {
+                diff += 256;
+                1
+            } else {
+                0
+            };
+            negated[32 + i] = diff as u8;
+        }
+        negated
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        // Offline vectors for the circuit `y = x^2, z = x^3` with private x = 3,
+        // produced by ark-groth16 0.4 (`circuit_specific_setup` + `prove`) and
+        // serialized with `serialize_compressed`. Mirrored in tests/fixtures/groth16-cube.json.
+        const VK: &str = "d4ff40ac8327d26359df50a0b6f7e01819b289c561c04ba658b2a8925cd2b30aaeb497aebe29f66d6bd9e2bf1668145caed59f1817e3064a9208f28d6bdced00bcb2ca2a9e5ec2b1206c9d33a1d10eec823e7057cc9c6a99ddee3265d396598c802dd06fa25790c4a7cfc74580f7fd1354276fbed68852ea4a11e24b3883f105539a01865cf1944bdd54501dfa131375f8ba107327ed8d5f629c8ae0de5a4e2714af57815599b9b1ba7315ee977c88f709ad6d590840f07da01f7a20c1440500e67aa4db7109cb221aa6b0244040ab2de197fcf528524e82dcb0bd7b900424270300000000000000721b6ea8657e415832f68cc7edbef9f8c47426c822e4f9921c06779dde47bfaab48520e65f31e1486bb05f804ff75efde3f2d7df1d7ca1acfea08d435822939da77390d27197f084ea411409b924ab8c36d27841696268a3e2aff07b01d643ac";
+        const PROOF: &str = "5249e36b22a008ce4b2e1daefd6af5da78ad658e84c9434ae301e7fa0ebd1f0f8343573f75fb7c6d37e03581c1a8dcaa3313059d9fd4124f270660ef99f6700814222969dde2acf7f769f9dace911db1e8e3c3f63f0c4af41eecbf65bf5560072d620c2b0d42b38fcd165653f1bf795cbee7fde13a79ad1b5ddf94808e9d2a0b";
+
+        fn decode(hex: &str) -> Vec<u8> {
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+                .collect()
+        }
+
+        fn scalar(value: u64) -> Vec<u8> {
+            let mut bytes = vec![0u8; SCALAR_LEN];
+            bytes[..8].copy_from_slice(&value.to_le_bytes());
+            bytes
+        }
+
+        #[test]
+        fn accepts_valid_proof() {
+            let inputs = vec![scalar(9), scalar(27)];
+            assert_eq!(verify(&decode(VK), &decode(PROOF), &inputs), Ok(true));
+        }
+
+        #[test]
+        fn rejects_wrong_public_input() {
+            let inputs = vec![scalar(9), scalar(28)];
+            assert_eq!(verify(&decode(VK), &decode(PROOF), &inputs), Ok(false));
+        }
+
+        #[test]
+        fn rejects_tampered_proof() {
+            let mut proof = decode(PROOF);
+            // Swap A and C: both are valid curve points, but the pairing no longer balances.
+            let (a, rest) = proof.split_at_mut(G1_COMPRESSED_LEN);
+            a.swap_with_slice(&mut rest[G2_COMPRESSED_LEN..]);
+            let inputs = vec![scalar(9), scalar(27)];
+            assert_eq!(verify(&decode(VK), &proof, &inputs), Ok(false));
+        }
+
+        #[test]
+        fn rejects_malformed_inputs() {
+            let vk = decode(VK);
+            let proof = decode(PROOF);
+            assert_eq!(public_input_count(&vk), Ok(2));
+            assert_eq!(
+                verify(&vk[..vk.len() - 1], &proof, &[scalar(9), scalar(27)]).err(),
+                Some(Groth16Error::MalformedVerificationKey)
+            );
+            assert_eq!(
+                verify(&vk, &proof[..PROOF_LEN - 1], &[scalar(9), scalar(27)]).err(),
+                Some(Groth16Error::MalformedProof)
+            );
+            assert_eq!(
+                verify(&vk, &proof, &[scalar(9)]).err(),
+                Some(Groth16Error::PublicInputCountMismatch)
+            );
+            assert_eq!(
+                verify(&vk, &proof, &[scalar(9), vec![0xff; SCALAR_LEN]]).err(),
+                Some(Groth16Error::InvalidPublicInput)
+            );
+        }
+    }
+}
@@ -35,7 +35,7 @@ Greylocker’s architecture is a constellation of interlocking systems, each a v
 ### 4. Data Pools
 - **Purpose**: Anonymized data-sharing hubs where users opt-in to earn GREY rewards.
 - **Structure**: `DataPool` accounts define reward rates and data types (e.g., Browsing, Location), with `Participant` accounts tracking contributions.
-- **Mechanics**: Governance creates pools; users join and receive rewards via `pay_data_reward`.
+- **Mechanics**: Governance creates pools; users join and submit one hashed `Contribution` per epoch. DataValidator stakers attest to or reject it, accepted data earns `reward_rate` per data point via `pay_data_reward`, and validators who vouched for data governance later invalidates are slashed. Attesting takes the validator's own stake (delegations don't count), staked at least a week before the submission, and locks it until `release_attestation` once the data can no longer be struck down.
 - **Vibe**: Glowing data streams feeding the ecosystem, a marketplace of shadows and incentives.
 
 ### 5. Dispute Resolution and Slashing
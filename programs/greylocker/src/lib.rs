@@ -1,7 +1,8 @@
 // lib.rs - The beating heart of Greylocker, a privacy-first Solana ecosystem
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, Burn, MintTo};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, Burn, MintTo, CloseAccount};
 use solana_program::{
+    keccak,
     program::{invoke, invoke_signed},
     system_instruction,
     sysvar::{clock::Clock, rent::Rent},
@@ -10,6 +11,89 @@ use solana_program::{
 // Declare the program ID (replace with your actual deployed ID)
 declare_id!("GREY1ockrXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX");
 
+pub const MAX_CUSTOM_DATA_TYPE_LEN: usize = 32; // Longest DataType::Custom name an access record can hold
+
+// Fixed-point scale for reward-per-token indices
+pub const REWARD_INDEX_PRECISION: u128 = 1_000_000_000_000;
+pub const SECONDS_PER_YEAR: u128 = 365 * 24 * 60 * 60;
+
+// Governance council bounds
+pub const MAX_COUNCIL_SIGNERS: usize = 10;
+pub const MIN_GOVERNANCE_TIMELOCK: i64 = 24 * 60 * 60; // 1 day
+
+// Token-vote bounds
+pub const MIN_VOTING_PERIOD: i64 = 24 * 60 * 60;       // 1 day
+pub const MAX_VOTING_PERIOD: i64 = 30 * 24 * 60 * 60;  // 30 days
+pub const VOTE_BOOST_MAX_LOCK: i64 = 365 * 24 * 60 * 60; // Lock remaining for the full boost
+pub const VOTE_BOOST_MAX_BPS: u128 = 10_000;            // Full boost doubles the weight
+
+// Dispute arbitration
+pub const JURY_SIZE: u8 = 3;
+pub const APPEAL_JURY_SIZE: u8 = 7;
+pub const MAX_APPEAL_ROUNDS: u8 = 1;
+pub const DISPUTE_SEATING_PERIOD: i64 = 2 * 24 * 60 * 60;   // Jurors take their seats
+pub const DISPUTE_RESPONSE_PERIOD: i64 = 3 * 24 * 60 * 60;  // Accused posts counter-evidence
+pub const DISPUTE_VOTING_PERIOD: i64 = 3 * 24 * 60 * 60;    // Jurors vote
+pub const DISPUTE_APPEAL_PERIOD: i64 = 3 * 24 * 60 * 60;    // Losing side may appeal / other side may match the bond
+pub const MAX_EVIDENCE_LEN: usize = 200;
+pub const MAX_REGISTERED_JURORS: usize = 64;
+pub const JURY_DRAW_DELAY_SLOTS: u64 = 10;                  // Draw seed comes from a slot nobody has seen yet
+
+// Reporter anti-spam
+pub const REPORTER_RATE_WINDOW: i64 = 7 * 24 * 60 * 60;  // Rolling filing window
+pub const MAX_DISPUTES_PER_WINDOW: u32 = 3;
+pub const MAX_OPEN_DISPUTES_PER_REPORTER: u32 = 5;
+pub const REJECTED_BOND_BURN_BPS: u64 = 5_000;            // Burned share of a rejected reporter's bond; rest to the accused
+
+// Reputation engine
+pub const REPUTATION_BASE: u64 = 50;                         // Where every new provider starts
+pub const REPUTATION_FEE_UNIT: u64 = 1_000 * 1_000_000_000;  // 1 point per 1K GREY in access fees
+pub const REPUTATION_MAX_FEE_POINTS: u64 = 20;
+pub const REPUTATION_TENURE_DAYS_PER_POINT: u64 = 30;
+pub const REPUTATION_MAX_TENURE_POINTS: u64 = 15;
+pub const REPUTATION_MAX_VINDICATION_POINTS: u64 = 10;      // 2 points per dismissed dispute
+pub const REPUTATION_MAX_COMPLETION_POINTS: u64 = 10;       // 1 point per 10 access grants run to expiry
+pub const REPUTATION_PENALTY_HALF_LIFE: i64 = 90 * 24 * 60 * 60;
+pub const REPUTATION_MIN_ACCESS_FEE: u64 = 10 * 1_000_000_000; // Smaller grants pay out but earn no reputation
+pub const REPUTATION_MIN_ACCESS_DURATION: i64 = 24 * 60 * 60;
+
+// Unbonding
+pub const MAX_UNBONDING_PERIOD: i64 = 60 * 24 * 60 * 60; // 60 days
+
+// Lock tiers
+pub const MAX_LOCK_TIERS: usize = 8;
+pub const MAX_LOCK_PERIOD: i64 = 4 * 365 * 24 * 60 * 60; // 4 years
+pub const MAX_LOCK_MULTIPLIER_BPS: u16 = 30_000; // 3x rewards at most
+
+// Treasury
+pub const MIN_TREASURY_EPOCH: i64 = 24 * 60 * 60;        // 1 day
+pub const MAX_TREASURY_EPOCH: i64 = 365 * 24 * 60 * 60;  // 1 year
+
+// Protocol fees
+pub const MAX_PROTOCOL_FEE_BPS: u64 = 1_000; // Fees never take more than 10%
+
+// Compounding
+pub const MAX_COMPOUND_TIP_BPS: u64 = 500; // Crankers take at most 5% of what they compound
+
+// Airdrops
+pub const MAX_AIRDROP_LEAVES: u32 = 80_000; // Keeps the claim bitmap under 10KB
+
+// Data contributions
+pub const CONTRIBUTION_EPOCH_DURATION: i64 = 24 * 60 * 60;      // One submission per participant per epoch
+pub const CONTRIBUTION_ATTESTATION_PERIOD: i64 = 2 * 24 * 60 * 60; // Validators attest or reject
+pub const CONTRIBUTION_CHALLENGE_PERIOD: i64 = 3 * 24 * 60 * 60;   // Governance may invalidate before payout
+pub const CONTRIBUTION_QUORUM: u16 = 3;                          // Attestations needed for a verdict
+pub const MIN_ATTESTER_STAKE_AGE: i64 = 7 * 24 * 60 * 60;         // Stake must predate a submission by this much to attest
+pub const MAX_DATA_POINTS_PER_CONTRIBUTION: u64 = 100_000;
+
+// Data pools
+pub const MAX_POOL_NAME_LEN: usize = 32;        // The name seeds the pool PDA, and a seed is at most 32 bytes
+pub const MAX_POOL_DATA_TYPE_LEN: usize = 46;   // Pool strings fit their allotted space with the length prefix
+pub const MAX_POOL_DESCRIPTION_LEN: usize = 196;
+
+// Delegation
+pub const MAX_OPERATOR_COMMISSION_BPS: u16 = 5_000; // Operators keep at most half of delegator rewards
+
 #[program]
 pub mod greylocker {
     use super::*;
@@ -28,20 +112,27 @@ pub mod greylocker {
         state.max_supply = 1_000_000_000 * 10u64.pow(9); // 1B GREY, 9 decimals
         state.authority = ctx.accounts.initializer.key();
         state.governance_authority = ctx.accounts.initializer.key();
+        state.next_vesting_id = 0;
+        state.total_vesting_allocated = 0;
+        state.total_vesting_claimed = 0;
+        state.total_vesting_revoked = 0;
+        state.treasury_epoch_started_at = Clock::get()?.unix_timestamp;
+        state.treasury_spent_this_epoch = 0;
+        state.total_treasury_spent = 0;
         state.bump = bump;
 
         // Initialize governance parameters with defaults
-        state.governance_parameters = GovernanceParameters {
-            security_stake_minimum: 100 * 10u64.pow(9),         // 100 GREY
-            service_stake_minimum: 10_000 * 10u64.pow(9),      // 10K GREY
-            data_validator_stake_minimum: 5_000 * 10u64.pow(9), // 5K GREY
-            liquidity_stake_minimum: 1_000 * 10u64.pow(9),     // 1K GREY
-            security_reward_rate: 5,                           // 5% APR
-            data_validator_reward_rate: 10,                    // 10% APR
-            liquidity_reward_rate: 8,                          // 8% APR
-            slashing_percentage: 50,                           // 50% slash
-            early_unstake_penalty: 10,                         // 10% penalty
-            reward_update_interval: 7 * 24 * 60 * 60,          // 7 days in seconds
+        state.governance_parameters = default_governance_parameters();
+
+        // Longer locks earn more: none, 30, 90, 180 and 365 days
+        state.lock_tiers = default_lock_tiers();
+
+        // Every reward index starts at zero and accrues from genesis
+        state.reward_index = RewardIndex {
+            security: 0,
+            data_validator: 0,
+            liquidity: 0,
+            last_updated: Clock::get()?.unix_timestamp,
         };
 
         // Mint 20% of max supply to treasury (200M GREY)
@@ -74,30 +165,149 @@ pub mod greylocker {
         Ok(())
     }
 
+    /// Spend from the treasury—capped per epoch and tagged for the record!
+    /// Vesting and airdrop escrows draw on the same epoch cap, so no outflow slips past it.
+    pub fn treasury_spend(ctx: Context<TreasurySpend>, amount: u64, category: SpendCategory) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(
+            ctx.accounts.governance_authority.key() == ctx.accounts.greylocker_state.governance_authority,
+            GreylockerError::NotAuthorized
+        );
+        require!(amount > 0, GreylockerError::InvalidParameterValue);
+
+        let clock = Clock::get()?;
+        let spent = ctx.accounts.greylocker_state.charge_treasury(amount, clock.unix_timestamp)?;
+
+        let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
+        let signer = &[&state_seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.recipient.to_account_info(),
+                    authority: ctx.accounts.greylocker_state.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        emit!(TreasurySpendEvent {
+            recipient: ctx.accounts.recipient.key(),
+            amount,
+            category,
+            spent_this_epoch: spent,
+            epoch_started_at: ctx.accounts.greylocker_state.treasury_epoch_started_at,
+            authority: ctx.accounts.governance_authority.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Migrate a wallet-owned treasury to the program PDA—no more hot-wallet treasury!
+    /// For deployments initialized before the treasury became a PDA; the legacy account is emptied and closed.
+    /// State still in the launch layout is read as such, grown to the current size and filled with defaults.
+    /// Deliberately single-signer: the legacy treasury is the governance wallet's own token account, and
+    /// no council can be seated until the state is migrated.
+    pub fn migrate_treasury(ctx: Context<MigrateTreasury>) -> Result<()> {
+        let clock = Clock::get()?;
+        let state_info = ctx.accounts.greylocker_state.to_account_info();
+        let mut state = load_greylocker_state(&state_info, clock.unix_timestamp)?;
+        require!(
+            ctx.accounts.governance_authority.key() == state.governance_authority,
+            GreylockerError::NotAuthorized
+        );
+        require!(ctx.accounts.legacy_treasury.key() == state.treasury, GreylockerError::NotAuthorized);
+        require!(ctx.accounts.grey_mint.key() == state.grey_mint, GreylockerError::NotAuthorized);
+
+        let amount = ctx.accounts.legacy_treasury.amount;
+        if amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.legacy_treasury.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                        authority: ctx.accounts.governance_authority.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+        token::close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.legacy_treasury.to_account_info(),
+                destination: ctx.accounts.governance_authority.to_account_info(),
+                authority: ctx.accounts.governance_authority.to_account_info(),
+            },
+        ))?;
+
+        let legacy_treasury = state.treasury;
+        state.treasury = ctx.accounts.treasury.key();
+        state.treasury_epoch_started_at = clock.unix_timestamp;
+        state.treasury_spent_this_epoch = 0;
+        store_greylocker_state(
+            &state_info,
+            &ctx.accounts.governance_authority.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &state,
+        )?;
+
+        emit!(MigrateTreasuryEvent {
+            legacy_treasury,
+            treasury: state.treasury,
+            amount,
+            authority: ctx.accounts.governance_authority.key(),
+        });
+
+        Ok(())
+    }
+
     /// Stake GREY tokens—lock in your power and join the ecosystem!
+    /// Every call opens a fresh position, so one wallet can hold as many as it likes.
     pub fn stake(ctx: Context<Stake>, amount: u64, stake_type: StakeType, lock_period: i64) -> Result<()> {
         require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
 
         let state = &ctx.accounts.greylocker_state;
-        let min_stake = match stake_type {
-            StakeType::Security => state.governance_parameters.security_stake_minimum,
-            StakeType::Service => state.governance_parameters.service_stake_minimum,
-            StakeType::DataValidator => state.governance_parameters.data_validator_stake_minimum,
-            StakeType::Liquidity => state.governance_parameters.liquidity_stake_minimum,
-        };
-        require!(amount >= min_stake, GreylockerError::InsufficientStakeAmount);
+        require!(amount >= min_stake_for(state, &stake_type), GreylockerError::InsufficientStakeAmount);
 
+        let (lock_tier, reward_multiplier_bps) = lock_tier_for(&state.lock_tiers, lock_period)?;
         let clock = Clock::get()?;
+        let lock_until = clock.unix_timestamp
+            .checked_add(lock_period)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let stake_registry = &mut ctx.accounts.stake_registry;
+        if stake_registry.owner == Pubkey::default() {
+            stake_registry.owner = ctx.accounts.user.key();
+            stake_registry.next_position_id = 0;
+            stake_registry.open_positions = 0;
+            stake_registry.bump = *ctx.bumps.get("stake_registry").unwrap();
+        }
+
         let stake_account = &mut ctx.accounts.stake_account;
         stake_account.owner = ctx.accounts.user.key();
-        stake_account.stake_type = stake_type;
+        stake_account.position_id = stake_registry.next_position_id;
+        stake_account.stake_type = stake_type.clone();
         stake_account.amount = amount;
-        stake_account.lock_until = clock.unix_timestamp + lock_period;
+        stake_account.lock_until = lock_until;
+        stake_account.lock_tier = lock_tier;
+        stake_account.reward_multiplier_bps = reward_multiplier_bps;
         stake_account.last_reward_claim = clock.unix_timestamp;
         stake_account.accumulated_rewards = 0;
+        stake_account.reward_index_snapshot = current_reward_index(state, &stake_type, clock.unix_timestamp)?;
         stake_account.staked_at = clock.unix_timestamp;
+        stake_account.last_increase_at = clock.unix_timestamp;
         stake_account.bump = *ctx.bumps.get("stake_account").unwrap();
 
+        stake_registry.next_position_id = stake_registry.next_position_id
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        stake_registry.open_positions = stake_registry.open_positions
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
         token::transfer(
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
@@ -110,29 +320,299 @@ pub mod greylocker {
             amount,
         )?;
 
-        ctx.accounts.greylocker_state.total_staked = state.total_staked
+        ctx.accounts.greylocker_state.total_staked = ctx.accounts.greylocker_state.total_staked
             .checked_add(amount)
             .ok_or(GreylockerError::ArithmeticError)?;
 
         emit!(StakeEvent {
             user: ctx.accounts.user.key(),
-            stake_account: stake_account.key(),
+            stake_account: ctx.accounts.stake_account.key(),
+            position_id: ctx.accounts.stake_account.position_id,
             stake_type,
             amount,
-            lock_until: stake_account.lock_until,
+            lock_until,
+            lock_tier,
+            reward_multiplier_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Top up an existing position—double down on your commitment!
+    /// A locked position re-locks for its tier's full duration, so fresh GREY can't borrow an old lock's multiplier.
+    pub fn top_up_stake(ctx: Context<TopUpStake>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(amount > 0, GreylockerError::InvalidParameterValue);
+
+        let clock = Clock::get()?;
+        let state = &ctx.accounts.greylocker_state;
+        let stake_account = &mut ctx.accounts.stake_account;
+        settle_rewards(state, stake_account, clock.unix_timestamp)?;
+        match state.lock_tiers.get(stake_account.lock_tier as usize) {
+            Some(tier) if stake_account.lock_tier != 0 => {
+                let relocked_until = clock.unix_timestamp
+                    .checked_add(tier.duration)
+                    .ok_or(GreylockerError::ArithmeticError)?;
+                stake_account.lock_until = stake_account.lock_until.max(relocked_until);
+            },
+            _ => {
+                stake_account.lock_tier = 0;
+                stake_account.reward_multiplier_bps = 10_000;
+            },
+        }
+        stake_account.amount = stake_account.amount
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        stake_account.last_increase_at = clock.unix_timestamp;
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        ctx.accounts.greylocker_state.total_staked = ctx.accounts.greylocker_state.total_staked
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        emit!(TopUpStakeEvent {
+            user: ctx.accounts.user.key(),
+            stake_account: ctx.accounts.stake_account.key(),
+            amount,
+            new_amount: ctx.accounts.stake_account.amount,
+            lock_until: ctx.accounts.stake_account.lock_until,
+        });
+
+        Ok(())
+    }
+
+    /// Extend a position's lock—commit longer, earn more!
+    /// Rewards so far settle at the old multiplier; the new tier applies from here on.
+    pub fn extend_lock(ctx: Context<ExtendLock>, lock_period: i64) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let state = &ctx.accounts.greylocker_state;
+        let (lock_tier, reward_multiplier_bps) = lock_tier_for(&state.lock_tiers, lock_period)?;
+        let lock_until = clock.unix_timestamp
+            .checked_add(lock_period)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let stake_account = &mut ctx.accounts.stake_account;
+        require!(lock_until > stake_account.lock_until, GreylockerError::LockNotExtended);
+        require!(reward_multiplier_bps >= stake_account.reward_multiplier_bps, GreylockerError::LockNotExtended);
+
+        settle_rewards(state, stake_account, clock.unix_timestamp)?;
+        stake_account.lock_until = lock_until;
+        stake_account.lock_tier = lock_tier;
+        stake_account.reward_multiplier_bps = reward_multiplier_bps;
+
+        emit!(ExtendLockEvent {
+            user: ctx.accounts.user.key(),
+            stake_account: stake_account.key(),
+            lock_until,
+            lock_tier,
+            reward_multiplier_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Split a position in two—carve off a slice and keep the lock!
+    pub fn split_stake(ctx: Context<SplitStake>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let state = &ctx.accounts.greylocker_state;
+        let source = &mut ctx.accounts.source_stake_account;
+        require!(!juror_locked(source), GreylockerError::JurorStakeLocked);
+        require!(source.open_attestations == 0, GreylockerError::AttestationStakeLocked);
+        // Only the linked position gets slashed, so it can't shed stake into a fresh one mid-dispute
+        if let Some(service_provider) = read_service_provider(&ctx.accounts.service_provider)? {
+            require!(
+                service_provider.stake_account != source.key() || service_provider.open_disputes == 0,
+                GreylockerError::ServiceUnderDispute
+            );
+        }
+        let min_stake = min_stake_for(state, &source.stake_type);
+        let remaining = source.amount
+            .checked_sub(amount)
+            .ok_or(GreylockerError::InsufficientStakeAmount)?;
+        require!(amount >= min_stake && remaining >= min_stake, GreylockerError::InsufficientStakeAmount);
+
+        settle_rewards(state, source, clock.unix_timestamp)?;
+        source.amount = remaining;
+
+        let stake_registry = &mut ctx.accounts.stake_registry;
+        let new_position = &mut ctx.accounts.new_stake_account;
+        new_position.owner = ctx.accounts.user.key();
+        new_position.position_id = stake_registry.next_position_id;
+        new_position.stake_type = source.stake_type.clone();
+        new_position.amount = amount;
+        new_position.lock_until = source.lock_until;
+        new_position.lock_tier = source.lock_tier;
+        new_position.auto_compound = source.auto_compound;
+        new_position.reward_multiplier_bps = source.reward_multiplier_bps;
+        new_position.last_reward_claim = clock.unix_timestamp;
+        new_position.accumulated_rewards = 0;
+        new_position.reward_index_snapshot = source.reward_index_snapshot;
+        new_position.staked_at = source.staked_at;
+        new_position.last_increase_at = clock.unix_timestamp; // Fresh weight—can't re-vote open proposals
+        new_position.bump = *ctx.bumps.get("new_stake_account").unwrap();
+
+        stake_registry.next_position_id = stake_registry.next_position_id
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        stake_registry.open_positions = stake_registry.open_positions
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let source_key = source.key();
+        let owner = source.owner;
+        let position_id = source.position_id.to_le_bytes();
+        let bump = source.bump;
+        let stake_seeds = &[b"stake-account".as_ref(), owner.as_ref(), position_id.as_ref(), &[bump]];
+        let signer = &[&stake_seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.source_stake_vault.to_account_info(),
+                    to: ctx.accounts.new_stake_vault.to_account_info(),
+                    authority: ctx.accounts.source_stake_account.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        emit!(SplitStakeEvent {
+            user: owner,
+            source_stake_account: source_key,
+            new_stake_account: ctx.accounts.new_stake_account.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Merge two positions into one—consolidate your power!
+    /// The source position is emptied and closed; the longer of the two locks wins.
+    pub fn merge_stakes(ctx: Context<MergeStakes>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let state = &ctx.accounts.greylocker_state;
+        let source = &mut ctx.accounts.source_stake_account;
+        let destination = &mut ctx.accounts.destination_stake_account;
+        require!(source.stake_type == destination.stake_type, GreylockerError::InvalidStakeType);
+        require!(source.unbonding_amount == 0, GreylockerError::UnbondingInProgress);
+        require!(source.open_unbondings == 0, GreylockerError::UnbondingInProgress);
+        require!(!source.delegation_open, GreylockerError::DelegationPoolOpen);
+        require!(!juror_locked(source), GreylockerError::JurorStakeLocked);
+        require!(source.open_attestations == 0, GreylockerError::AttestationStakeLocked);
+        // Disputes against the service execute against its linked position, so that one can't be merged away
+        if let Some(service_provider) = read_service_provider(&ctx.accounts.service_provider)? {
+            require!(service_provider.stake_account != source.key(), GreylockerError::ServiceStakeLinked);
+            require!(
+                service_provider.stake_account != destination.key() || service_provider.open_disputes == 0,
+                GreylockerError::ServiceUnderDispute
+            );
+        }
+
+        settle_rewards(state, source, clock.unix_timestamp)?;
+        settle_rewards(state, destination, clock.unix_timestamp)?;
+
+        let merged_amount = source.amount;
+        destination.amount = destination.amount
+            .checked_add(merged_amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        destination.accumulated_rewards = destination.accumulated_rewards
+            .checked_add(source.accumulated_rewards)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        if source.lock_until > destination.lock_until {
+            destination.lock_until = source.lock_until;
+            destination.lock_tier = source.lock_tier;
+            destination.reward_multiplier_bps = source.reward_multiplier_bps;
+        }
+        destination.last_increase_at = clock.unix_timestamp;
+        source.amount = 0;
+        source.accumulated_rewards = 0;
+
+        let stake_registry = &mut ctx.accounts.stake_registry;
+        stake_registry.open_positions = stake_registry.open_positions
+            .checked_sub(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let source_key = source.key();
+        let owner = source.owner;
+        let position_id = source.position_id.to_le_bytes();
+        let bump = source.bump;
+        let stake_seeds = &[b"stake-account".as_ref(), owner.as_ref(), position_id.as_ref(), &[bump]];
+        let signer = &[&stake_seeds[..]];
+
+        let vault_balance = ctx.accounts.source_stake_vault.amount;
+        if vault_balance > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.source_stake_vault.to_account_info(),
+                        to: ctx.accounts.destination_stake_vault.to_account_info(),
+                        authority: ctx.accounts.source_stake_account.to_account_info(),
+                    },
+                    signer,
+                ),
+                vault_balance,
+            )?;
+        }
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.source_stake_vault.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: ctx.accounts.source_stake_account.to_account_info(),
+            },
+            signer,
+        ))?;
+
+        emit!(MergeStakesEvent {
+            user: owner,
+            source_stake_account: source_key,
+            destination_stake_account: ctx.accounts.destination_stake_account.key(),
+            merged_amount,
+            new_amount: ctx.accounts.destination_stake_account.amount,
         });
 
         Ok(())
     }
 
-    /// Unstake GREY tokens—claim your freedom, but beware the penalty!
-    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    /// Request an unstake—start the cooldown, but beware the penalty!
+    /// The early-unstake penalty burns now; the rest waits out the unbonding period and stays slashable.
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
         require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(amount > 0, GreylockerError::InvalidParameterValue);
 
         let clock = Clock::get()?;
         let stake_account = &mut ctx.accounts.stake_account;
         require!(stake_account.owner == ctx.accounts.user.key(), GreylockerError::NotAuthorized);
         require!(amount <= stake_account.amount, GreylockerError::InsufficientStakeAmount);
+        require!(!juror_locked(stake_account), GreylockerError::JurorStakeLocked);
+        require!(stake_account.open_attestations == 0, GreylockerError::AttestationStakeLocked);
+        if stake_account.delegation_open {
+            // Delegated GREY doesn't count here—the operator keeps its own skin in the game
+            let remaining = stake_account.amount - amount;
+            require!(
+                remaining >= min_stake_for(&ctx.accounts.greylocker_state, &stake_account.stake_type),
+                GreylockerError::SelfBondTooLow
+            );
+        }
 
         let early_unstake = clock.unix_timestamp < stake_account.lock_until;
         let penalty = if early_unstake {
@@ -145,36 +625,45 @@ pub mod greylocker {
         };
         let return_amount = amount.checked_sub(penalty).ok_or(GreylockerError::ArithmeticError)?;
 
-        // Calculate and update rewards before unstaking
-        let rewards = Self::calculate_rewards(&ctx.accounts.greylocker_state, stake_account, clock.unix_timestamp)?;
-        stake_account.accumulated_rewards = stake_account.accumulated_rewards
-            .checked_add(rewards)
-            .ok_or(GreylockerError::ArithmeticError)?;
-        stake_account.last_reward_claim = clock.unix_timestamp;
+        // Calculate and update rewards before the balance leaves the active stake
+        settle_rewards(&ctx.accounts.greylocker_state, stake_account, clock.unix_timestamp)?;
 
         stake_account.amount = stake_account.amount
             .checked_sub(amount)
             .ok_or(GreylockerError::ArithmeticError)?;
+        stake_account.unbonding_amount = stake_account.unbonding_amount
+            .checked_add(return_amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
         ctx.accounts.greylocker_state.total_staked = ctx.accounts.greylocker_state.total_staked
             .checked_sub(amount)
             .ok_or(GreylockerError::ArithmeticError)?;
 
-        let stake_seeds = &[b"stake-account", stake_account.owner.as_ref(), &[stake_account.bump]];
+        let available_at = clock.unix_timestamp
+            .checked_add(ctx.accounts.greylocker_state.governance_parameters.unbonding_period)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let request_id = stake_account.next_unbonding_id;
+        stake_account.next_unbonding_id = request_id
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        stake_account.open_unbondings = stake_account.open_unbondings
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let unbonding_request = &mut ctx.accounts.unbonding_request;
+        unbonding_request.stake_account = stake_account.key();
+        unbonding_request.owner = stake_account.owner;
+        unbonding_request.request_id = request_id;
+        unbonding_request.amount = return_amount;
+        unbonding_request.requested_at = clock.unix_timestamp;
+        unbonding_request.available_at = available_at;
+        unbonding_request.bump = *ctx.bumps.get("unbonding_request").unwrap();
+
+        let stake_key = stake_account.key();
+        let owner = stake_account.owner;
+        let position_id = stake_account.position_id.to_le_bytes();
+        let bump = stake_account.bump;
+        let stake_seeds = &[b"stake-account".as_ref(), owner.as_ref(), position_id.as_ref(), &[bump]];
         let signer = &[&stake_seeds[..]];
 
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.stake_vault.to_account_info(),
-                    to: ctx.accounts.user_token_account.to_account_info(),
-                    authority: ctx.accounts.stake_account.to_account_info(),
-                },
-                signer,
-            ),
-            return_amount,
-        )?;
-
         if penalty > 0 {
             token::burn(
                 CpiContext::new_with_signer(
@@ -193,19 +682,108 @@ pub mod greylocker {
                 .ok_or(GreylockerError::ArithmeticError)?;
         }
 
-        emit!(UnstakeEvent {
+        emit!(RequestUnstakeEvent {
             user: ctx.accounts.user.key(),
-            stake_account: stake_account.key(),
+            stake_account: stake_key,
             amount,
             penalty_amount: penalty,
             return_amount,
             early_unstake,
+            available_at,
+            request_id,
+        });
+
+        Ok(())
+    }
+
+    /// Withdraw unbonded GREY—cooldown served, freedom claimed!
+    /// Blocked while any dispute against the owner's service is still open. Closes the position once it's emptied.
+    pub fn withdraw_unbonded(ctx: Context<WithdrawUnbonded>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        require!(
+            clock.unix_timestamp >= ctx.accounts.unbonding_request.available_at,
+            GreylockerError::UnbondingNotComplete
+        );
+
+        // The owner's ServiceProvider PDA is always passed; if it exists, it must have no open disputes
+        let linked_service = read_service_provider(&ctx.accounts.service_provider)?;
+        if let Some(service_provider) = &linked_service {
+            require!(service_provider.open_disputes == 0, GreylockerError::OpenDisputeBlocksWithdrawal);
+        }
+
+        // Slashes during the cooldown come out of unbonding_amount, so pay what survived
+        let stake_account = &mut ctx.accounts.stake_account;
+        let withdraw_amount = stake_account.unbonding_amount.min(ctx.accounts.unbonding_request.amount);
+        stake_account.unbonding_amount = stake_account.unbonding_amount
+            .checked_sub(withdraw_amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        stake_account.open_unbondings = stake_account.open_unbondings
+            .checked_sub(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let stake_key = stake_account.key();
+        let owner = stake_account.owner;
+        let position_id = stake_account.position_id.to_le_bytes();
+        let bump = stake_account.bump;
+        let stake_seeds = &[b"stake-account".as_ref(), owner.as_ref(), position_id.as_ref(), &[bump]];
+        let signer = &[&stake_seeds[..]];
+
+        if withdraw_amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.stake_vault.to_account_info(),
+                        to: ctx.accounts.user_token_account.to_account_info(),
+                        authority: ctx.accounts.stake_account.to_account_info(),
+                    },
+                    signer,
+                ),
+                withdraw_amount,
+            )?;
+        }
+
+        // Last request out of an emptied position: close its vault and the position, and free the registry slot.
+        // The position backing a service stays open—dispute execution still needs it, empty or not
+        ctx.accounts.stake_vault.reload()?;
+        let backs_service = matches!(&linked_service, Some(service_provider) if service_provider.stake_account == stake_key);
+        let position_closed = !backs_service
+            && position_emptied(&ctx.accounts.stake_account)
+            && ctx.accounts.stake_vault.amount == 0;
+        if position_closed {
+            token::close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: ctx.accounts.stake_vault.to_account_info(),
+                    destination: ctx.accounts.user.to_account_info(),
+                    authority: ctx.accounts.stake_account.to_account_info(),
+                },
+                signer,
+            ))?;
+            ctx.accounts.stake_account.close(ctx.accounts.user.to_account_info())?;
+
+            let stake_registry = &mut ctx.accounts.stake_registry;
+            stake_registry.open_positions = stake_registry.open_positions
+                .checked_sub(1)
+                .ok_or(GreylockerError::ArithmeticError)?;
+        }
+
+        emit!(WithdrawUnbondedEvent {
+            user: ctx.accounts.user.key(),
+            stake_account: stake_key,
+            request_id: ctx.accounts.unbonding_request.request_id,
+            requested_amount: ctx.accounts.unbonding_request.amount,
+            withdrawn_amount: withdraw_amount,
+            position_closed,
         });
 
         Ok(())
     }
 
     /// Claim staking rewards—reap the fruits of your loyalty!
+    /// Paid out of the reward reserve; whatever the epoch budget can't cover stays queued on the position.
     pub fn claim_stake_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
         require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
 
@@ -213,86 +791,124 @@ pub mod greylocker {
         let stake_account = &mut ctx.accounts.stake_account;
         require!(stake_account.owner == ctx.accounts.user.key(), GreylockerError::NotAuthorized);
 
-        let rewards = Self::calculate_rewards(&ctx.accounts.greylocker_state, stake_account, clock.unix_timestamp)?;
-        let total_rewards = stake_account.accumulated_rewards
-            .checked_add(rewards)
-            .ok_or(GreylockerError::ArithmeticError)?;
+        settle_rewards(&ctx.accounts.greylocker_state, stake_account, clock.unix_timestamp)?;
+        let total_rewards = stake_account.accumulated_rewards;
         require!(total_rewards > 0, GreylockerError::NoRewardsToClaim);
 
-        let new_total_supply = ctx.accounts.greylocker_state.total_supply
-            .checked_add(total_rewards)
-            .ok_or(GreylockerError::ArithmeticError)?;
-        require!(new_total_supply <= ctx.accounts.greylocker_state.max_supply, GreylockerError::MaxSupplyReached);
-
-        stake_account.accumulated_rewards = 0;
-        stake_account.last_reward_claim = clock.unix_timestamp;
+        let draw = draw_from_reserve(
+            &mut ctx.accounts.reward_reserve,
+            ctx.accounts.reserve_vault.amount,
+            total_rewards,
+            clock.unix_timestamp,
+        )?;
+        stake_account.accumulated_rewards = draw.deferred;
 
-        let state_seeds = &[b"greylocker-state", &[ctx.accounts.greylocker_state.bump]];
+        let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
         let signer = &[&state_seeds[..]];
-        token::mint_to(
+        token::transfer(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    mint: ctx.accounts.grey_mint.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reserve_vault.to_account_info(),
                     to: ctx.accounts.user_token_account.to_account_info(),
                     authority: ctx.accounts.greylocker_state.to_account_info(),
                 },
                 signer,
             ),
-            total_rewards,
+            draw.paid,
         )?;
 
-        ctx.accounts.greylocker_state.total_supply = new_total_supply;
-
         emit!(ClaimRewardsEvent {
             user: ctx.accounts.user.key(),
-            stake_account: stake_account.key(),
-            rewards_amount: total_rewards,
-            new_total_supply,
+            stake_account: ctx.accounts.stake_account.key(),
+            rewards_amount: draw.paid,
+            deferred_amount: draw.deferred,
+            forfeited_amount: draw.forfeited,
         });
 
         Ok(())
     }
 
-    /// Register as a service provider—step into the arena!
-    pub fn register_service(ctx: Context<RegisterService>, service_info: ServiceInfo) -> Result<()> {
+    /// Open a delegation pool—let passive holders back your service or validator!
+    /// Delegated GREY counts toward the operator's minimums and is slashed alongside their own stake.
+    pub fn open_delegation_pool(ctx: Context<OpenDelegationPool>, commission_bps: u16) -> Result<()> {
         require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(commission_bps <= MAX_OPERATOR_COMMISSION_BPS, GreylockerError::InvalidParameterValue);
 
-        let stake_account = &ctx.accounts.stake_account;
-        require!(stake_account.stake_type == StakeType::Service, GreylockerError::InvalidStakeType);
+        let clock = Clock::get()?;
+        let stake_account = &mut ctx.accounts.operator_stake_account;
         require!(
-            stake_account.amount >= ctx.accounts.greylocker_state.governance_parameters.service_stake_minimum,
-            GreylockerError::InsufficientStakeAmount
+            stake_account.stake_type == StakeType::Service || stake_account.stake_type == StakeType::DataValidator,
+            GreylockerError::InvalidStakeType
         );
-
-        let clock = Clock::get()?;
-        let service_provider = &mut ctx.accounts.service_provider;
-        service_provider.owner = ctx.accounts.user.key();
-        service_provider.stake_account = stake_account.key();
-        service_provider.service_info = service_info;
-        service_provider.registered_at = clock.unix_timestamp;
-        service_provider.total_access_fees_paid = 0;
-        service_provider.reputation_score = 70; // Neutral-positive start
-        service_provider.bump = *ctx.bumps.get("service_provider").unwrap();
-
-        emit!(RegisterServiceEvent {
-            service_provider: service_provider.key(),
-            owner: service_provider.owner,
-            stake_account: stake_account.key(),
-            service_name: service_provider.service_info.name.clone(),
+        require!(
+            stake_account.amount >= min_stake_for(&ctx.accounts.greylocker_state, &stake_account.stake_type),
+            GreylockerError::SelfBondTooLow
+        );
+        stake_account.delegation_open = true;
+
+        let delegation_pool = &mut ctx.accounts.delegation_pool;
+        delegation_pool.operator = ctx.accounts.operator.key();
+        delegation_pool.operator_stake_account = stake_account.key();
+        delegation_pool.stake_type = stake_account.stake_type.clone();
+        delegation_pool.vault = ctx.accounts.delegation_vault.key();
+        delegation_pool.commission_bps = commission_bps;
+        delegation_pool.total_delegated = 0;
+        delegation_pool.total_shares = 0;
+        delegation_pool.total_unbonding = 0;
+        delegation_pool.unbonding_shares = 0;
+        delegation_pool.reward_index_snapshot = current_reward_index(
+            &ctx.accounts.greylocker_state,
+            &delegation_reward_type(&stake_account.stake_type),
+            clock.unix_timestamp,
+        )?;
+        delegation_pool.acc_reward_per_share = 0;
+        delegation_pool.operator_rewards = 0;
+        delegation_pool.bump = *ctx.bumps.get("delegation_pool").unwrap();
+
+        emit!(OpenDelegationPoolEvent {
+            delegation_pool: delegation_pool.key(),
+            operator: delegation_pool.operator,
+            operator_stake_account: delegation_pool.operator_stake_account,
+            stake_type: delegation_pool.stake_type.clone(),
+            commission_bps,
         });
 
         Ok(())
     }
 
-    /// Pay access fee—services reward users for their data!
-    pub fn pay_access_fee(ctx: Context<PayAccessFee>, amount: u64, data_type: DataType, duration: i64) -> Result<()> {
+    /// Delegate GREY to an operator—back a provider without running one!
+    pub fn delegate(ctx: Context<Delegate>, amount: u64) -> Result<()> {
         require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(amount > 0, GreylockerError::InvalidParameterValue);
 
-        let service_provider = &mut ctx.accounts.service_provider;
-        require!(service_provider.owner == ctx.accounts.service_owner.key(), GreylockerError::NotAuthorized);
+        let clock = Clock::get()?;
+        let delegation_pool = &mut ctx.accounts.delegation_pool;
+        delegation_pool.accrue(&ctx.accounts.greylocker_state, clock.unix_timestamp)?;
+
+        let delegation = &mut ctx.accounts.delegation;
+        if delegation.delegator == Pubkey::default() {
+            delegation.delegator = ctx.accounts.delegator.key();
+            delegation.pool = delegation_pool.key();
+            delegation.bump = *ctx.bumps.get("delegation").unwrap();
+        }
+        delegation.settle(delegation_pool)?;
 
-        service_provider.total_access_fees_paid = service_provider.total_access_fees_paid
+        let shares = delegation_pool.shares_for(amount)?;
+        require!(shares > 0, GreylockerError::InvalidParameterValue);
+        delegation_pool.total_delegated = delegation_pool.total_delegated
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        delegation_pool.total_shares = delegation_pool.total_shares
+            .checked_add(shares)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        delegation.shares = delegation.shares
+            .checked_add(shares)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        delegation.reward_debt = delegation_pool.reward_debt_for(delegation.shares)?;
+
+        let operator_stake_account = &mut ctx.accounts.operator_stake_account;
+        operator_stake_account.delegated_amount = operator_stake_account.delegated_amount
             .checked_add(amount)
             .ok_or(GreylockerError::ArithmeticError)?;
 
@@ -300,990 +916,7464 @@ pub mod greylocker {
             CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.service_token_account.to_account_info(),
-                    to: ctx.accounts.user_token_account.to_account_info(),
-                    authority: ctx.accounts.service_owner.to_account_info(),
+                    from: ctx.accounts.delegator_token_account.to_account_info(),
+                    to: ctx.accounts.delegation_vault.to_account_info(),
+                    authority: ctx.accounts.delegator.to_account_info(),
                 },
             ),
             amount,
         )?;
 
-        let clock = Clock::get()?;
-        if ctx.accounts.access_record.owner != System::id() {
-            let access_record = &mut ctx.accounts.access_record;
-            access_record.service_provider = service_provider.key();
-            access_record.user = ctx.accounts.user.key();
-            access_record.data_type = data_type;
-            access_record.amount = amount;
-            access_record.granted_at = clock.unix_timestamp;
-            access_record.expires_at = clock.unix_timestamp + duration;
-            access_record.bump = *ctx.bumps.get("access_record").unwrap();
-        }
-
-        emit!(PayAccessFeeEvent {
-            service_provider: service_provider.key(),
-            user: ctx.accounts.user.key(),
+        emit!(DelegateEvent {
+            delegation_pool: ctx.accounts.delegation_pool.key(),
+            delegator: ctx.accounts.delegator.key(),
             amount,
-            data_type,
-            expires_at: clock.unix_timestamp + duration,
+            shares,
+            total_delegated: ctx.accounts.delegation_pool.total_delegated,
         });
 
         Ok(())
     }
 
-    /// Create a dispute—call out the bad actors!
-    pub fn create_dispute(ctx: Context<CreateDispute>, evidence: String, dispute_type: DisputeType) -> Result<()> {
+    /// Undelegate—start the cooldown; the GREY stays slashable until it's withdrawn!
+    pub fn undelegate(ctx: Context<Undelegate>, shares: u64) -> Result<()> {
         require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(shares > 0, GreylockerError::InvalidParameterValue);
 
         let clock = Clock::get()?;
-        let dispute = &mut ctx.accounts.dispute;
-        dispute.dispute_id = dispute.key();
-        dispute.reporter = ctx.accounts.reporter.key();
-        dispute.reported_service = ctx.accounts.service_provider.key();
-        dispute.evidence = evidence;
-        dispute.created_at = clock.unix_timestamp;
-        dispute.status = DisputeStatus::Pending;
-        dispute.resolution = None;
-        dispute.resolved_at = None;
-        dispute.dispute_type = dispute_type;
-        dispute.bump = *ctx.bumps.get("dispute").unwrap();
+        let delegation_pool = &mut ctx.accounts.delegation_pool;
+        delegation_pool.accrue(&ctx.accounts.greylocker_state, clock.unix_timestamp)?;
 
-        emit!(CreateDisputeEvent {
-            dispute_id: dispute.dispute_id,
-            reporter: dispute.reporter,
-            reported_service: dispute.reported_service,
-            dispute_type,
+        let delegation = &mut ctx.accounts.delegation;
+        require!(shares <= delegation.shares, GreylockerError::InsufficientDelegationShares);
+        delegation.settle(delegation_pool)?;
+
+        let amount = delegation_pool.value_of(shares)?;
+        let unbonding_shares = delegation_pool.unbonding_shares_for(amount)?;
+        delegation_pool.total_delegated = delegation_pool.total_delegated
+            .checked_sub(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        delegation_pool.total_shares = delegation_pool.total_shares
+            .checked_sub(shares)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        delegation_pool.total_unbonding = delegation_pool.total_unbonding
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        delegation_pool.unbonding_shares = delegation_pool.unbonding_shares
+            .checked_add(unbonding_shares)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        delegation.shares -= shares;
+        delegation.reward_debt = delegation_pool.reward_debt_for(delegation.shares)?;
+        delegation.unbonding_shares = delegation.unbonding_shares
+            .checked_add(unbonding_shares)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        // A fresh request restarts the cooldown for everything still unbonding
+        delegation.unbonding_available_at = clock.unix_timestamp
+            .checked_add(ctx.accounts.greylocker_state.governance_parameters.unbonding_period)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let operator_stake_account = &mut ctx.accounts.operator_stake_account;
+        operator_stake_account.delegated_amount = operator_stake_account.delegated_amount.saturating_sub(amount);
+
+        emit!(UndelegateEvent {
+            delegation_pool: delegation_pool.key(),
+            delegator: delegation.delegator,
+            shares,
+            amount,
+            available_at: delegation.unbonding_available_at,
         });
 
         Ok(())
     }
 
-    /// Resolve a dispute—justice is served!
-    pub fn resolve_dispute(ctx: Context<ResolveDispute>, resolution: DisputeResolution) -> Result<()> {
+    /// Withdraw undelegated GREY—cooldown served, back to your wallet!
+    /// Blocked while any dispute against the operator's service is still open.
+    pub fn withdraw_undelegated(ctx: Context<WithdrawUndelegated>) -> Result<()> {
         require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
-        require!(
-            ctx.accounts.resolver.key() == ctx.accounts.greylocker_state.governance_authority,
-            GreylockerError::NotAuthorized
-        );
-
-        let dispute = &mut ctx.accounts.dispute;
-        require!(dispute.status == DisputeStatus::Pending, GreylockerError::DisputeAlreadyResolved);
 
         let clock = Clock::get()?;
-        dispute.status = DisputeStatus::Resolved;
-        dispute.resolution = Some(resolution);
-        dispute.resolved_at = Some(clock.unix_timestamp);
-
-        if resolution == DisputeResolution::Upheld && ctx.accounts.stake_account.owner != System::id() {
-            let service_provider = &mut ctx.accounts.service_provider;
-            let stake_account = &mut ctx.accounts.stake_account;
-            require!(stake_account.owner == service_provider.owner, GreylockerError::NotAuthorized);
-
-            service_provider.reputation_score = service_provider.reputation_score.saturating_sub(10);
-            let slash_amount = stake_account.amount
-                .checked_mul(ctx.accounts.greylocker_state.governance_parameters.slashing_percentage as u64)
-                .and_then(|val| val.checked_div(100))
-                .ok_or(GreylockerError::ArithmeticError)?;
-            let burn_amount = slash_amount / 2;
-            let reporter_amount = slash_amount - burn_amount;
-
-            stake_account.amount = stake_account.amount
-                .checked_sub(slash_amount)
-                .ok_or(GreylockerError::ArithmeticError)?;
-            ctx.accounts.greylocker_state.total_staked = ctx.accounts.greylocker_state.total_staked
-                .checked_sub(slash_amount)
-                .ok_or(GreylockerError::ArithmeticError)?;
-
-            let stake_seeds = &[b"stake-account", stake_account.owner.as_ref(), &[stake_account.bump]];
-            let signer = &[&stake_seeds[..]];
-
-            if reporter_amount > 0 {
-                token::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Transfer {
-                            from: ctx.accounts.stake_vault.to_account_info(),
-                            to: ctx.accounts.reporter_token_account.to_account_info(),
-                            authority: ctx.accounts.stake_account.to_account_info(),
-                        },
-                        signer,
-                    ),
-                    reporter_amount,
-                )?;
-            }
+        let delegation = &mut ctx.accounts.delegation;
+        require!(delegation.unbonding_shares > 0, GreylockerError::InvalidParameterValue);
+        require!(clock.unix_timestamp >= delegation.unbonding_available_at, GreylockerError::UnbondingNotComplete);
 
-            if burn_amount > 0 {
-                token::burn(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.token_program.to_account_info(),
-                        Burn {
-                            mint: ctx.accounts.grey_mint.to_account_info(),
-                            from: ctx.accounts.stake_vault.to_account_info(),
-                            authority: ctx.accounts.stake_account.to_account_info(),
-                        },
-                        signer,
-                    ),
-                    burn_amount,
-                )?;
-                ctx.accounts.greylocker_state.total_supply = ctx.accounts.greylocker_state.total_supply
-                    .checked_sub(burn_amount)
-                    .ok_or(GreylockerError::ArithmeticError)?;
-            }
+        // The operator's ServiceProvider PDA is always passed; if it exists, it must have no open disputes
+        if let Some(service_provider) = read_service_provider(&ctx.accounts.service_provider)? {
+            require!(service_provider.open_disputes == 0, GreylockerError::OpenDisputeBlocksWithdrawal);
+        }
 
-            emit!(SlashEvent {
-                stake_account: stake_account.key(),
-                dispute_id: dispute.dispute_id,
-                slash_amount,
-                burn_amount,
-                reporter_amount,
-            });
+        let delegation_pool = &mut ctx.accounts.delegation_pool;
+        let amount = delegation_pool.unbonding_value_of(delegation.unbonding_shares)?;
+        delegation_pool.total_unbonding = delegation_pool.total_unbonding
+            .checked_sub(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        delegation_pool.unbonding_shares = delegation_pool.unbonding_shares
+            .checked_sub(delegation.unbonding_shares)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        delegation.unbonding_shares = 0;
+
+        let operator_stake_account = delegation_pool.operator_stake_account;
+        let pool_bump = delegation_pool.bump;
+        let pool_seeds = &[b"delegation-pool".as_ref(), operator_stake_account.as_ref(), &[pool_bump]];
+        let signer = &[&pool_seeds[..]];
+        if amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.delegation_vault.to_account_info(),
+                        to: ctx.accounts.delegator_token_account.to_account_info(),
+                        authority: ctx.accounts.delegation_pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
         }
 
-        emit!(ResolveDisputeEvent {
-            dispute_id: dispute.dispute_id,
-            resolver: ctx.accounts.resolver.key(),
-            resolution,
-            service_provider: ctx.accounts.service_provider.key(),
-            reputation_score: ctx.accounts.service_provider.reputation_score,
+        emit!(WithdrawUndelegatedEvent {
+            delegation_pool: ctx.accounts.delegation_pool.key(),
+            delegator: ctx.accounts.delegator.key(),
+            amount,
         });
 
         Ok(())
     }
 
-    /// Create a data pool—share and earn in the anonymized data economy!
-    pub fn create_data_pool(ctx: Context<CreateDataPool>, pool_info: DataPoolInfo) -> Result<()> {
+    /// Claim delegation rewards—your cut of the operator's earnings, commission already taken!
+    pub fn claim_delegation_rewards(ctx: Context<ClaimDelegationRewards>) -> Result<()> {
         require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
-        require!(
-            ctx.accounts.governance_authority.key() == ctx.accounts.greylocker_state.governance_authority,
-            GreylockerError::NotAuthorized
-        );
 
         let clock = Clock::get()?;
-        let data_pool = &mut ctx.accounts.data_pool;
-        data_pool.pool_id = data_pool.key();
-        data_pool.name = pool_info.name;
-        data_pool.data_type = pool_info.data_type;
-        data_pool.reward_rate = pool_info.reward_rate;
-        data_pool.description = pool_info.description;
-        data_pool.created_at = clock.unix_timestamp;
-        data_pool.total_participants = 0;
-        data_pool.total_rewards_paid = 0;
-        data_pool.active = true;
-        data_pool.bump = *ctx.bumps.get("data_pool").unwrap();
-
-        emit!(CreateDataPoolEvent {
-            pool_id: data_pool.pool_id,
-            name: data_pool.name.clone(),
-            data_type: data_pool.data_type.clone(),
-            reward_rate: data_pool.reward_rate,
-        });
-
-        Ok(())
-    }
+        let delegation_pool = &mut ctx.accounts.delegation_pool;
+        delegation_pool.accrue(&ctx.accounts.greylocker_state, clock.unix_timestamp)?;
 
-    /// Join a data pool—opt in and start earning!
-    pub fn join_data_pool(ctx: Context<JoinDataPool>) -> Result<()> {
-        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
-        require!(ctx.accounts.data_pool.active, GreylockerError::DataPoolInactive);
+        let delegation = &mut ctx.accounts.delegation;
+        delegation.settle(delegation_pool)?;
+        let total_rewards = delegation.pending_rewards;
+        require!(total_rewards > 0, GreylockerError::NoRewardsToClaim);
 
-        let clock = Clock::get()?;
-        let participant = &mut ctx.accounts.participant;
-        participant.user = ctx.accounts.user.key();
-        participant.pool_id = ctx.accounts.data_pool.key();
-        participant.joined_at = clock.unix_timestamp;
-        participant.total_rewards_received = 0;
-        participant.last_reward_at = 0;
-        participant.bump = *ctx.bumps.get("participant").unwrap();
+        let draw = draw_from_reserve(
+            &mut ctx.accounts.reward_reserve,
+            ctx.accounts.reserve_vault.amount,
+            total_rewards,
+            clock.unix_timestamp,
+        )?;
+        delegation.pending_rewards = draw.deferred;
 
-        ctx.accounts.data_pool.total_participants = ctx.accounts.data_pool.total_participants
-            .checked_add(1)
-            .ok_or(GreylockerError::ArithmeticError)?;
+        let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
+        let signer = &[&state_seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reserve_vault.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.greylocker_state.to_account_info(),
+                },
+                signer,
+            ),
+            draw.paid,
+        )?;
 
-        emit!(JoinDataPoolEvent {
-            user: participant.user,
-            pool_id: participant.pool_id,
-            pool_name: ctx.accounts.data_pool.name.clone(),
+        emit!(ClaimDelegationRewardsEvent {
+            delegation_pool: ctx.accounts.delegation_pool.key(),
+            recipient: ctx.accounts.delegator.key(),
+            commission: false,
+            rewards_amount: draw.paid,
+            deferred_amount: draw.deferred,
+            forfeited_amount: draw.forfeited,
         });
 
         Ok(())
     }
 
-    /// Pay data reward—reward users for their contributions!
-    pub fn pay_data_reward(ctx: Context<PayDataReward>, amount: u64) -> Result<()> {
+    /// Claim operator commission—the price of running the show!
+    pub fn claim_operator_commission(ctx: Context<ClaimOperatorCommission>) -> Result<()> {
         require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
-        require!(
-            ctx.accounts.caller.key() == ctx.accounts.greylocker_state.governance_authority,
-            GreylockerError::NotAuthorized
-        );
-
-        let participant = &mut ctx.accounts.participant;
-        require!(
-            participant.user == ctx.accounts.user.key() && participant.pool_id == ctx.accounts.data_pool.key(),
-            GreylockerError::UserNotInDataPool
-        );
 
         let clock = Clock::get()?;
-        participant.total_rewards_received = participant.total_rewards_received
-            .checked_add(amount)
-            .ok_or(GreylockerError::ArithmeticError)?;
-        participant.last_reward_at = clock.unix_timestamp;
-
-        ctx.accounts.data_pool.total_rewards_paid = ctx.accounts.data_pool.total_rewards_paid
-            .checked_add(amount)
-            .ok_or(GreylockerError::ArithmeticError)?;
+        let delegation_pool = &mut ctx.accounts.delegation_pool;
+        delegation_pool.accrue(&ctx.accounts.greylocker_state, clock.unix_timestamp)?;
+        let total_rewards = delegation_pool.operator_rewards;
+        require!(total_rewards > 0, GreylockerError::NoRewardsToClaim);
 
-        let new_total_supply = ctx.accounts.greylocker_state.total_supply
-            .checked_add(amount)
-            .ok_or(GreylockerError::ArithmeticError)?;
-        require!(new_total_supply <= ctx.accounts.greylocker_state.max_supply, GreylockerError::MaxSupplyReached);
+        let draw = draw_from_reserve(
+            &mut ctx.accounts.reward_reserve,
+            ctx.accounts.reserve_vault.amount,
+            total_rewards,
+            clock.unix_timestamp,
+        )?;
+        delegation_pool.operator_rewards = draw.deferred;
 
-        let state_seeds = &[b"greylocker-state", &[ctx.accounts.greylocker_state.bump]];
+        let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
         let signer = &[&state_seeds[..]];
-        token::mint_to(
+        token::transfer(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                MintTo {
-                    mint: ctx.accounts.grey_mint.to_account_info(),
-                    to: ctx.accounts.user_token_account.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reserve_vault.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
                     authority: ctx.accounts.greylocker_state.to_account_info(),
                 },
                 signer,
             ),
-            amount,
+            draw.paid,
         )?;
 
-        ctx.accounts.greylocker_state.total_supply = new_total_supply;
-
-        emit!(PayDataRewardEvent {
-            user: participant.user,
-            pool_id: ctx.accounts.data_pool.key(),
-            amount,
-            new_total_supply,
+        emit!(ClaimDelegationRewardsEvent {
+            delegation_pool: ctx.accounts.delegation_pool.key(),
+            recipient: ctx.accounts.operator.key(),
+            commission: true,
+            rewards_amount: draw.paid,
+            deferred_amount: draw.deferred,
+            forfeited_amount: draw.forfeited,
         });
 
         Ok(())
     }
 
-    /// Update governance parameter—tune the ecosystem’s pulse!
-    pub fn update_governance_parameter(ctx: Context<UpdateGovernanceParameter>, parameter: GovernanceParameter, value: u64) -> Result<()> {
-        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
-        require!(
-            ctx.accounts.governance_authority.key() == ctx.accounts.greylocker_state.governance_authority,
-            GreylockerError::NotAuthorized
-        );
-
-        let state = &mut ctx.accounts.greylocker_state;
-        match parameter {
-            GovernanceParameter::SecurityStakeMinimum => state.governance_parameters.security_stake_minimum = value,
-            GovernanceParameter::ServiceStakeMinimum => state.governance_parameters.service_stake_minimum = value,
-            GovernanceParameter::DataValidatorStakeMinimum => state.governance_parameters.data_validator_stake_minimum = value,
-            GovernanceParameter::LiquidityStakeMinimum => state.governance_parameters.liquidity_stake_minimum = value,
-            GovernanceParameter::SecurityRewardRate => {
-                require!(value <= 100, GreylockerError::InvalidParameterValue);
-                state.governance_parameters.security_reward_rate = value;
-            },
-            GovernanceParameter::DataValidatorRewardRate => {
-                require!(value <= 100, GreylockerError::InvalidParameterValue);
-                state.governance_parameters.data_validator_reward_rate = value;
-            },
-            GovernanceParameter::LiquidityRewardRate => {
-                require!(value <= 100, GreylockerError::InvalidParameterValue);
-                state.governance_parameters.liquidity_reward_rate = value;
-            },
-            GovernanceParameter::SlashingPercentage => {
-                require!(value <= 100, GreylockerError::InvalidParameterValue);
-                state.governance_parameters.slashing_percentage = value;
-            },
-            GovernanceParameter::EarlyUnstakePenalty => {
-                require!(value <= 100, GreylockerError::InvalidParameterValue);
-                state.governance_parameters.early_unstake_penalty = value;
-            },
-            GovernanceParameter::RewardUpdateInterval => state.governance_parameters.reward_update_interval = value,
-        }
+    /// Toggle auto-compounding—let your rewards work for you!
+    pub fn set_auto_compound(ctx: Context<SetAutoCompound>, enabled: bool) -> Result<()> {
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.auto_compound = enabled;
 
-        emit!(UpdateGovernanceParameterEvent {
-            parameter,
-            new_value: value,
-            authority: ctx.accounts.governance_authority.key(),
+        emit!(SetAutoCompoundEvent {
+            user: ctx.accounts.user.key(),
+            stake_account: stake_account.key(),
+            enabled,
         });
 
         Ok(())
     }
 
-    /// Pause the program—freeze the grid!
-    pub fn pause(ctx: Context<Pause>) -> Result<()> {
-        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::AlreadyPaused);
-        require!(
-            ctx.accounts.pauser.key() == ctx.accounts.greylocker_state.authority,
-            GreylockerError::NotAuthorized
-        );
-        ctx.accounts.greylocker_state.paused = true;
+    /// Crank compounding—roll a position's rewards back into its stake, for a tip!
+    /// Permissionless; the position must have opted in with `set_auto_compound`.
+    pub fn crank_compound(ctx: Context<CrankCompound>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
 
-        emit!(PauseEvent {
-            authority: ctx.accounts.pauser.key(),
-        });
+        let clock = Clock::get()?;
+        let stake_account = &mut ctx.accounts.stake_account;
+        require!(stake_account.auto_compound, GreylockerError::AutoCompoundDisabled);
+
+        settle_rewards(&ctx.accounts.greylocker_state, stake_account, clock.unix_timestamp)?;
+        let total_rewards = stake_account.accumulated_rewards;
+        require!(total_rewards > 0, GreylockerError::NoRewardsToClaim);
+
+        let draw = draw_from_reserve(
+            &mut ctx.accounts.reward_reserve,
+            ctx.accounts.reserve_vault.amount,
+            total_rewards,
+            clock.unix_timestamp,
+        )?;
+        let tip = draw.paid
+            .checked_mul(ctx.accounts.greylocker_state.governance_parameters.compound_tip_bps as u64)
+            .and_then(|val| val.checked_div(10_000))
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let compounded = draw.paid - tip;
+
+        stake_account.accumulated_rewards = draw.deferred;
+        // Compounded rewards leave last_increase_at alone: a stranger's crank must not cost the owner their vote
+        stake_account.amount = stake_account.amount
+            .checked_add(compounded)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        ctx.accounts.greylocker_state.total_staked = ctx.accounts.greylocker_state.total_staked
+            .checked_add(compounded)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
+        let signer = &[&state_seeds[..]];
+        for (amount, to) in [
+            (compounded, ctx.accounts.stake_vault.to_account_info()),
+            (tip, ctx.accounts.cranker_token_account.to_account_info()),
+        ] {
+            if amount > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.reserve_vault.to_account_info(),
+                            to,
+                            authority: ctx.accounts.greylocker_state.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    amount,
+                )?;
+            }
+        }
+
+        emit!(CompoundEvent {
+            stake_account: ctx.accounts.stake_account.key(),
+            cranker: ctx.accounts.cranker.key(),
+            compounded_amount: compounded,
+            tip_amount: tip,
+            deferred_amount: draw.deferred,
+            forfeited_amount: draw.forfeited,
+            new_amount: ctx.accounts.stake_account.amount,
+        });
 
         Ok(())
     }
 
-    /// Unpause the program—reactivate the system!
-    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
-        require!(ctx.accounts.greylocker_state.paused, GreylockerError::NotPaused);
+    /// Set up liquid staking—Liquidity stakes get a tradeable stGREY receipt!
+    /// One-shot bootstrap by the governance authority, like seating the council; it sets no rates and moves no GREY.
+    pub fn initialize_liquid_staking(ctx: Context<InitializeLiquidStaking>) -> Result<()> {
+        let state = &ctx.accounts.greylocker_state;
         require!(
-            ctx.accounts.pauser.key() == ctx.accounts.greylocker_state.authority,
+            ctx.accounts.governance_authority.key() == state.governance_authority,
             GreylockerError::NotAuthorized
         );
-        ctx.accounts.greylocker_state.paused = false;
 
-        emit!(UnpauseEvent {
-            authority: ctx.accounts.pauser.key(),
+        let clock = Clock::get()?;
+        let pool = &mut ctx.accounts.liquid_staking_pool;
+        pool.st_mint = ctx.accounts.st_grey_mint.key();
+        pool.vault = ctx.accounts.liquid_vault.key();
+        pool.total_backing = 0;
+        pool.pending_rewards = 0;
+        pool.reward_index_snapshot = current_reward_index(state, &StakeType::Liquidity, clock.unix_timestamp)?;
+        pool.bump = *ctx.bumps.get("liquid_staking_pool").unwrap();
+
+        emit!(InitializeLiquidStakingEvent {
+            liquid_staking_pool: pool.key(),
+            st_mint: pool.st_mint,
+            vault: pool.vault,
         });
 
         Ok(())
     }
 
-    /// Governance mint—fuel the ecosystem’s growth!
-    pub fn governance_mint(ctx: Context<GovernanceMint>, amount: u64) -> Result<()> {
+    /// Stake GREY for stGREY—liquidity that keeps earning while you trade it!
+    pub fn stake_liquid(ctx: Context<StakeLiquid>, amount: u64) -> Result<()> {
         require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
         require!(
-            ctx.accounts.governance_authority.key() == ctx.accounts.greylocker_state.governance_authority,
-            GreylockerError::NotAuthorized
+            amount >= ctx.accounts.greylocker_state.governance_parameters.liquidity_stake_minimum,
+            GreylockerError::InsufficientStakeAmount
         );
 
-        let new_total_supply = ctx.accounts.greylocker_state.total_supply
+        let clock = Clock::get()?;
+        let st_supply = ctx.accounts.st_grey_mint.supply;
+        let pool = &mut ctx.accounts.liquid_staking_pool;
+        pool.accrue(&ctx.accounts.greylocker_state, clock.unix_timestamp)?;
+
+        // Value left behind with no stGREY outstanding belongs to nobody: unharvested rewards are
+        // cancelled and orphaned backing goes to the treasury instead of gifting the next staker
+        let orphaned_backing = if st_supply == 0 { pool.total_backing } else { 0 };
+        if st_supply == 0 {
+            pool.total_backing = 0;
+            pool.pending_rewards = 0;
+        }
+
+        // Unharvested rewards already belong to current holders, so they count toward the rate
+        let st_amount = share_conversion(amount, st_supply, pool.total_value()?)?;
+        require!(st_amount > 0, GreylockerError::InvalidParameterValue);
+        pool.total_backing = pool.total_backing
             .checked_add(amount)
             .ok_or(GreylockerError::ArithmeticError)?;
-        require!(new_total_supply <= ctx.accounts.greylocker_state.max_supply, GreylockerError::MaxSupplyReached);
 
-        let state_seeds = &[b"greylocker-state", &[ctx.accounts.greylocker_state.bump]];
-        let signer = &[&state_seeds[..]];
+        let pool_seeds = &[b"liquid-staking".as_ref(), &[ctx.accounts.liquid_staking_pool.bump]];
+        let signer = &[&pool_seeds[..]];
+        if orphaned_backing > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.liquid_vault.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                        authority: ctx.accounts.liquid_staking_pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                orphaned_backing,
+            )?;
+            ctx.accounts.greylocker_state.total_staked = ctx.accounts.greylocker_state.total_staked
+                .checked_sub(orphaned_backing)
+                .ok_or(GreylockerError::ArithmeticError)?;
+        }
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    to: ctx.accounts.liquid_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
         token::mint_to(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 MintTo {
-                    mint: ctx.accounts.grey_mint.to_account_info(),
-                    to: ctx.accounts.recipient.to_account_info(),
-                    authority: ctx.accounts.greylocker_state.to_account_info(),
+                    mint: ctx.accounts.st_grey_mint.to_account_info(),
+                    to: ctx.accounts.user_st_token_account.to_account_info(),
+                    authority: ctx.accounts.liquid_staking_pool.to_account_info(),
                 },
                 signer,
             ),
-            amount,
+            st_amount,
         )?;
 
-        ctx.accounts.greylocker_state.total_supply = new_total_supply;
+        ctx.accounts.greylocker_state.total_staked = ctx.accounts.greylocker_state.total_staked
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
 
-        emit!(GovernanceMintEvent {
-            recipient: ctx.accounts.recipient.key(),
+        emit!(StakeLiquidEvent {
+            user: ctx.accounts.user.key(),
             amount,
-            new_total_supply,
+            st_amount,
+            total_backing: ctx.accounts.liquid_staking_pool.total_backing,
         });
 
         Ok(())
     }
 
-    // Helper function to calculate staking rewards
-    fn calculate_rewards(state: &GreylockerState, stake_account: &StakeAccount, current_time: i64) -> Result<u64> {
-        if current_time < stake_account.last_reward_claim + state.governance_parameters.reward_update_interval as i64 {
-            return Ok(0); // Not enough time has passed
-        }
+    /// Harvest liquid staking rewards—every stGREY gets worth a little more GREY!
+    pub fn harvest_liquid_rewards(ctx: Context<HarvestLiquidRewards>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
 
-        let time_since_last_claim = (current_time - stake_account.last_reward_claim) as u64;
-        let reward_rate = match stake_account.stake_type {
-            StakeType::Security => state.governance_parameters.security_reward_rate,
-            StakeType::DataValidator => state.governance_parameters.data_validator_reward_rate,
-            StakeType::Liquidity => state.governance_parameters.liquidity_reward_rate,
-            StakeType::Service => 0, // No rewards for service stakes
-        };
+        let clock = Clock::get()?;
+        let pool = &mut ctx.accounts.liquid_staking_pool;
+        pool.accrue(&ctx.accounts.greylocker_state, clock.unix_timestamp)?;
+        require!(pool.pending_rewards > 0, GreylockerError::NoRewardsToClaim);
+
+        let draw = draw_from_reserve(
+            &mut ctx.accounts.reward_reserve,
+            ctx.accounts.reserve_vault.amount,
+            pool.pending_rewards,
+            clock.unix_timestamp,
+        )?;
+        pool.pending_rewards = draw.deferred;
+        pool.total_backing = pool.total_backing
+            .checked_add(draw.paid)
+            .ok_or(GreylockerError::ArithmeticError)?;
 
-        if reward_rate == 0 || stake_account.amount == 0 {
-            return Ok(0);
-        }
+        let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
+        let signer = &[&state_seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reserve_vault.to_account_info(),
+                    to: ctx.accounts.liquid_vault.to_account_info(),
+                    authority: ctx.accounts.greylocker_state.to_account_info(),
+                },
+                signer,
+            ),
+            draw.paid,
+        )?;
 
-        // Rewards = amount * rate * time / (365 days * 100)
-        let rewards = (stake_account.amount as u128)
-            .checked_mul(reward_rate as u128)
-            .and_then(|val| val.checked_mul(time_since_last_claim as u128))
-            .and_then(|val| val.checked_div(365 * 24 * 60 * 60 * 100))
-            .and_then(|val| u64::try_from(val).ok())
+        // Harvested rewards are staked GREY from here on
+        ctx.accounts.greylocker_state.total_staked = ctx.accounts.greylocker_state.total_staked
+            .checked_add(draw.paid)
             .ok_or(GreylockerError::ArithmeticError)?;
 
-        Ok(rewards)
+        emit!(HarvestLiquidRewardsEvent {
+            rewards_amount: draw.paid,
+            deferred_amount: draw.deferred,
+            forfeited_amount: draw.forfeited,
+            total_backing: ctx.accounts.liquid_staking_pool.total_backing,
+            st_supply: ctx.accounts.st_grey_mint.supply,
+        });
+
+        Ok(())
     }
-}
 
-// Account structs
-#[account]
-pub struct GreylockerState {
-    pub grey_mint: Pubkey,
-    pub treasury: Pubkey,
-    pub paused: bool,
-    pub total_staked: u64,
-    pub total_supply: u64,
-    pub max_supply: u64,
-    pub authority: Pubkey,
-    pub governance_authority: Pubkey,
-    pub governance_parameters: GovernanceParameters,
-    pub bump: u8,
-}
+    /// Unwrap stGREY—burn the receipt and get a regular Liquidity position back!
+    /// The new position is unlocked, so it leaves through `request_unstake` like any other.
+    pub fn unwrap_liquid_stake(ctx: Context<UnwrapLiquidStake>, st_amount: u64) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(st_amount > 0, GreylockerError::InvalidParameterValue);
 
-#[account]
-pub struct StakeAccount {
-    pub owner: Pubkey,
-    pub stake_type: StakeType,
-    pub amount: u64,
-    pub lock_until: i64,
-    pub last_reward_claim: i64,
-    pub accumulated_rewards: u64,
-    pub staked_at: i64,
-    pub bump: u8,
-}
+        let clock = Clock::get()?;
+        let state = &ctx.accounts.greylocker_state;
+        let st_supply = ctx.accounts.st_grey_mint.supply;
+        require!(st_amount <= st_supply, GreylockerError::InsufficientStakeAmount);
+        let pool = &mut ctx.accounts.liquid_staking_pool;
+        pool.accrue(state, clock.unix_timestamp)?;
+
+        // The holder's cut of unharvested rewards rides along as rewards owed on the new position;
+        // the last stGREY out takes everything, rounding dust included
+        let (amount, owed_rewards) = if st_amount == st_supply {
+            (pool.total_backing, pool.pending_rewards)
+        } else {
+            (
+                share_conversion(st_amount, pool.total_backing, st_supply)?,
+                share_conversion(st_amount, pool.pending_rewards, st_supply)?,
+            )
+        };
+        require!(amount > 0, GreylockerError::InvalidParameterValue);
+        pool.total_backing = pool.total_backing
+            .checked_sub(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        pool.pending_rewards = pool.pending_rewards
+            .checked_sub(owed_rewards)
+            .ok_or(GreylockerError::ArithmeticError)?;
 
-#[account]
-pub struct ServiceProvider {
-    pub owner: Pubkey,
-    pub stake_account: Pubkey,
-    pub service_info: ServiceInfo,
-    pub registered_at: i64,
-    pub total_access_fees_paid: u64,
-    pub reputation_score: u8,
-    pub bump: u8,
-}
+        let stake_registry = &mut ctx.accounts.stake_registry;
+        if stake_registry.owner == Pubkey::default() {
+            stake_registry.owner = ctx.accounts.user.key();
+            stake_registry.next_position_id = 0;
+            stake_registry.open_positions = 0;
+            stake_registry.bump = *ctx.bumps.get("stake_registry").unwrap();
+        }
 
-#[account]
-pub struct AccessRecord {
-    pub service_provider: Pubkey,
-    pub user: Pubkey,
-    pub data_type: DataType,
-    pub amount: u64,
-    pub granted_at: i64,
-    pub expires_at: i64,
-    pub bump: u8,
-}
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.owner = ctx.accounts.user.key();
+        stake_account.position_id = stake_registry.next_position_id;
+        stake_account.stake_type = StakeType::Liquidity;
+        stake_account.amount = amount;
+        stake_account.lock_until = clock.unix_timestamp;
+        stake_account.lock_tier = 0;
+        stake_account.reward_multiplier_bps = state.lock_tiers[0].multiplier_bps;
+        stake_account.last_reward_claim = clock.unix_timestamp;
+        stake_account.accumulated_rewards = owed_rewards;
+        stake_account.reward_index_snapshot = current_reward_index(state, &StakeType::Liquidity, clock.unix_timestamp)?;
+        stake_account.staked_at = clock.unix_timestamp;
+        stake_account.last_increase_at = clock.unix_timestamp;
+        stake_account.bump = *ctx.bumps.get("stake_account").unwrap();
 
-#[account]
-pub struct Dispute {
-    pub dispute_id: Pubkey,
-    pub reporter: Pubkey,
-    pub reported_service: Pubkey,
-    pub evidence: String,
-    pub created_at: i64,
-    pub status: DisputeStatus,
-    pub resolution: Option<DisputeResolution>,
-    pub resolved_at: Option<i64>,
-    pub dispute_type: DisputeType,
-    pub bump: u8,
-}
+        stake_registry.next_position_id = stake_registry.next_position_id
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        stake_registry.open_positions = stake_registry.open_positions
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
 
-#[account]
-pub struct DataPool {
-    pub pool_id: Pubkey,
-    pub name: String,
-    pub data_type: String,
-    pub reward_rate: u64,
-    pub description: String,
-    pub created_at: i64,
-    pub total_participants: u64,
-    pub total_rewards_paid: u64,
-    pub active: bool,
-    pub bump: u8,
-}
+        token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.st_grey_mint.to_account_info(),
+                    from: ctx.accounts.user_st_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            st_amount,
+        )?;
 
-#[account]
-pub struct PoolParticipant {
-    pub user: Pubkey,
-    pub pool_id: Pubkey,
-    pub joined_at: i64,
-    pub total_rewards_received: u64,
-    pub last_reward_at: i64,
-    pub bump: u8,
-}
+        let pool_seeds = &[b"liquid-staking".as_ref(), &[ctx.accounts.liquid_staking_pool.bump]];
+        let signer = &[&pool_seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.liquid_vault.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.liquid_staking_pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
 
-// Data structs
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct GovernanceParameters {
-    pub security_stake_minimum: u64,
-    pub service_stake_minimum: u64,
-    pub data_validator_stake_minimum: u64,
-    pub liquidity_stake_minimum: u64,
-    pub security_reward_rate: u8,
-    pub data_validator_reward_rate: u8,
-    pub liquidity_reward_rate: u8,
-    pub slashing_percentage: u8,
-    pub early_unstake_penalty: u8,
-    pub reward_update_interval: u64,
-}
+        emit!(UnwrapLiquidStakeEvent {
+            user: ctx.accounts.user.key(),
+            stake_account: ctx.accounts.stake_account.key(),
+            st_amount,
+            amount,
+            owed_rewards,
+        });
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct ServiceInfo {
-    pub name: String,
-    pub description: String,
-    pub metadata_uri: String,
-}
+        Ok(())
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct DataPoolInfo {
-    pub name: String,
-    pub data_type: String,
-    pub reward_rate: u64,
-    pub description: String,
-}
+    /// Create a vesting schedule—treasury GREY for the team and backers, released on a timetable!
+    /// Funds move from the treasury into an escrow held by the state PDA, once a council proposal
+    /// for exactly this schedule has cleared its timelock; the governance authority submits it.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_vesting_schedule(
+        ctx: Context<CreateVestingSchedule>,
+        beneficiary: Pubkey,
+        total_amount: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+        kind: VestingKind,
+        revocable: bool,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.governance_authority.key() == ctx.accounts.greylocker_state.governance_authority,
+            GreylockerError::NotAuthorized
+        );
+        require!(total_amount > 0, GreylockerError::InvalidVestingSchedule);
+        require!(start_ts < end_ts && start_ts <= cliff_ts && cliff_ts <= end_ts, GreylockerError::InvalidVestingSchedule);
+        if let VestingKind::Step { interval } = kind {
+            require!(interval > 0 && interval <= end_ts - start_ts, GreylockerError::InvalidVestingSchedule);
+        }
+        // No back-dating: a schedule that starts in the past would vest on creation
+        let clock = Clock::get()?;
+        require!(start_ts >= clock.unix_timestamp, GreylockerError::InvalidVestingSchedule);
+        carry_out_proposal(
+            &mut ctx.accounts.proposal,
+            &ctx.accounts.governance_council,
+            ProposalAction::CreateVestingSchedule { beneficiary, total_amount, start_ts, cliff_ts, end_ts, kind: kind.clone(), revocable },
+            ctx.accounts.governance_authority.key(),
+            clock.unix_timestamp,
+        )?;
 
-// Enums
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
-pub enum StakeType {
-    Security,
-    Service,
-    DataValidator,
-    Liquidity,
-}
+        let state = &mut ctx.accounts.greylocker_state;
+        state.charge_treasury(total_amount, clock.unix_timestamp)?;
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        schedule.schedule_id = state.next_vesting_id;
+        schedule.beneficiary = beneficiary;
+        schedule.total_amount = total_amount;
+        schedule.claimed_amount = 0;
+        schedule.start_ts = start_ts;
+        schedule.cliff_ts = cliff_ts;
+        schedule.end_ts = end_ts;
+        schedule.kind = kind.clone();
+        schedule.revocable = revocable;
+        schedule.revoked = false;
+        schedule.bump = *ctx.bumps.get("vesting_schedule").unwrap();
+
+        state.next_vesting_id = state.next_vesting_id
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        state.total_vesting_allocated = state.total_vesting_allocated
+            .checked_add(total_amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+        let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
+        let signer = &[&state_seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.vesting_escrow.to_account_info(),
+                    authority: ctx.accounts.greylocker_state.to_account_info(),
+                },
+                signer,
+            ),
+            total_amount,
+        )?;
+
+        emit!(CreateVestingEvent {
+            vesting_schedule: ctx.accounts.vesting_schedule.key(),
+            schedule_id: ctx.accounts.vesting_schedule.schedule_id,
+            beneficiary,
+            total_amount,
+            start_ts,
+            cliff_ts,
+            end_ts,
+            kind,
+            revocable,
+        });
+
+        Ok(())
+    }
+
+    /// Claim vested GREY—patience pays!
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        let clock = Clock::get()?;
+        let schedule = &mut ctx.accounts.vesting_schedule;
+        let claimable = schedule.vested_amount(clock.unix_timestamp)?
+            .saturating_sub(schedule.claimed_amount);
+        require!(claimable > 0, GreylockerError::NothingVested);
+
+        schedule.claimed_amount = schedule.claimed_amount
+            .checked_add(claimable)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        ctx.accounts.greylocker_state.total_vesting_claimed = ctx.accounts.greylocker_state.total_vesting_claimed
+            .checked_add(claimable)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
+        let signer = &[&state_seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vesting_escrow.to_account_info(),
+                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    authority: ctx.accounts.greylocker_state.to_account_info(),
+                },
+                signer,
+            ),
+            claimable,
+        )?;
+
+        emit!(ClaimVestedEvent {
+            vesting_schedule: ctx.accounts.vesting_schedule.key(),
+            beneficiary: ctx.accounts.beneficiary.key(),
+            amount: claimable,
+            total_claimed: ctx.accounts.vesting_schedule.claimed_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Create an airdrop—post a merkle root and fund it from the treasury!
+    /// Runs on a council proposal for exactly this airdrop once its timelock clears; the governance authority submits it.
+    pub fn create_airdrop(
+        ctx: Context<CreateAirdrop>,
+        merkle_root: [u8; 32],
+        num_leaves: u32,
+        total_amount: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.governance_authority.key() == ctx.accounts.greylocker_state.governance_authority,
+            GreylockerError::NotAuthorized
+        );
+        require!(num_leaves > 0 && num_leaves <= MAX_AIRDROP_LEAVES, GreylockerError::InvalidAirdrop);
+        require!(total_amount > 0, GreylockerError::InvalidAirdrop);
+        let clock = Clock::get()?;
+        require!(expires_at > clock.unix_timestamp, GreylockerError::InvalidAirdrop);
+        carry_out_proposal(
+            &mut ctx.accounts.proposal,
+            &ctx.accounts.governance_council,
+            ProposalAction::CreateAirdrop { merkle_root, num_leaves, total_amount, expires_at },
+            ctx.accounts.governance_authority.key(),
+            clock.unix_timestamp,
+        )?;
+        ctx.accounts.greylocker_state.charge_treasury(total_amount, clock.unix_timestamp)?;
+
+        let distributor = &mut ctx.accounts.distributor;
+        distributor.merkle_root = merkle_root;
+        distributor.vault = ctx.accounts.airdrop_vault.key();
+        distributor.total_amount = total_amount;
+        distributor.claimed_amount = 0;
+        distributor.num_leaves = num_leaves;
+        distributor.claimed_count = 0;
+        distributor.expires_at = expires_at;
+        distributor.clawed_back = false;
+        distributor.claimed_bitmap = vec![0; (num_leaves as usize).div_ceil(8)];
+        distributor.bump = *ctx.bumps.get("distributor").unwrap();
+
+        let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
+        let signer = &[&state_seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.airdrop_vault.to_account_info(),
+                    authority: ctx.accounts.greylocker_state.to_account_info(),
+                },
+                signer,
+            ),
+            total_amount,
+        )?;
+
+        emit!(CreateAirdropEvent {
+            distributor: ctx.accounts.distributor.key(),
+            merkle_root,
+            num_leaves,
+            total_amount,
+            expires_at,
+        });
+
+        Ok(())
+    }
+
+    /// Claim an airdrop—prove you're on the list and collect your GREY!
+    pub fn claim_airdrop(ctx: Context<ClaimAirdrop>, index: u32, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let claimant = ctx.accounts.claimant.key();
+        ctx.accounts.distributor.record_claim(index, &claimant, amount, &proof, clock.unix_timestamp)?;
+
+        let merkle_root = ctx.accounts.distributor.merkle_root;
+        let distributor_seeds = &[b"distributor".as_ref(), merkle_root.as_ref(), &[ctx.accounts.distributor.bump]];
+        let signer = &[&distributor_seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.airdrop_vault.to_account_info(),
+                    to: ctx.accounts.claimant_token_account.to_account_info(),
+                    authority: ctx.accounts.distributor.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        emit!(ClaimAirdropEvent {
+            distributor: ctx.accounts.distributor.key(),
+            claimant,
+            index,
+            amount,
+            stake_account: None,
+        });
+
+        Ok(())
+    }
+
+    /// Claim an airdrop straight into a new stake position—skip the wallet, start earning!
+    pub fn claim_airdrop_and_stake(
+        ctx: Context<ClaimAirdropAndStake>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        stake_type: StakeType,
+        lock_period: i64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let claimant = ctx.accounts.claimant.key();
+        ctx.accounts.distributor.record_claim(index, &claimant, amount, &proof, clock.unix_timestamp)?;
+
+        let state = &ctx.accounts.greylocker_state;
+        require!(amount >= min_stake_for(state, &stake_type), GreylockerError::InsufficientStakeAmount);
+        let (lock_tier, reward_multiplier_bps) = lock_tier_for(&state.lock_tiers, lock_period)?;
+        let lock_until = clock.unix_timestamp
+            .checked_add(lock_period)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let stake_registry = &mut ctx.accounts.stake_registry;
+        if stake_registry.owner == Pubkey::default() {
+            stake_registry.owner = claimant;
+            stake_registry.next_position_id = 0;
+            stake_registry.open_positions = 0;
+            stake_registry.bump = *ctx.bumps.get("stake_registry").unwrap();
+        }
+
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.owner = claimant;
+        stake_account.position_id = stake_registry.next_position_id;
+        stake_account.stake_type = stake_type.clone();
+        stake_account.amount = amount;
+        stake_account.lock_until = lock_until;
+        stake_account.lock_tier = lock_tier;
+        stake_account.reward_multiplier_bps = reward_multiplier_bps;
+        stake_account.last_reward_claim = clock.unix_timestamp;
+        stake_account.accumulated_rewards = 0;
+        stake_account.reward_index_snapshot = current_reward_index(state, &stake_type, clock.unix_timestamp)?;
+        stake_account.staked_at = clock.unix_timestamp;
+        stake_account.last_increase_at = clock.unix_timestamp;
+        stake_account.bump = *ctx.bumps.get("stake_account").unwrap();
+
+        stake_registry.next_position_id = stake_registry.next_position_id
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        stake_registry.open_positions = stake_registry.open_positions
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let merkle_root = ctx.accounts.distributor.merkle_root;
+        let distributor_seeds = &[b"distributor".as_ref(), merkle_root.as_ref(), &[ctx.accounts.distributor.bump]];
+        let signer = &[&distributor_seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.airdrop_vault.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                    authority: ctx.accounts.distributor.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+
+        ctx.accounts.greylocker_state.total_staked = ctx.accounts.greylocker_state.total_staked
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        emit!(ClaimAirdropEvent {
+            distributor: ctx.accounts.distributor.key(),
+            claimant,
+            index,
+            amount,
+            stake_account: Some(ctx.accounts.stake_account.key()),
+        });
+
+        Ok(())
+    }
+
+    /// Claw back an expired airdrop—unclaimed GREY goes home to the treasury!
+    pub fn clawback_airdrop(ctx: Context<ClawbackAirdrop>) -> Result<()> {
+        let clock = Clock::get()?;
+        let distributor = &mut ctx.accounts.distributor;
+        require!(clock.unix_timestamp >= distributor.expires_at, GreylockerError::AirdropNotExpired);
+        require!(!distributor.clawed_back, GreylockerError::AirdropExpired);
+        distributor.clawed_back = true;
+
+        let amount = ctx.accounts.airdrop_vault.amount;
+        let merkle_root = distributor.merkle_root;
+        let distributor_seeds = &[b"distributor".as_ref(), merkle_root.as_ref(), &[distributor.bump]];
+        let signer = &[&distributor_seeds[..]];
+        if amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.airdrop_vault.to_account_info(),
+                        to: ctx.accounts.treasury.to_account_info(),
+                        authority: ctx.accounts.distributor.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+
+        emit!(ClawbackAirdropEvent {
+            distributor: ctx.accounts.distributor.key(),
+            amount,
+            claimed_count: ctx.accounts.distributor.claimed_count,
+        });
+
+        Ok(())
+    }
+
+    /// Set up the reward reserve—every emission has to be paid for!
+    /// One-shot bootstrap by the governance authority; every later change is a ConfigureRewardReserve proposal.
+    pub fn initialize_reward_reserve(
+        ctx: Context<InitializeRewardReserve>,
+        epoch_emission_budget: u64,
+        epoch_duration: i64,
+        shortfall_mode: ReserveShortfallMode,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.governance_authority.key() == ctx.accounts.greylocker_state.governance_authority,
+            GreylockerError::NotAuthorized
+        );
+        require!(epoch_duration > 0, GreylockerError::InvalidParameterValue);
+
+        let clock = Clock::get()?;
+        let reserve = &mut ctx.accounts.reward_reserve;
+        reserve.vault = ctx.accounts.reserve_vault.key();
+        reserve.epoch_emission_budget = epoch_emission_budget;
+        reserve.epoch_duration = epoch_duration;
+        reserve.current_epoch = 0;
+        reserve.epoch_started_at = clock.unix_timestamp;
+        reserve.emitted_this_epoch = 0;
+        reserve.coverage_bps = 0; // Nothing funded yet
+        reserve.shortfall_mode = shortfall_mode.clone();
+        reserve.total_funded = 0;
+        reserve.total_emitted = 0;
+        reserve.total_forfeited = 0;
+        reserve.bump = *ctx.bumps.get("reward_reserve").unwrap();
+
+        emit!(InitializeRewardReserveEvent {
+            reward_reserve: reserve.key(),
+            vault: reserve.vault,
+            epoch_emission_budget,
+            epoch_duration,
+            shortfall_mode,
+        });
+
+        Ok(())
+    }
+
+    /// Fund the reward reserve—top up the well from the treasury or anyone generous!
+    pub fn fund_reward_reserve(ctx: Context<FundRewardReserve>, amount: u64) -> Result<()> {
+        require!(amount > 0, GreylockerError::InvalidParameterValue);
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder_token_account.to_account_info(),
+                    to: ctx.accounts.reserve_vault.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let new_balance = ctx.accounts.reserve_vault.amount
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let reserve = &mut ctx.accounts.reward_reserve;
+        reserve.total_funded = reserve.total_funded
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        reserve.raise_coverage(new_balance);
+
+        emit!(FundRewardReserveEvent {
+            funder: ctx.accounts.funder.key(),
+            amount,
+            reserve_balance: new_balance,
+            coverage_bps: reserve.coverage_bps,
+        });
+
+        Ok(())
+    }
+
+    /// Register as a service provider—step into the arena!
+    pub fn register_service(ctx: Context<RegisterService>, service_info: ServiceInfo) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let stake_account = &ctx.accounts.stake_account;
+        require!(stake_account.stake_type == StakeType::Service, GreylockerError::InvalidStakeType);
+        require!(
+            backing_stake(stake_account) >= ctx.accounts.greylocker_state.governance_parameters.service_stake_minimum,
+            GreylockerError::InsufficientStakeAmount
+        );
+
+        let clock = Clock::get()?;
+        let service_provider = &mut ctx.accounts.service_provider;
+        service_provider.owner = ctx.accounts.user.key();
+        service_provider.stake_account = stake_account.key();
+        service_provider.service_info = service_info;
+        service_provider.registered_at = clock.unix_timestamp;
+        service_provider.total_access_fees_paid = 0;
+        service_provider.reputation_fees = 0;
+        service_provider.upheld_disputes = 0;
+        service_provider.last_offense_at = 0;
+        service_provider.frozen = false;
+        service_provider.dismissed_disputes = 0;
+        service_provider.completed_access_grants = 0;
+        service_provider.penalty_points = 0;
+        service_provider.penalty_updated_at = clock.unix_timestamp;
+        service_provider.open_disputes = 0;
+        service_provider.access_grants_issued = 0;
+        service_provider.reputation_score = compute_reputation(service_provider, clock.unix_timestamp);
+        service_provider.bump = *ctx.bumps.get("service_provider").unwrap();
+
+        emit!(RegisterServiceEvent {
+            service_provider: service_provider.key(),
+            owner: service_provider.owner,
+            stake_account: stake_account.key(),
+            service_name: service_provider.service_info.name.clone(),
+        });
+
+        Ok(())
+    }
+
+    /// Reinstate a frozen service—restake and get back in the game!
+    pub fn reinstate_service(ctx: Context<ReinstateService>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let stake_account = &ctx.accounts.stake_account;
+        require!(stake_account.stake_type == StakeType::Service, GreylockerError::InvalidStakeType);
+        require!(
+            backing_stake(stake_account) >= ctx.accounts.greylocker_state.governance_parameters.service_stake_minimum,
+            GreylockerError::InsufficientStakeAmount
+        );
+
+        let service_provider = &mut ctx.accounts.service_provider;
+        require!(service_provider.frozen, GreylockerError::InvalidParameterValue);
+        service_provider.frozen = false;
+        service_provider.stake_account = stake_account.key();
+
+        emit!(ReinstateServiceEvent {
+            service_provider: service_provider.key(),
+            stake_account: stake_account.key(),
+            stake_amount: backing_stake(stake_account),
+        });
+
+        Ok(())
+    }
+
+    /// Record an access grant that ran its course—honest services earn their stripes!
+    pub fn record_access_expiry(ctx: Context<RecordAccessExpiry>) -> Result<()> {
+        let clock = Clock::get()?;
+        let access_record = &mut ctx.accounts.access_record;
+        require!(clock.unix_timestamp >= access_record.expires_at, GreylockerError::AccessNotExpired);
+        require!(!access_record.expiry_recorded, GreylockerError::AccessNotExpired);
+        require!(
+            earns_reputation(access_record.amount, access_record.expires_at.saturating_sub(access_record.granted_at)),
+            GreylockerError::InvalidParameterValue
+        ); // Free, tiny or fleeting grants earn nothing
+        access_record.expiry_recorded = true;
+
+        let service_provider = &mut ctx.accounts.service_provider;
+        service_provider.completed_access_grants = service_provider.completed_access_grants.saturating_add(1);
+        let service_key = service_provider.key();
+        refresh_reputation(service_provider, service_key, clock.unix_timestamp, ReputationReason::AccessCompleted);
+
+        Ok(())
+    }
+
+    /// Refresh a provider's reputation—tenure accrues and old sins fade!
+    pub fn refresh_service_reputation(ctx: Context<RefreshServiceReputation>) -> Result<()> {
+        let clock = Clock::get()?;
+        let service_provider = &mut ctx.accounts.service_provider;
+        let service_key = service_provider.key();
+        refresh_reputation(service_provider, service_key, clock.unix_timestamp, ReputationReason::Refresh);
+        Ok(())
+    }
+
+    /// Pay access fee—services reward users for their data!
+    pub fn pay_access_fee(ctx: Context<PayAccessFee>, amount: u64, data_type: DataType, duration: i64) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let service_provider = &mut ctx.accounts.service_provider;
+        require!(service_provider.owner == ctx.accounts.service_owner.key(), GreylockerError::NotAuthorized);
+        require!(!service_provider.frozen, GreylockerError::ServiceFrozen);
+        require!(ctx.accounts.user.key() != service_provider.owner, GreylockerError::SelfDealing);
+
+        service_provider.total_access_fees_paid = service_provider.total_access_fees_paid
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        if earns_reputation(amount, duration) {
+            service_provider.reputation_fees = service_provider.reputation_fees
+                .checked_add(amount)
+                .ok_or(GreylockerError::ArithmeticError)?;
+        }
+
+        let params = &ctx.accounts.greylocker_state.governance_parameters;
+        let split = split_protocol_fee(amount, params.protocol_fee_bps, params.fee_burn_share_bps)?;
+        pay_with_protocol_fee(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.grey_mint.to_account_info(),
+            &ctx.accounts.service_token_account.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.service_owner.to_account_info(),
+            &[],
+            &split,
+        )?;
+        ctx.accounts.greylocker_state.total_supply = ctx.accounts.greylocker_state.total_supply
+            .checked_sub(split.burned)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let clock = Clock::get()?;
+        let expires_at = record_access(
+            &mut ctx.accounts.access_record,
+            service_provider.key(),
+            ctx.accounts.user.key(),
+            data_type.clone(),
+            amount,
+            clock.unix_timestamp,
+            duration,
+            Pubkey::default(),
+            service_provider.access_grants_issued,
+            *ctx.bumps.get("access_record").unwrap(),
+        )?;
+        service_provider.access_grants_issued = service_provider.access_grants_issued
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let service_key = service_provider.key();
+        refresh_reputation(service_provider, service_key, clock.unix_timestamp, ReputationReason::AccessFeePaid);
+
+        emit!(PayAccessFeeEvent {
+            service_provider: service_provider.key(),
+            user: ctx.accounts.user.key(),
+            gross_amount: amount,
+            net_amount: split.net,
+            burned_amount: split.burned,
+            treasury_amount: split.to_treasury,
+            data_type,
+            expires_at,
+            access_grant: Pubkey::default(),
+        });
+
+        Ok(())
+    }
+
+    /// Settle a vault access grant—the vault program calls in here so every paid grant
+    /// is backed by a staked service provider, real GREY and an `AccessRecord`.
+    pub fn settle_vault_access(
+        ctx: Context<SettleVaultAccess>,
+        access_grant: Pubkey,
+        amount: u64,
+        data_type: DataType,
+        duration: i64,
+        min_reputation: u8,
+    ) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(duration > 0, GreylockerError::InvalidParameterValue);
+
+        let stake_account = &ctx.accounts.service_stake_account;
+        require!(stake_account.stake_type == StakeType::Service, GreylockerError::InvalidStakeType);
+        require!(
+            backing_stake(stake_account) >= ctx.accounts.greylocker_state.governance_parameters.service_stake_minimum,
+            GreylockerError::InsufficientStakeAmount
+        );
+
+        // The vault owner's reputation floor is checked against a freshly computed score
+        let clock = Clock::get()?;
+        let service_provider = &mut ctx.accounts.service_provider;
+        require!(!service_provider.frozen, GreylockerError::ServiceFrozen);
+        require!(ctx.accounts.user.key() != service_provider.owner, GreylockerError::SelfDealing);
+        require!(
+            compute_reputation(service_provider, clock.unix_timestamp) >= min_reputation,
+            GreylockerError::ReputationTooLow
+        );
+        service_provider.total_access_fees_paid = service_provider.total_access_fees_paid
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        if earns_reputation(amount, duration) {
+            service_provider.reputation_fees = service_provider.reputation_fees
+                .checked_add(amount)
+                .ok_or(GreylockerError::ArithmeticError)?;
+        }
+
+        let params = &ctx.accounts.greylocker_state.governance_parameters;
+        let split = split_protocol_fee(amount, params.protocol_fee_bps, params.fee_burn_share_bps)?;
+        pay_with_protocol_fee(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.grey_mint.to_account_info(),
+            &ctx.accounts.service_token_account.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.service_owner.to_account_info(),
+            &[],
+            &split,
+        )?;
+        ctx.accounts.greylocker_state.total_supply = ctx.accounts.greylocker_state.total_supply
+            .checked_sub(split.burned)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let expires_at = record_access(
+            &mut ctx.accounts.access_record,
+            service_provider.key(),
+            ctx.accounts.user.key(),
+            data_type.clone(),
+            amount,
+            clock.unix_timestamp,
+            duration,
+            access_grant,
+            service_provider.access_grants_issued,
+            *ctx.bumps.get("access_record").unwrap(),
+        )?;
+        service_provider.access_grants_issued = service_provider.access_grants_issued
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let service_key = service_provider.key();
+        refresh_reputation(service_provider, service_key, clock.unix_timestamp, ReputationReason::AccessFeePaid);
+
+        emit!(PayAccessFeeEvent {
+            service_provider: service_provider.key(),
+            user: ctx.accounts.user.key(),
+            gross_amount: amount,
+            net_amount: split.net,
+            burned_amount: split.burned,
+            treasury_amount: split.to_treasury,
+            data_type,
+            expires_at,
+            access_grant,
+        });
+
+        Ok(())
+    }
+
+    /// Create a dispute—call out the bad actors!
+    pub fn create_dispute(ctx: Context<CreateDispute>, evidence: String, dispute_type: DisputeType) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(evidence.len() <= MAX_EVIDENCE_LEN, GreylockerError::EvidenceTooLong);
+
+        let clock = Clock::get()?;
+        let reporter_record = &mut ctx.accounts.reporter_record;
+        if reporter_record.reporter == Pubkey::default() {
+            reporter_record.reporter = ctx.accounts.reporter.key();
+            reporter_record.window_started_at = clock.unix_timestamp;
+            reporter_record.bump = *ctx.bumps.get("reporter_record").unwrap();
+        }
+        reporter_record.record_filing(clock.unix_timestamp)?;
+
+        let reporter_bond = ctx.accounts.greylocker_state.governance_parameters.reporter_bond;
+        if reporter_bond > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.reporter_token_account.to_account_info(),
+                        to: ctx.accounts.reporter_bond_vault.to_account_info(),
+                        authority: ctx.accounts.reporter.to_account_info(),
+                    },
+                ),
+                reporter_bond,
+            )?;
+        }
+
+        let service_provider = &mut ctx.accounts.service_provider;
+        service_provider.open_disputes = service_provider.open_disputes
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        // Filed through a provider pool: the pool can't be closed out from under the dispute
+        let service_key = service_provider.key();
+        let data_pool_key = match ctx.accounts.data_pool.as_mut() {
+            Some(data_pool) => {
+                require!(data_pool.service_provider == service_key, GreylockerError::NotAuthorized);
+                data_pool.open_disputes = data_pool.open_disputes
+                    .checked_add(1)
+                    .ok_or(GreylockerError::ArithmeticError)?;
+                Some(data_pool.key())
+            },
+            None => None,
+        };
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.dispute_id = dispute.key();
+        dispute.reporter = ctx.accounts.reporter.key();
+        dispute.reported_service = ctx.accounts.service_provider.key();
+        dispute.accused = ctx.accounts.service_provider.owner;
+        dispute.evidence = evidence;
+        dispute.counter_evidence = String::new();
+        dispute.created_at = clock.unix_timestamp;
+        dispute.status = DisputeStatus::Pending;
+        dispute.resolution = None;
+        dispute.resolved_at = None;
+        dispute.dispute_type = dispute_type.clone();
+        dispute.round = 0;
+        dispute.jury_size = JURY_SIZE;
+        dispute.jurors_seated = 0;
+        dispute.votes_upheld = 0;
+        dispute.votes_dismissed = 0;
+        dispute.phase_deadline = clock.unix_timestamp
+            .checked_add(DISPUTE_SEATING_PERIOD)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        dispute.appellant = Pubkey::default();
+        dispute.appeal_bond = 0;
+        dispute.appeal_bonds_posted = 0;
+        dispute.executed = false;
+        dispute.reporter_bond = reporter_bond;
+        dispute.data_pool = data_pool_key;
+        dispute.drawn_jurors = Vec::new();
+        dispute.draw_slot = clock.slot
+            .checked_add(JURY_DRAW_DELAY_SLOTS)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        dispute.bump = *ctx.bumps.get("dispute").unwrap();
+
+        emit!(CreateDisputeEvent {
+            dispute_id: dispute.dispute_id,
+            reporter: dispute.reporter,
+            reported_service: dispute.reported_service,
+            dispute_type,
+            reporter_bond,
+            data_pool: data_pool_key,
+        });
+
+        Ok(())
+    }
+
+    /// Answer the charges—the accused gets their say!
+    pub fn respond_to_dispute(ctx: Context<RespondToDispute>, counter_evidence: String) -> Result<()> {
+        require!(counter_evidence.len() <= MAX_EVIDENCE_LEN, GreylockerError::EvidenceTooLong);
+
+        let dispute = &mut ctx.accounts.dispute;
+        require!(
+            dispute.status == DisputeStatus::Pending || dispute.status == DisputeStatus::ResponseWindow,
+            GreylockerError::InvalidDisputePhase
+        );
+        require!(dispute.counter_evidence.is_empty(), GreylockerError::AlreadyResponded);
+        dispute.counter_evidence = counter_evidence.clone();
+
+        emit!(DisputeResponseEvent {
+            dispute_id: dispute.dispute_id,
+            accused: ctx.accounts.owner.key(),
+            counter_evidence,
+        });
+
+        Ok(())
+    }
+
+    /// Join the juror pool—DataValidator stakers volunteer, the draw decides who sits!
+    /// Draw weight is the position's own stake; delegated GREY buys no seats.
+    pub fn register_juror(ctx: Context<RegisterJuror>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let stake_account = &mut ctx.accounts.juror_stake_account;
+        require!(stake_account.stake_type == StakeType::DataValidator, GreylockerError::InvalidStakeType);
+        require!(
+            stake_account.amount >= ctx.accounts.greylocker_state.governance_parameters.data_validator_stake_minimum,
+            GreylockerError::InsufficientStakeAmount
+        );
+        require!(!stake_account.juror_registered, GreylockerError::JurorAlreadyRegistered);
+
+        let juror_registry = &mut ctx.accounts.juror_registry;
+        if juror_registry.entries.is_empty() && juror_registry.total_weight == 0 {
+            juror_registry.bump = *ctx.bumps.get("juror_registry").unwrap();
+        }
+        require!(juror_registry.entries.len() < MAX_REGISTERED_JURORS, GreylockerError::JurorRegistryFull);
+
+        let weight = stake_account.amount;
+        juror_registry.entries.push(JurorEntry {
+            juror: stake_account.owner,
+            stake_account: stake_account.key(),
+            weight,
+        });
+        juror_registry.total_weight = juror_registry.total_weight
+            .checked_add(weight)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        stake_account.juror_registered = true;
+
+        emit!(JurorRegistryEvent {
+            juror: stake_account.owner,
+            stake_account: stake_account.key(),
+            weight,
+            registered: true,
+        });
+
+        Ok(())
+    }
+
+    /// Leave the juror pool—seats already taken stay locked until their disputes resolve!
+    pub fn deregister_juror(ctx: Context<DeregisterJuror>) -> Result<()> {
+        let stake_key = ctx.accounts.juror_stake_account.key();
+        let juror_registry = &mut ctx.accounts.juror_registry;
+        let index = juror_registry.entries
+            .iter()
+            .position(|entry| entry.stake_account == stake_key)
+            .ok_or(GreylockerError::JurorNotRegistered)?;
+        let entry = juror_registry.entries.swap_remove(index);
+        juror_registry.total_weight = juror_registry.total_weight.saturating_sub(entry.weight);
+
+        let stake_account = &mut ctx.accounts.juror_stake_account;
+        stake_account.juror_registered = false;
+
+        emit!(JurorRegistryEvent {
+            juror: entry.juror,
+            stake_account: stake_key,
+            weight: entry.weight,
+            registered: false,
+        });
+
+        Ok(())
+    }
+
+    /// Draw the jury—stake-weighted and seeded by a slot hash nobody could see at filing!
+    /// If the seed slot has aged out of SlotHashes the draw is re-armed for a fresh future slot.
+    pub fn draw_jury(ctx: Context<DrawJury>) -> Result<()> {
+        let clock = Clock::get()?;
+        let dispute = &mut ctx.accounts.dispute;
+        require!(dispute.is_seating(), GreylockerError::InvalidDisputePhase);
+        require!(clock.unix_timestamp < dispute.phase_deadline, GreylockerError::InvalidDisputePhase);
+        require!(dispute.drawn_jurors.is_empty(), GreylockerError::JuryAlreadyDrawn);
+        require!(clock.slot > dispute.draw_slot, GreylockerError::JuryDrawNotReady);
+
+        let slot_hash = match find_slot_hash(&ctx.accounts.slot_hashes, dispute.draw_slot)? {
+            Some(hash) => hash,
+            None => {
+                dispute.draw_slot = clock.slot
+                    .checked_add(JURY_DRAW_DELAY_SLOTS)
+                    .ok_or(GreylockerError::ArithmeticError)?;
+                emit!(JuryDrawRearmedEvent {
+                    dispute_id: dispute.dispute_id,
+                    round: dispute.round,
+                    draw_slot: dispute.draw_slot,
+                });
+                return Ok(());
+            },
+        };
+
+        let seed = keccak::hashv(&[&slot_hash, dispute.dispute_id.as_ref(), &[dispute.round]]).0;
+        let jurors = draw_jurors(&ctx.accounts.juror_registry, seed, dispute.jury_size, &[dispute.reporter, dispute.accused]);
+        require!(!jurors.is_empty(), GreylockerError::JurorRegistryEmpty);
+        dispute.drawn_jurors = jurors.clone();
+
+        emit!(JuryDrawnEvent {
+            dispute_id: dispute.dispute_id,
+            round: dispute.round,
+            jurors,
+        });
+
+        Ok(())
+    }
+
+    /// Take a drawn jury seat—the juror's stake stays locked until the dispute resolves!
+    pub fn join_jury(ctx: Context<JoinJury>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let juror = ctx.accounts.juror.key();
+        let stake_account = &mut ctx.accounts.juror_stake_account;
+        require!(stake_account.stake_type == StakeType::DataValidator, GreylockerError::InvalidStakeType);
+        require!(
+            stake_account.amount >= ctx.accounts.greylocker_state.governance_parameters.data_validator_stake_minimum,
+            GreylockerError::InsufficientStakeAmount
+        );
+
+        let dispute = &mut ctx.accounts.dispute;
+        require!(dispute.is_seating(), GreylockerError::InvalidDisputePhase);
+        require!(clock.unix_timestamp < dispute.phase_deadline, GreylockerError::InvalidDisputePhase);
+        require!(dispute.jurors_seated < dispute.jury_size, GreylockerError::JuryFull);
+        require!(juror != dispute.reporter && juror != dispute.accused, GreylockerError::ConflictedJuror);
+        require!(dispute.drawn_jurors.contains(&juror), GreylockerError::JurorNotDrawn);
+
+        stake_account.jury_duties = stake_account.jury_duties
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let juror_record = &mut ctx.accounts.juror_record;
+        juror_record.dispute = dispute.key();
+        juror_record.juror = juror;
+        juror_record.stake_account = stake_account.key();
+        juror_record.round = dispute.round;
+        juror_record.vote = None;
+        juror_record.bump = *ctx.bumps.get("juror_record").unwrap();
+
+        dispute.jurors_seated += 1;
+        if dispute.jurors_seated as usize == dispute.drawn_jurors.len() {
+            dispute.close_seating(clock.unix_timestamp)?;
+        }
+
+        emit!(JurorSeatedEvent {
+            dispute_id: dispute.dispute_id,
+            juror,
+            round: dispute.round,
+            jurors_seated: dispute.jurors_seated,
+        });
+
+        Ok(())
+    }
+
+    /// Cast a jury vote—uphold or dismiss!
+    pub fn cast_jury_vote(ctx: Context<CastJuryVote>, resolution: DisputeResolution) -> Result<()> {
+        let clock = Clock::get()?;
+        let dispute = &mut ctx.accounts.dispute;
+        require!(dispute.status == DisputeStatus::Voting, GreylockerError::InvalidDisputePhase);
+        require!(clock.unix_timestamp < dispute.phase_deadline, GreylockerError::InvalidDisputePhase);
+
+        let juror_record = &mut ctx.accounts.juror_record;
+        require!(juror_record.vote.is_none(), GreylockerError::AlreadyVoted);
+        juror_record.vote = Some(resolution.clone());
+        match resolution {
+            DisputeResolution::Upheld => dispute.votes_upheld += 1,
+            DisputeResolution::Dismissed => dispute.votes_dismissed += 1,
+        }
+
+        emit!(JuryVoteEvent {
+            dispute_id: dispute.dispute_id,
+            juror: juror_record.juror,
+            round: dispute.round,
+            resolution,
+        });
+
+        Ok(())
+    }
+
+    /// Step down from a finished case—the ruling is executed, so the juror's stake is free again!
+    pub fn release_juror(ctx: Context<ReleaseJuror>) -> Result<()> {
+        require!(ctx.accounts.dispute.executed, GreylockerError::InvalidDisputePhase);
+
+        let stake_account = &mut ctx.accounts.juror_stake_account;
+        stake_account.jury_duties = stake_account.jury_duties.saturating_sub(1);
+
+        emit!(JurorReleasedEvent {
+            dispute_id: ctx.accounts.dispute.dispute_id,
+            juror: ctx.accounts.juror_record.juror,
+            round: ctx.accounts.juror_record.round,
+        });
+
+        Ok(())
+    }
+
+    /// Move a dispute along its timeline—anyone can turn the crank!
+    pub fn advance_dispute(ctx: Context<AdvanceDispute>) -> Result<()> {
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        let dispute = &mut ctx.accounts.dispute;
+        require!(now >= dispute.phase_deadline, GreylockerError::DisputePhaseNotOver);
+
+        match dispute.status {
+            // An empty jury has nobody to hear the case; fall straight through to a ruling
+            DisputeStatus::Pending => {
+                if dispute.jurors_seated == 0 {
+                    dispute.record_ruling(now)?;
+                } else {
+                    dispute.close_seating(now)?;
+                }
+            },
+            DisputeStatus::ResponseWindow => {
+                dispute.status = DisputeStatus::Voting;
+                dispute.phase_deadline = now
+                    .checked_add(DISPUTE_VOTING_PERIOD)
+                    .ok_or(GreylockerError::ArithmeticError)?;
+            },
+            DisputeStatus::Voting => dispute.record_ruling(now)?,
+            DisputeStatus::Appealed => {
+                if dispute.appeal_bonds_posted < 2 {
+                    // Unmatched appeal never reached a jury—the original ruling stands and the bond goes back
+                    dispute.round += 1;
+                    dispute.status = DisputeStatus::Resolved;
+                    dispute.phase_deadline = now;
+                } else if dispute.jurors_seated == 0 {
+                    dispute.record_ruling(now)?;
+                } else {
+                    dispute.close_seating(now)?;
+                }
+            },
+            DisputeStatus::Resolved => return err!(GreylockerError::InvalidDisputePhase),
+        }
+
+        emit!(AdvanceDisputeEvent {
+            dispute_id: dispute.dispute_id,
+            status: dispute.status.clone(),
+            resolution: dispute.resolution.clone(),
+            phase_deadline: dispute.phase_deadline,
+        });
+
+        Ok(())
+    }
+
+    /// Appeal a ruling—put your bond where your mouth is!
+    pub fn appeal_dispute(ctx: Context<AppealDispute>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let appellant = ctx.accounts.appellant.key();
+        let appeal_bond = ctx.accounts.greylocker_state.governance_parameters.appeal_bond;
+        let dispute = &mut ctx.accounts.dispute;
+        require!(dispute.status == DisputeStatus::Resolved, GreylockerError::InvalidDisputePhase);
+        require!(clock.unix_timestamp < dispute.phase_deadline, GreylockerError::AppealWindowClosed);
+        require!(dispute.round < MAX_APPEAL_ROUNDS, GreylockerError::AppealWindowClosed);
+        require!(appellant == dispute.losing_party(), GreylockerError::NotAuthorized);
+
+        dispute.status = DisputeStatus::Appealed;
+        dispute.appellant = appellant;
+        dispute.appeal_bond = appeal_bond;
+        dispute.appeal_bonds_posted = 1;
+        dispute.phase_deadline = clock.unix_timestamp
+            .checked_add(DISPUTE_APPEAL_PERIOD)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        if appeal_bond > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.appellant_token_account.to_account_info(),
+                        to: ctx.accounts.bond_vault.to_account_info(),
+                        authority: ctx.accounts.appellant.to_account_info(),
+                    },
+                ),
+                appeal_bond,
+            )?;
+        }
+
+        emit!(AppealDisputeEvent {
+            dispute_id: ctx.accounts.dispute.dispute_id,
+            appellant,
+            appeal_bond,
+            match_deadline: ctx.accounts.dispute.phase_deadline,
+        });
+
+        Ok(())
+    }
+
+    /// Match an appeal bond—defend the ruling before a bigger jury!
+    pub fn post_appeal_bond(ctx: Context<PostAppealBond>) -> Result<()> {
+        let clock = Clock::get()?;
+        let respondent = ctx.accounts.respondent.key();
+        let dispute = &mut ctx.accounts.dispute;
+        require!(dispute.status == DisputeStatus::Appealed, GreylockerError::InvalidDisputePhase);
+        require!(dispute.appeal_bonds_posted == 1, GreylockerError::InvalidDisputePhase);
+        require!(clock.unix_timestamp < dispute.phase_deadline, GreylockerError::AppealWindowClosed);
+        require!(
+            (respondent == dispute.reporter || respondent == dispute.accused) && respondent != dispute.appellant,
+            GreylockerError::NotAuthorized
+        );
+
+        // Fresh, larger jury for the appeal round
+        dispute.appeal_bonds_posted = 2;
+        dispute.round += 1;
+        dispute.jury_size = APPEAL_JURY_SIZE;
+        dispute.jurors_seated = 0;
+        dispute.votes_upheld = 0;
+        dispute.votes_dismissed = 0;
+        dispute.phase_deadline = clock.unix_timestamp
+            .checked_add(DISPUTE_SEATING_PERIOD)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        dispute.drawn_jurors = Vec::new();
+        dispute.draw_slot = clock.slot
+            .checked_add(JURY_DRAW_DELAY_SLOTS)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let appeal_bond = dispute.appeal_bond;
+        if appeal_bond > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.respondent_token_account.to_account_info(),
+                        to: ctx.accounts.bond_vault.to_account_info(),
+                        authority: ctx.accounts.respondent.to_account_info(),
+                    },
+                ),
+                appeal_bond,
+            )?;
+        }
+
+        emit!(AppealBondPostedEvent {
+            dispute_id: ctx.accounts.dispute.dispute_id,
+            respondent,
+            appeal_bond,
+            round: ctx.accounts.dispute.round,
+        });
+
+        Ok(())
+    }
+
+    /// Carry out a final ruling—justice is served, appeals exhausted!
+    /// Slashing waits for the appeal window; the bond pot goes to whichever side prevailed.
+    pub fn execute_dispute_ruling(ctx: Context<ExecuteDisputeRuling>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let dispute = &mut ctx.accounts.dispute;
+        require!(dispute.status == DisputeStatus::Resolved, GreylockerError::InvalidDisputePhase);
+        require!(!dispute.executed, GreylockerError::DisputeAlreadyResolved);
+        require!(clock.unix_timestamp >= dispute.phase_deadline, GreylockerError::DisputePhaseNotOver);
+
+        let resolution = dispute.resolution.clone().ok_or(GreylockerError::InvalidDisputePhase)?;
+        let dispute_type = dispute.dispute_type.clone();
+        dispute.executed = true;
+        dispute.resolved_at = Some(clock.unix_timestamp);
+
+        let dispute_key = dispute.key();
+        let dispute_id = dispute.dispute_id;
+        let reporter = dispute.reporter;
+        let reported_service = dispute.reported_service;
+        let dispute_bump = dispute.bump;
+        require!(
+            dispute.appeal_bonds_posted == 0 || ctx.accounts.bond_vault.is_some(),
+            GreylockerError::MissingBondVault
+        );
+
+        if let Some(bond_vault) = ctx.accounts.bond_vault.as_ref() {
+            require!(
+                bond_vault.key() == Pubkey::find_program_address(&[b"dispute-bond", dispute_key.as_ref()], &crate::ID).0,
+                GreylockerError::NotAuthorized
+            );
+            // A lone, unmatched appeal bond is refunded; a contested pot goes to whoever prevailed
+            let winner_token_account = if dispute.appeal_bonds_posted == 1 {
+                if dispute.appellant == reporter {
+                    ctx.accounts.reporter_token_account.to_account_info()
+                } else {
+                    ctx.accounts.accused_token_account.to_account_info()
+                }
+            } else {
+                match resolution {
+                    DisputeResolution::Upheld => ctx.accounts.reporter_token_account.to_account_info(),
+                    DisputeResolution::Dismissed => ctx.accounts.accused_token_account.to_account_info(),
+                }
+            };
+            if bond_vault.amount > 0 {
+                let dispute_seeds = &[b"dispute".as_ref(), reporter.as_ref(), reported_service.as_ref(), &[dispute_bump]];
+                let signer = &[&dispute_seeds[..]];
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: bond_vault.to_account_info(),
+                            to: winner_token_account,
+                            authority: ctx.accounts.dispute.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    bond_vault.amount,
+                )?;
+            }
+        }
+
+        // Reporter bond: refunded when upheld; otherwise partly burned and the rest paid to the accused
+        let reporter_bond = ctx.accounts.reporter_bond_vault.amount;
+        let (bond_refund, bond_burn, bond_to_accused) = match resolution {
+            DisputeResolution::Upheld => (reporter_bond, 0, 0),
+            DisputeResolution::Dismissed => {
+                let burn = reporter_bond
+                    .checked_mul(REJECTED_BOND_BURN_BPS)
+                    .and_then(|val| val.checked_div(10_000))
+                    .ok_or(GreylockerError::ArithmeticError)?;
+                (0, burn, reporter_bond - burn)
+            },
+        };
+        {
+            let dispute_seeds = &[b"dispute".as_ref(), reporter.as_ref(), reported_service.as_ref(), &[dispute_bump]];
+            let signer = &[&dispute_seeds[..]];
+            for (amount, to) in [
+                (bond_refund, ctx.accounts.reporter_token_account.to_account_info()),
+                (bond_to_accused, ctx.accounts.accused_token_account.to_account_info()),
+            ] {
+                if amount > 0 {
+                    token::transfer(
+                        CpiContext::new_with_signer(
+                            ctx.accounts.token_program.to_account_info(),
+                            Transfer {
+                                from: ctx.accounts.reporter_bond_vault.to_account_info(),
+                                to,
+                                authority: ctx.accounts.dispute.to_account_info(),
+                            },
+                            signer,
+                        ),
+                        amount,
+                    )?;
+                }
+            }
+            if bond_burn > 0 {
+                token::burn(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Burn {
+                            mint: ctx.accounts.grey_mint.to_account_info(),
+                            from: ctx.accounts.reporter_bond_vault.to_account_info(),
+                            authority: ctx.accounts.dispute.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    bond_burn,
+                )?;
+                ctx.accounts.greylocker_state.total_supply = ctx.accounts.greylocker_state.total_supply
+                    .checked_sub(bond_burn)
+                    .ok_or(GreylockerError::ArithmeticError)?;
+            }
+        }
+
+        let reporter_record = &mut ctx.accounts.reporter_record;
+        reporter_record.open_disputes = reporter_record.open_disputes.saturating_sub(1);
+        ctx.accounts.service_provider.open_disputes = ctx.accounts.service_provider.open_disputes.saturating_sub(1);
+
+        // Pool-level hook: an upheld dispute shuts the provider's pool to new joins and submissions
+        if let Some(data_pool_key) = ctx.accounts.dispute.data_pool {
+            let data_pool = ctx.accounts.data_pool.as_mut().ok_or(GreylockerError::MissingDataPool)?;
+            require!(data_pool.key() == data_pool_key, GreylockerError::NotAuthorized);
+            data_pool.open_disputes = data_pool.open_disputes.saturating_sub(1);
+            if resolution == DisputeResolution::Upheld && data_pool.active {
+                data_pool.active = false;
+                data_pool.paused = false;
+                emit!(DataPoolStatusEvent {
+                    pool_id: data_pool_key,
+                    active: false,
+                    paused: false,
+                });
+            }
+        }
+        if resolution == DisputeResolution::Dismissed {
+            reporter_record.total_rejected = reporter_record.total_rejected
+                .checked_add(1)
+                .ok_or(GreylockerError::ArithmeticError)?;
+        }
+
+        emit!(ReporterBondSettledEvent {
+            dispute_id,
+            reporter,
+            refunded: bond_refund,
+            burned: bond_burn,
+            paid_to_accused: bond_to_accused,
+        });
+
+        if resolution == DisputeResolution::Upheld {
+            let params = &ctx.accounts.greylocker_state.governance_parameters;
+            let service_provider = &mut ctx.accounts.service_provider;
+            let stake_account = &mut ctx.accounts.stake_account;
+
+            // Graduated by offense type and escalated for every prior upheld dispute
+            let prior_offenses = service_provider.upheld_disputes;
+            let slash_percentage = slash_percentage_for(params, &dispute_type, prior_offenses);
+            let service_stake_minimum = params.service_stake_minimum;
+            let reputation_penalty = reputation_penalty_for(&dispute_type, prior_offenses);
+            service_provider.penalty_points = decayed_penalty(
+                service_provider.penalty_points,
+                service_provider.penalty_updated_at,
+                clock.unix_timestamp,
+            )
+            .saturating_add(reputation_penalty as u32);
+            service_provider.penalty_updated_at = clock.unix_timestamp;
+            service_provider.upheld_disputes = prior_offenses.saturating_add(1);
+            service_provider.last_offense_at = clock.unix_timestamp;
+            let service_key = service_provider.key();
+            refresh_reputation(service_provider, service_key, clock.unix_timestamp, ReputationReason::DisputeUpheld);
+
+            // Unbonding GREY is still at risk, so slash on the full position
+            let slashable = stake_account.amount
+                .checked_add(stake_account.unbonding_amount)
+                .ok_or(GreylockerError::ArithmeticError)?;
+            let slash_amount = slashable
+                .checked_mul(slash_percentage)
+                .and_then(|val| val.checked_div(100))
+                .ok_or(GreylockerError::ArithmeticError)?;
+            let burn_amount = slash_amount / 2;
+            let reporter_amount = slash_amount - burn_amount;
+
+            // Take from active stake first, then from the unbonding balance
+            let from_active = slash_amount.min(stake_account.amount);
+            let from_unbonding = slash_amount - from_active;
+            stake_account.amount = stake_account.amount
+                .checked_sub(from_active)
+                .ok_or(GreylockerError::ArithmeticError)?;
+            stake_account.unbonding_amount = stake_account.unbonding_amount
+                .checked_sub(from_unbonding)
+                .ok_or(GreylockerError::ArithmeticError)?;
+            ctx.accounts.greylocker_state.total_staked = ctx.accounts.greylocker_state.total_staked
+                .checked_sub(from_active)
+                .ok_or(GreylockerError::ArithmeticError)?;
+
+            // Delegators share the operator's fate, at the same percentage
+            if stake_account.delegation_open {
+                let stake_key = stake_account.key();
+                let delegation_pool = ctx.accounts.delegation_pool.as_mut().ok_or(GreylockerError::MissingDelegationPool)?;
+                let delegation_vault = ctx.accounts.delegation_vault.as_ref().ok_or(GreylockerError::MissingDelegationPool)?;
+                require!(
+                    delegation_pool.key() == Pubkey::find_program_address(&[b"delegation-pool", stake_key.as_ref()], &crate::ID).0,
+                    GreylockerError::NotAuthorized
+                );
+                require!(delegation_vault.key() == delegation_pool.vault, GreylockerError::NotAuthorized);
+
+                delegation_pool.accrue(&ctx.accounts.greylocker_state, clock.unix_timestamp)?;
+                let (from_delegated, from_delegated_unbonding) = delegation_pool.slash(slash_percentage)?;
+                stake_account.delegated_amount = stake_account.delegated_amount.saturating_sub(from_delegated);
+                let delegated_slash = from_delegated
+                    .checked_add(from_delegated_unbonding)
+                    .ok_or(GreylockerError::ArithmeticError)?;
+
+                let delegated_burn = delegated_slash / 2;
+                let delegated_to_reporter = delegated_slash - delegated_burn;
+                let pool_bump = delegation_pool.bump;
+                let pool_seeds = &[b"delegation-pool".as_ref(), stake_key.as_ref(), &[pool_bump]];
+                let signer = &[&pool_seeds[..]];
+                if delegated_to_reporter > 0 {
+                    token::transfer(
+                        CpiContext::new_with_signer(
+                            ctx.accounts.token_program.to_account_info(),
+                            Transfer {
+                                from: delegation_vault.to_account_info(),
+                                to: ctx.accounts.reporter_token_account.to_account_info(),
+                                authority: delegation_pool.to_account_info(),
+                            },
+                            signer,
+                        ),
+                        delegated_to_reporter,
+                    )?;
+                }
+                if delegated_burn > 0 {
+                    token::burn(
+                        CpiContext::new_with_signer(
+                            ctx.accounts.token_program.to_account_info(),
+                            Burn {
+                                mint: ctx.accounts.grey_mint.to_account_info(),
+                                from: delegation_vault.to_account_info(),
+                                authority: delegation_pool.to_account_info(),
+                            },
+                            signer,
+                        ),
+                        delegated_burn,
+                    )?;
+                    ctx.accounts.greylocker_state.total_supply = ctx.accounts.greylocker_state.total_supply
+                        .checked_sub(delegated_burn)
+                        .ok_or(GreylockerError::ArithmeticError)?;
+                }
+
+                emit!(DelegationSlashEvent {
+                    delegation_pool: delegation_pool.key(),
+                    dispute_id,
+                    slashed_delegated: from_delegated,
+                    slashed_unbonding: from_delegated_unbonding,
+                    burn_amount: delegated_burn,
+                    reporter_amount: delegated_to_reporter,
+                });
+            }
+
+            let frozen = backing_stake(stake_account) < service_stake_minimum;
+            service_provider.frozen = service_provider.frozen || frozen;
+
+            let stake_key = stake_account.key();
+            let owner = stake_account.owner;
+            let position_id = stake_account.position_id.to_le_bytes();
+            let bump = stake_account.bump;
+            let stake_seeds = &[b"stake-account".as_ref(), owner.as_ref(), position_id.as_ref(), &[bump]];
+            let signer = &[&stake_seeds[..]];
+
+            if reporter_amount > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.stake_vault.to_account_info(),
+                            to: ctx.accounts.reporter_token_account.to_account_info(),
+                            authority: ctx.accounts.stake_account.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    reporter_amount,
+                )?;
+            }
+
+            if burn_amount > 0 {
+                token::burn(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Burn {
+                            mint: ctx.accounts.grey_mint.to_account_info(),
+                            from: ctx.accounts.stake_vault.to_account_info(),
+                            authority: ctx.accounts.stake_account.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    burn_amount,
+                )?;
+                ctx.accounts.greylocker_state.total_supply = ctx.accounts.greylocker_state.total_supply
+                    .checked_sub(burn_amount)
+                    .ok_or(GreylockerError::ArithmeticError)?;
+            }
+
+            emit!(SlashEvent {
+                stake_account: stake_key,
+                dispute_id,
+                slash_amount,
+                burn_amount,
+                reporter_amount,
+                slash_percentage: slash_percentage as u8,
+                prior_offenses,
+            });
+
+            if frozen {
+                emit!(ServiceFrozenEvent {
+                    service_provider: ctx.accounts.service_provider.key(),
+                    stake_account: stake_key,
+                    remaining_stake: backing_stake(&ctx.accounts.stake_account),
+                });
+            }
+        }
+
+        if resolution == DisputeResolution::Dismissed {
+            let service_provider = &mut ctx.accounts.service_provider;
+            service_provider.dismissed_disputes = service_provider.dismissed_disputes.saturating_add(1);
+            let service_key = service_provider.key();
+            refresh_reputation(service_provider, service_key, clock.unix_timestamp, ReputationReason::DisputeDismissed);
+        }
+
+        emit!(ResolveDisputeEvent {
+            dispute_id,
+            resolver: ctx.accounts.executor.key(),
+            resolution,
+            service_provider: ctx.accounts.service_provider.key(),
+            reputation_score: ctx.accounts.service_provider.reputation_score,
+        });
+
+        Ok(())
+    }
+
+    /// Create a data pool—share and earn in the anonymized data economy!
+    /// Governance pools pay from the reward reserve, so each needs a council proposal past its timelock.
+    pub fn create_data_pool(ctx: Context<CreateDataPool>, pool_info: DataPoolInfo) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(
+            ctx.accounts.governance_authority.key() == ctx.accounts.greylocker_state.governance_authority,
+            GreylockerError::NotAuthorized
+        );
+
+        let clock = Clock::get()?;
+        carry_out_proposal(
+            &mut ctx.accounts.proposal,
+            &ctx.accounts.governance_council,
+            ProposalAction::CreateDataPool { pool_info: pool_info.clone() },
+            ctx.accounts.governance_authority.key(),
+            clock.unix_timestamp,
+        )?;
+        let data_pool = &mut ctx.accounts.data_pool;
+        let pool_id = data_pool.key();
+        data_pool.init(pool_id, pool_info, Pubkey::default(), clock.unix_timestamp, *ctx.bumps.get("data_pool").unwrap())?;
+
+        emit!(CreateDataPoolEvent {
+            pool_id: data_pool.pool_id,
+            name: data_pool.name.clone(),
+            data_type: data_pool.data_type.clone(),
+            reward_rate: data_pool.reward_rate,
+            max_participants: data_pool.max_participants,
+            expires_at: data_pool.expires_at,
+            epsilon_budget: data_pool.epsilon_budget,
+            max_query_epsilon: data_pool.max_query_epsilon,
+            service_provider: Pubkey::default(),
+            initial_budget: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Open a provider data pool—staked services buy data straight from users!
+    /// Rewards come out of the provider's escrowed budget, never the reward reserve.
+    pub fn create_provider_data_pool(ctx: Context<CreateProviderDataPool>, pool_info: DataPoolInfo, initial_budget: u64) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(initial_budget > 0, GreylockerError::InvalidParameterValue);
+
+        let params = &ctx.accounts.greylocker_state.governance_parameters;
+        let stake_account = &ctx.accounts.service_stake_account;
+        require!(stake_account.stake_type == StakeType::Service, GreylockerError::InvalidStakeType);
+        require!(
+            backing_stake(stake_account) >= params.service_stake_minimum,
+            GreylockerError::InsufficientStakeAmount
+        );
+
+        let clock = Clock::get()?;
+        let service_provider = &ctx.accounts.service_provider;
+        require!(!service_provider.frozen, GreylockerError::ServiceFrozen);
+        require!(
+            compute_reputation(service_provider, clock.unix_timestamp) >= params.pool_creator_min_reputation,
+            GreylockerError::ReputationTooLow
+        );
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.service_token_account.to_account_info(),
+                    to: ctx.accounts.budget_vault.to_account_info(),
+                    authority: ctx.accounts.service_owner.to_account_info(),
+                },
+            ),
+            initial_budget,
+        )?;
+
+        let service_provider_key = service_provider.key();
+        let data_pool = &mut ctx.accounts.data_pool;
+        let pool_id = data_pool.key();
+        data_pool.init(pool_id, pool_info, service_provider_key, clock.unix_timestamp, *ctx.bumps.get("data_pool").unwrap())?;
+        data_pool.budget_funded = initial_budget;
+
+        emit!(CreateDataPoolEvent {
+            pool_id,
+            name: data_pool.name.clone(),
+            data_type: data_pool.data_type.clone(),
+            reward_rate: data_pool.reward_rate,
+            max_participants: data_pool.max_participants,
+            expires_at: data_pool.expires_at,
+            epsilon_budget: data_pool.epsilon_budget,
+            max_query_epsilon: data_pool.max_query_epsilon,
+            service_provider: service_provider_key,
+            initial_budget,
+        });
+
+        Ok(())
+    }
+
+    /// Join a data pool—opt in and start earning!
+    /// `consent_hash` commits to the terms the user agreed to; every (re)join records fresh consent.
+    pub fn join_data_pool(ctx: Context<JoinDataPool>, consent_hash: [u8; 32]) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let data_pool = &ctx.accounts.data_pool;
+        require!(data_pool.is_open(clock.unix_timestamp), GreylockerError::DataPoolInactive);
+        require!(
+            data_pool.max_participants == 0 || data_pool.total_participants < data_pool.max_participants,
+            GreylockerError::DataPoolFull
+        );
+        require!(consent_hash != [0u8; 32], GreylockerError::ConsentRequired);
+
+        let participant = &mut ctx.accounts.participant;
+        participant.user = ctx.accounts.user.key();
+        participant.pool_id = ctx.accounts.data_pool.key();
+        participant.joined_at = clock.unix_timestamp;
+        participant.total_rewards_received = 0;
+        participant.last_reward_at = 0;
+        participant.owed_rewards = 0;
+        participant.accepted_data_points = 0;
+        participant.consent_hash = consent_hash;
+        participant.consented_at = clock.unix_timestamp;
+        participant.queries_answered = 0;
+        participant.bump = *ctx.bumps.get("participant").unwrap();
+
+        ctx.accounts.data_pool.total_participants = ctx.accounts.data_pool.total_participants
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        emit!(JoinDataPoolEvent {
+            user: participant.user,
+            pool_id: participant.pool_id,
+            pool_name: ctx.accounts.data_pool.name.clone(),
+            consent_hash,
+            consented_at: participant.consented_at,
+        });
+
+        Ok(())
+    }
+
+    /// Update a data pool—retune its rate, limits and blurb (governance or the sponsoring provider)
+    /// Rate changes apply to contributions submitted from now on.
+    pub fn update_data_pool(
+        ctx: Context<UpdateDataPool>,
+        description: Option<String>,
+        reward_rate: Option<u64>,
+        max_participants: Option<u64>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.data_pool.is_controller(&ctx.accounts.greylocker_state, &ctx.accounts.data_pool_creator.key(), &ctx.accounts.authority.key()),
+            GreylockerError::NotAuthorized
+        );
+
+        let clock = Clock::get()?;
+        let data_pool = &mut ctx.accounts.data_pool;
+        require!(data_pool.active, GreylockerError::DataPoolInactive);
+        if let Some(description) = description {
+            require!(description.len() <= MAX_POOL_DESCRIPTION_LEN, GreylockerError::InvalidDataPoolConfig);
+            data_pool.description = description;
+        }
+        if let Some(reward_rate) = reward_rate {
+            data_pool.reward_rate = reward_rate;
+        }
+        if let Some(max_participants) = max_participants {
+            require!(
+                max_participants == 0 || max_participants >= data_pool.total_participants,
+                GreylockerError::InvalidDataPoolConfig
+            );
+            data_pool.max_participants = max_participants;
+        }
+        if let Some(expires_at) = expires_at {
+            require!(expires_at == 0 || expires_at > clock.unix_timestamp, GreylockerError::InvalidDataPoolConfig);
+            data_pool.expires_at = expires_at;
+        }
+
+        emit!(UpdateDataPoolEvent {
+            pool_id: data_pool.key(),
+            reward_rate: data_pool.reward_rate,
+            max_participants: data_pool.max_participants,
+            expires_at: data_pool.expires_at,
+        });
+
+        Ok(())
+    }
+
+    /// Pause or resume a data pool—hold joins and submissions; earned rewards still pay out (governance or the sponsoring provider)
+    pub fn set_data_pool_paused(ctx: Context<UpdateDataPool>, paused: bool) -> Result<()> {
+        require!(
+            ctx.accounts.data_pool.is_controller(&ctx.accounts.greylocker_state, &ctx.accounts.data_pool_creator.key(), &ctx.accounts.authority.key()),
+            GreylockerError::NotAuthorized
+        );
+
+        let data_pool = &mut ctx.accounts.data_pool;
+        require!(data_pool.active, GreylockerError::DataPoolInactive);
+        data_pool.paused = paused;
+
+        emit!(DataPoolStatusEvent {
+            pool_id: data_pool.key(),
+            active: data_pool.active,
+            paused,
+        });
+
+        Ok(())
+    }
+
+    /// Deactivate a data pool—no new joins or submissions, ever (governance or the sponsoring provider)
+    /// Contributions already in flight can still be finalized and paid.
+    pub fn deactivate_data_pool(ctx: Context<UpdateDataPool>) -> Result<()> {
+        require!(
+            ctx.accounts.data_pool.is_controller(&ctx.accounts.greylocker_state, &ctx.accounts.data_pool_creator.key(), &ctx.accounts.authority.key()),
+            GreylockerError::NotAuthorized
+        );
+
+        let data_pool = &mut ctx.accounts.data_pool;
+        require!(data_pool.active, GreylockerError::DataPoolInactive);
+        data_pool.active = false;
+        data_pool.paused = false;
+
+        emit!(DataPoolStatusEvent {
+            pool_id: data_pool.key(),
+            active: false,
+            paused: false,
+        });
+
+        Ok(())
+    }
+
+    /// Fund a data pool—top up its reward budget (governance or the sponsoring provider)!
+    /// Once funded, the pool pays contributors from its own vault instead of the reward reserve.
+    pub fn fund_data_pool(ctx: Context<FundDataPool>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(amount > 0, GreylockerError::InvalidParameterValue);
+        require!(ctx.accounts.data_pool.active, GreylockerError::DataPoolInactive);
+        // Funding flips the pool to budget mode, so a stranger's dust deposit could cap every reward
+        require!(
+            ctx.accounts.data_pool.is_controller(
+                &ctx.accounts.greylocker_state,
+                &ctx.accounts.data_pool_creator.key(),
+                &ctx.accounts.funder.key()
+            ),
+            GreylockerError::NotAuthorized
+        );
+
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder_token_account.to_account_info(),
+                    to: ctx.accounts.budget_vault.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let data_pool = &mut ctx.accounts.data_pool;
+        data_pool.budget_funded = data_pool.budget_funded
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        emit!(FundDataPoolEvent {
+            pool_id: data_pool.key(),
+            funder: ctx.accounts.funder.key(),
+            amount,
+            budget_remaining: data_pool.budget_remaining(),
+        });
+
+        Ok(())
+    }
+
+    /// Close a data pool—sweep its unspent budget home and reclaim rent (governance or the sponsoring provider)
+    /// Provider escrow returns to the provider; governance budgets return to the treasury.
+    pub fn close_data_pool(ctx: Context<CloseDataPool>) -> Result<()> {
+        let pool_creator = ctx.accounts.data_pool_creator.key();
+        let data_pool = &ctx.accounts.data_pool;
+        require!(
+            data_pool.is_controller(&ctx.accounts.greylocker_state, &pool_creator, &ctx.accounts.authority.key()),
+            GreylockerError::NotAuthorized
+        );
+        if data_pool.service_provider == Pubkey::default() {
+            require!(ctx.accounts.refund_account.key() == ctx.accounts.greylocker_state.treasury, GreylockerError::NotAuthorized);
+        } else {
+            require!(ctx.accounts.refund_account.owner == pool_creator, GreylockerError::NotAuthorized);
+        }
+
+        let clock = Clock::get()?;
+        require!(
+            !data_pool.active || (data_pool.expires_at != 0 && clock.unix_timestamp >= data_pool.expires_at),
+            GreylockerError::DataPoolStillActive
+        );
+        require!(
+            data_pool.open_contributions == 0 && data_pool.open_disputes == 0,
+            GreylockerError::DataPoolStillActive
+        );
+        // Deferred rewards and departed participants' settlements are paid from this pool; it can't sweep them
+        require!(data_pool.owed_rewards == 0, GreylockerError::DataPoolRewardsOwed);
+        require!(
+            data_pool.budget_funded == 0 || ctx.accounts.budget_vault.is_some(),
+            GreylockerError::MissingBudgetVault
+        );
+
+        let mut refunded = 0;
+        if let Some(budget_vault) = ctx.accounts.budget_vault.as_ref() {
+            require!(
+                budget_vault.key() == Pubkey::find_program_address(&[b"data-pool-vault", data_pool.key().as_ref()], &crate::ID).0,
+                GreylockerError::NotAuthorized
+            );
+            let pool_seeds = &[b"data-pool".as_ref(), pool_creator.as_ref(), data_pool.name.as_bytes(), &[data_pool.bump]];
+            let signer = &[&pool_seeds[..]];
+            refunded = budget_vault.amount;
+            if refunded > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: budget_vault.to_account_info(),
+                            to: ctx.accounts.refund_account.to_account_info(),
+                            authority: data_pool.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    refunded,
+                )?;
+            }
+            token::close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: budget_vault.to_account_info(),
+                    destination: ctx.accounts.data_pool_creator.to_account_info(),
+                    authority: data_pool.to_account_info(),
+                },
+                signer,
+            ))?;
+        }
+
+        emit!(CloseDataPoolEvent {
+            pool_id: data_pool.key(),
+            refund_account: ctx.accounts.refund_account.key(),
+            refunded,
+        });
+
+        Ok(())
+    }
+
+    /// Submit a data contribution—commit this epoch's data for validators to check!
+    pub fn submit_contribution(ctx: Context<SubmitContribution>, epoch: u64, data_hash: [u8; 32], data_points: u64) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(
+            data_points > 0 && data_points <= MAX_DATA_POINTS_PER_CONTRIBUTION && data_hash != [0u8; 32],
+            GreylockerError::InvalidContribution
+        );
+
+        // Epochs count from the pool's creation; only the current one is open
+        let clock = Clock::get()?;
+        require!(ctx.accounts.data_pool.is_open(clock.unix_timestamp), GreylockerError::DataPoolInactive);
+        let current_epoch = (clock.unix_timestamp - ctx.accounts.data_pool.created_at) / CONTRIBUTION_EPOCH_DURATION;
+        require!(epoch == current_epoch as u64, GreylockerError::InvalidContribution);
+
+        // Funded pools turn away work they can't currently cover; the escrow itself waits for acceptance
+        // so a pile of unvetted submissions can't tie up the budget
+        let reward = ctx.accounts.data_pool.reward_rate
+            .checked_mul(data_points)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        require!(
+            ctx.accounts.data_pool.budget_funded == 0 || reward <= ctx.accounts.data_pool.budget_remaining(),
+            GreylockerError::DataPoolBudgetExhausted
+        );
+
+        let contribution = &mut ctx.accounts.contribution;
+        contribution.pool = ctx.accounts.data_pool.key();
+        contribution.participant = ctx.accounts.participant.key();
+        contribution.contributor = ctx.accounts.user.key();
+        contribution.epoch = epoch;
+        contribution.data_hash = data_hash;
+        contribution.data_points = data_points;
+        contribution.submitted_at = clock.unix_timestamp;
+        contribution.approvals = 0;
+        contribution.rejections = 0;
+        contribution.status = ContributionStatus::Pending;
+        contribution.finalized_at = 0;
+        contribution.reward = reward;
+        contribution.escrowed = false;
+        contribution.bump = *ctx.bumps.get("contribution").unwrap();
+
+        let data_pool = &mut ctx.accounts.data_pool;
+        data_pool.total_contributions = data_pool.total_contributions
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        data_pool.open_contributions = data_pool.open_contributions
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        emit!(SubmitContributionEvent {
+            contribution: contribution.key(),
+            pool_id: contribution.pool,
+            contributor: contribution.contributor,
+            epoch,
+            data_hash,
+            data_points,
+        });
+
+        Ok(())
+    }
+
+    /// Attest to a contribution—DataValidator stakers vouch for the data, or reject it!
+    /// Eligibility counts the validator's own stake only, and that stake stays locked until the attestation is released.
+    pub fn attest_contribution(ctx: Context<AttestContribution>, approve: bool) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let validator = ctx.accounts.validator.key();
+        let stake_account = &mut ctx.accounts.validator_stake_account;
+        require!(stake_account.stake_type == StakeType::DataValidator, GreylockerError::InvalidStakeType);
+        require!(
+            stake_account.amount >= ctx.accounts.greylocker_state.governance_parameters.data_validator_stake_minimum,
+            GreylockerError::InsufficientStakeAmount
+        );
+
+        let contribution = &mut ctx.accounts.contribution;
+        require!(contribution.status == ContributionStatus::Pending, GreylockerError::InvalidContributionPhase);
+        require!(
+            clock.unix_timestamp < contribution.submitted_at + CONTRIBUTION_ATTESTATION_PERIOD,
+            GreylockerError::InvalidContributionPhase
+        );
+        require!(validator != contribution.contributor, GreylockerError::ConflictedValidator);
+
+        // Independence: the pool's creator can't grade its own pool, and a position opened
+        // after (or just before) the submission can't be a sock puppet spun up to vouch for it
+        let data_pool = &ctx.accounts.data_pool;
+        let (creator_pool, _) = Pubkey::find_program_address(
+            &[b"data-pool", validator.as_ref(), data_pool.name.as_ref()],
+            &crate::ID,
+        );
+        require!(creator_pool != data_pool.key(), GreylockerError::ConflictedValidator);
+        if data_pool.service_provider != Pubkey::default() {
+            let (validator_provider, _) = Pubkey::find_program_address(&[b"service-provider", validator.as_ref()], &crate::ID);
+            require!(validator_provider != data_pool.service_provider, GreylockerError::ConflictedValidator);
+        }
+        require!(
+            stake_account.staked_at.saturating_add(MIN_ATTESTER_STAKE_AGE) <= contribution.submitted_at,
+            GreylockerError::AttesterStakeTooNew
+        );
+
+        stake_account.open_attestations = stake_account.open_attestations
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        if approve {
+            contribution.approvals = contribution.approvals.saturating_add(1);
+        } else {
+            contribution.rejections = contribution.rejections.saturating_add(1);
+        }
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.contribution = contribution.key();
+        attestation.validator = validator;
+        attestation.stake_account = stake_account.key();
+        attestation.approve = approve;
+        attestation.slashed = false;
+        attestation.bump = *ctx.bumps.get("attestation").unwrap();
+
+        emit!(AttestContributionEvent {
+            contribution: contribution.key(),
+            validator,
+            approve,
+            approvals: contribution.approvals,
+            rejections: contribution.rejections,
+        });
+
+        Ok(())
+    }
+
+    /// Release an attestation—once the data can no longer be struck down, the validator's stake is free again!
+    /// Permissionless; approvals of invalidated data stay locked until they've been slashed.
+    pub fn release_attestation(ctx: Context<ReleaseAttestation>) -> Result<()> {
+        let clock = Clock::get()?;
+        let contribution = &ctx.accounts.contribution;
+        let attestation = &ctx.accounts.attestation;
+        let releasable = match contribution.status {
+            ContributionStatus::Pending => false,
+            ContributionStatus::Accepted => clock.unix_timestamp >= contribution.finalized_at + CONTRIBUTION_CHALLENGE_PERIOD,
+            ContributionStatus::Invalidated => !attestation.approve || attestation.slashed,
+            ContributionStatus::Rejected | ContributionStatus::Paid | ContributionStatus::Forfeited => true,
+        };
+        require!(releasable, GreylockerError::InvalidContributionPhase);
+
+        let stake_account = &mut ctx.accounts.stake_account;
+        stake_account.open_attestations = stake_account.open_attestations.saturating_sub(1);
+
+        emit!(AttestationReleasedEvent {
+            contribution: contribution.key(),
+            validator: attestation.validator,
+            stake_account: stake_account.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Finalize a contribution—tally attestations once the window closes!
+    /// Anyone may call; accepted data earns reward_rate per data point.
+    pub fn finalize_contribution(ctx: Context<FinalizeContribution>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let contribution = &mut ctx.accounts.contribution;
+        require!(contribution.status == ContributionStatus::Pending, GreylockerError::InvalidContributionPhase);
+        require!(
+            clock.unix_timestamp >= contribution.submitted_at + CONTRIBUTION_ATTESTATION_PERIOD,
+            GreylockerError::InvalidContributionPhase
+        );
+
+        // Without a quorum nobody has vouched for the data, so it goes unpaid
+        let attestations = contribution.approvals.saturating_add(contribution.rejections);
+        let accepted = attestations >= CONTRIBUTION_QUORUM && contribution.approvals > contribution.rejections;
+        contribution.finalized_at = clock.unix_timestamp;
+        if accepted {
+            contribution.status = ContributionStatus::Accepted;
+            contribution.escrowed = ctx.accounts.data_pool.commit_budget(contribution.reward)?;
+        } else {
+            contribution.status = ContributionStatus::Rejected;
+            let data_pool = &mut ctx.accounts.data_pool;
+            if contribution.escrowed {
+                data_pool.release_budget(contribution.reward);
+            }
+            data_pool.open_contributions = data_pool.open_contributions.saturating_sub(1);
+            contribution.reward = 0;
+        }
+
+        emit!(FinalizeContributionEvent {
+            contribution: contribution.key(),
+            pool_id: contribution.pool,
+            accepted,
+            approvals: contribution.approvals,
+            rejections: contribution.rejections,
+            reward: contribution.reward,
+        });
+
+        Ok(())
+    }
+
+    /// Invalidate a contribution—strike down bad data before it's paid (governance only)
+    /// A deliberate fast path, like pause: it only withholds an unpaid reward, and it has to land inside a
+    /// challenge period that a timelocked proposal could outlast.
+    pub fn invalidate_contribution(ctx: Context<InvalidateContribution>) -> Result<()> {
+        require!(
+            ctx.accounts.governance_authority.key() == ctx.accounts.greylocker_state.governance_authority,
+            GreylockerError::NotAuthorized
+        );
+
+        let clock = Clock::get()?;
+        let contribution = &mut ctx.accounts.contribution;
+        require!(contribution.status == ContributionStatus::Accepted, GreylockerError::InvalidContributionPhase);
+        require!(
+            clock.unix_timestamp < contribution.finalized_at + CONTRIBUTION_CHALLENGE_PERIOD,
+            GreylockerError::InvalidContributionPhase
+        );
+        contribution.status = ContributionStatus::Invalidated;
+        let data_pool = &mut ctx.accounts.data_pool;
+        if contribution.escrowed {
+            data_pool.release_budget(contribution.reward);
+        }
+        data_pool.open_contributions = data_pool.open_contributions.saturating_sub(1);
+        contribution.reward = 0;
+
+        emit!(InvalidateContributionEvent {
+            contribution: contribution.key(),
+            pool_id: contribution.pool,
+            contributor: contribution.contributor,
+            approvals: contribution.approvals,
+        });
+
+        Ok(())
+    }
+
+    /// Slash an attester—validators who vouched for invalidated data pay for it!
+    /// Permissionless; the slashed GREY is burned.
+    pub fn slash_attester(ctx: Context<SlashAttester>) -> Result<()> {
+        require!(
+            ctx.accounts.contribution.status == ContributionStatus::Invalidated,
+            GreylockerError::InvalidContributionPhase
+        );
+        let attestation = &mut ctx.accounts.attestation;
+        require!(attestation.approve && !attestation.slashed, GreylockerError::AttesterNotSlashable);
+        attestation.slashed = true;
+
+        // Unbonding GREY is still at risk; delegators only answer for service disputes
+        let slash_percentage = ctx.accounts.greylocker_state.governance_parameters.attestation_slash as u64;
+        let stake_account = &mut ctx.accounts.stake_account;
+        let slashable = stake_account.amount
+            .checked_add(stake_account.unbonding_amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let slash_amount = slashable
+            .checked_mul(slash_percentage)
+            .and_then(|val| val.checked_div(100))
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let from_active = slash_amount.min(stake_account.amount);
+        let from_unbonding = slash_amount - from_active;
+        stake_account.amount = stake_account.amount
+            .checked_sub(from_active)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        stake_account.unbonding_amount = stake_account.unbonding_amount
+            .checked_sub(from_unbonding)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let stake_key = stake_account.key();
+        let owner = stake_account.owner;
+        let position_id = stake_account.position_id.to_le_bytes();
+        let bump = stake_account.bump;
+
+        let state = &mut ctx.accounts.greylocker_state;
+        state.total_staked = state.total_staked
+            .checked_sub(from_active)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        state.total_supply = state.total_supply
+            .checked_sub(slash_amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        if slash_amount > 0 {
+            let stake_seeds = &[b"stake-account".as_ref(), owner.as_ref(), position_id.as_ref(), &[bump]];
+            let signer = &[&stake_seeds[..]];
+            token::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.grey_mint.to_account_info(),
+                        from: ctx.accounts.stake_vault.to_account_info(),
+                        authority: ctx.accounts.stake_account.to_account_info(),
+                    },
+                    signer,
+                ),
+                slash_amount,
+            )?;
+        }
+
+        emit!(AttesterSlashEvent {
+            contribution: ctx.accounts.contribution.key(),
+            validator: attestation.validator,
+            stake_account: stake_key,
+            slash_amount,
+            slash_percentage: slash_percentage as u8,
+        });
+
+        Ok(())
+    }
+
+    /// Pay data reward—reward users for their accepted contributions!
+    /// Anyone may call once the challenge period has passed; funded pools pay from their own budget.
+    pub fn pay_data_reward(ctx: Context<PayDataReward>) -> Result<()> {
+        // A paused pool stops taking work but can't hold back rewards already earned
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let contribution = &mut ctx.accounts.contribution;
+        require!(contribution.status == ContributionStatus::Accepted, GreylockerError::InvalidContributionPhase);
+        require!(
+            clock.unix_timestamp >= contribution.finalized_at + CONTRIBUTION_CHALLENGE_PERIOD,
+            GreylockerError::InvalidContributionPhase
+        );
+        contribution.status = ContributionStatus::Paid;
+        let amount = contribution.reward;
+        let escrowed = contribution.escrowed;
+        let data_points = contribution.data_points;
+        let contribution_key = contribution.key();
+
+        let participant = &mut ctx.accounts.participant;
+        require!(
+            participant.user == ctx.accounts.user.key() && participant.pool_id == ctx.accounts.data_pool.key(),
+            GreylockerError::UserNotInDataPool
+        );
+        // Work submitted under consent the user later withdrew is never paid
+        require!(
+            ctx.accounts.contribution.submitted_at >= participant.joined_at,
+            GreylockerError::InvalidContributionPhase
+        );
+        participant.accepted_data_points = participant.accepted_data_points
+            .checked_add(data_points)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let data_pool = &mut ctx.accounts.data_pool;
+        data_pool.accepted_data_points = data_pool.accepted_data_points
+            .checked_add(data_points)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        data_pool.open_contributions = data_pool.open_contributions.saturating_sub(1);
+        if escrowed {
+            data_pool.release_budget(amount);
+        }
+
+        // Anything still owed from a dry epoch is paid ahead of the new award
+        let owed = participant.owed_rewards
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        require!(owed > 0, GreylockerError::NoRewardsToClaim);
+        let funded = data_pool.budget_funded > 0;
+        let draw = if funded {
+            let budget_vault = ctx.accounts.budget_vault.as_ref().ok_or(GreylockerError::MissingBudgetVault)?;
+            require!(
+                budget_vault.key() == Pubkey::find_program_address(&[b"data-pool-vault", data_pool.key().as_ref()], &crate::ID).0,
+                GreylockerError::NotAuthorized
+            );
+            data_pool.draw_budget(budget_vault.amount, owed)?
+        } else {
+            draw_from_reserve(
+                &mut ctx.accounts.reward_reserve,
+                ctx.accounts.reserve_vault.amount,
+                owed,
+                clock.unix_timestamp,
+            )?
+        };
+
+        // Provider pools are data purchases, so they pay the access-fee rate
+        let params = &ctx.accounts.greylocker_state.governance_parameters;
+        let fee_bps = if ctx.accounts.data_pool.service_provider == Pubkey::default() {
+            params.data_reward_fee_bps
+        } else {
+            params.protocol_fee_bps
+        };
+        let split = split_protocol_fee(draw.paid, fee_bps, params.fee_burn_share_bps)?;
+
+        ctx.accounts.data_pool.owed_rewards = ctx.accounts.data_pool.owed_rewards
+            .saturating_sub(participant.owed_rewards)
+            .checked_add(draw.deferred)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        participant.owed_rewards = draw.deferred;
+        participant.total_rewards_received = participant.total_rewards_received
+            .checked_add(split.net)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        participant.last_reward_at = clock.unix_timestamp;
+
+        ctx.accounts.data_pool.total_rewards_paid = ctx.accounts.data_pool.total_rewards_paid
+            .checked_add(draw.paid)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        // Funded pools sign for their own vault; the rest are paid by the state PDA from the reserve
+        let state_bump = [ctx.accounts.greylocker_state.bump];
+        let pool_bump = [ctx.accounts.data_pool.bump];
+        let pool_creator = ctx.accounts.data_pool_creator.key();
+        let pool_name = ctx.accounts.data_pool.name.clone();
+        let state_seeds: &[&[u8]] = &[b"greylocker-state", &state_bump];
+        let pool_seeds: &[&[u8]] = &[b"data-pool", pool_creator.as_ref(), pool_name.as_bytes(), &pool_bump];
+        let (from, authority, seeds) = match ctx.accounts.budget_vault.as_ref().filter(|_| funded) {
+            Some(budget_vault) => (budget_vault.to_account_info(), ctx.accounts.data_pool.to_account_info(), pool_seeds),
+            None => (ctx.accounts.reserve_vault.to_account_info(), ctx.accounts.greylocker_state.to_account_info(), state_seeds),
+        };
+        pay_with_protocol_fee(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.grey_mint.to_account_info(),
+            &from,
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            &authority,
+            &[seeds],
+            &split,
+        )?;
+        if split.burned > 0 {
+            ctx.accounts.greylocker_state.total_supply = ctx.accounts.greylocker_state.total_supply
+                .checked_sub(split.burned)
+                .ok_or(GreylockerError::ArithmeticError)?;
+        }
+
+        emit!(PayDataRewardEvent {
+            user: participant.user,
+            pool_id: ctx.accounts.data_pool.key(),
+            contribution: contribution_key,
+            amount: split.net,
+            burned_amount: split.burned,
+            treasury_amount: split.to_treasury,
+            deferred_amount: draw.deferred,
+            forfeited_amount: draw.forfeited,
+        });
+
+        Ok(())
+    }
+
+    /// Record a query—charge a participant's privacy budget and pay them for the answer!
+    /// Only the pool operator may record; queries stop once the participant's epsilon runs out.
+    pub fn record_query(ctx: Context<RecordQuery>, query_hash: [u8; 32], epsilon: u64, fee: u64) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+        require!(
+            ctx.accounts.data_pool.is_controller(&ctx.accounts.greylocker_state, &ctx.accounts.data_pool_creator.key(), &ctx.accounts.authority.key()),
+            GreylockerError::NotAuthorized
+        );
+        let clock = Clock::get()?;
+        require!(ctx.accounts.data_pool.is_open(clock.unix_timestamp), GreylockerError::DataPoolInactive);
+
+        // Only a live membership carries consent; a participant who left has nothing to query
+        let data_pool = &ctx.accounts.data_pool;
+        let participant = &mut ctx.accounts.participant;
+        require!(
+            participant.user == ctx.accounts.user.key() && participant.pool_id == data_pool.key(),
+            GreylockerError::UserNotInDataPool
+        );
+        require!(participant.consent_hash != [0u8; 32], GreylockerError::ConsentRequired);
+        require!(epsilon > 0 && epsilon <= data_pool.max_query_epsilon, GreylockerError::InvalidQueryEpsilon);
+
+        // The ledger outlives memberships, so leaving and rejoining doesn't reset the budget
+        let ledger = &mut ctx.accounts.privacy_ledger;
+        if ledger.user == Pubkey::default() {
+            ledger.user = participant.user;
+            ledger.pool_id = data_pool.key();
+            ledger.bump = *ctx.bumps.get("privacy_ledger").unwrap();
+        }
+        let epsilon_spent = ledger.epsilon_spent
+            .checked_add(epsilon)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        require!(epsilon_spent <= data_pool.epsilon_budget, GreylockerError::PrivacyBudgetExhausted);
+        ledger.epsilon_spent = epsilon_spent;
+        participant.queries_answered = participant.queries_answered
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        // Queries are data sales, so they carry the access-fee rate
+        let params = &ctx.accounts.greylocker_state.governance_parameters;
+        let split = split_protocol_fee(fee, params.protocol_fee_bps, params.fee_burn_share_bps)?;
+        pay_with_protocol_fee(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.grey_mint.to_account_info(),
+            &ctx.accounts.operator_token_account.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &[],
+            &split,
+        )?;
+        participant.total_rewards_received = participant.total_rewards_received
+            .checked_add(split.net)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        ctx.accounts.greylocker_state.total_supply = ctx.accounts.greylocker_state.total_supply
+            .checked_sub(split.burned)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let data_pool = &mut ctx.accounts.data_pool;
+        data_pool.total_queries = data_pool.total_queries
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        emit!(PrivacyBudgetConsumedEvent {
+            pool_id: data_pool.key(),
+            user: participant.user,
+            operator: ctx.accounts.authority.key(),
+            query_hash,
+            epsilon,
+            epsilon_spent,
+            epsilon_budget: data_pool.epsilon_budget,
+            fee_paid: split.net,
+        });
+
+        Ok(())
+    }
+
+    /// Leave a data pool—withdraw consent and reclaim rent!
+    /// Works even while the protocol is paused; anything owed moves to a settlement record claimable later.
+    pub fn leave_data_pool(ctx: Context<LeaveDataPool>) -> Result<()> {
+        let clock = Clock::get()?;
+        let participant = &ctx.accounts.participant;
+        let owed = participant.owed_rewards;
+        let consent_hash = participant.consent_hash;
+        let consented_at = participant.consented_at;
+
+        let data_pool = &mut ctx.accounts.data_pool;
+        data_pool.total_participants = data_pool.total_participants.saturating_sub(1);
+
+        let settlement = &mut ctx.accounts.pool_settlement;
+        if settlement.user == Pubkey::default() {
+            settlement.user = ctx.accounts.user.key();
+            settlement.pool_id = data_pool.key();
+            settlement.bump = *ctx.bumps.get("pool_settlement").unwrap();
+        }
+        settlement.owed_rewards = settlement.owed_rewards
+            .checked_add(owed)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        emit!(ConsentRevokedEvent {
+            user: ctx.accounts.user.key(),
+            pool_id: ctx.accounts.data_pool.key(),
+            service_provider: ctx.accounts.data_pool.service_provider,
+            consent_hash,
+            consented_at,
+            revoked_at: clock.unix_timestamp,
+            rewards_settled: owed,
+        });
+
+        Ok(())
+    }
+
+    /// Settle a departed contributor's accepted work—consent withdrawn after acceptance still gets paid!
+    /// Anyone may call once the challenge period has passed; the reward lands in the contributor's settlement record.
+    pub fn settle_contribution(ctx: Context<SettleContribution>) -> Result<()> {
+        let clock = Clock::get()?;
+        let contribution = &mut ctx.accounts.contribution;
+        require!(contribution.status == ContributionStatus::Accepted, GreylockerError::InvalidContributionPhase);
+        require!(
+            clock.unix_timestamp >= contribution.finalized_at + CONTRIBUTION_CHALLENGE_PERIOD,
+            GreylockerError::InvalidContributionPhase
+        );
+
+        // The contributor either left, or left and rejoined under fresh consent
+        let participant_info = ctx.accounts.participant.to_account_info();
+        let withdrawn = participant_info.data_is_empty()
+            || Account::<PoolParticipant>::try_from(&participant_info)?.joined_at > contribution.submitted_at;
+        require!(withdrawn, GreylockerError::InvalidContributionPhase);
+
+        contribution.status = ContributionStatus::Paid;
+        let reward = contribution.reward;
+        let data_pool = &mut ctx.accounts.data_pool;
+        if contribution.escrowed {
+            data_pool.release_budget(reward);
+        }
+        data_pool.open_contributions = data_pool.open_contributions.saturating_sub(1);
+        data_pool.accepted_data_points = data_pool.accepted_data_points
+            .checked_add(contribution.data_points)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let settlement = &mut ctx.accounts.pool_settlement;
+        if settlement.user == Pubkey::default() {
+            settlement.user = contribution.contributor;
+            settlement.pool_id = data_pool.key();
+            settlement.bump = *ctx.bumps.get("pool_settlement").unwrap();
+        }
+        settlement.owed_rewards = settlement.owed_rewards
+            .checked_add(reward)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        data_pool.owed_rewards = data_pool.owed_rewards
+            .checked_add(reward)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        emit!(SettleContributionEvent {
+            contribution: contribution.key(),
+            pool_id: contribution.pool,
+            contributor: contribution.contributor,
+            reward,
+            owed_rewards: settlement.owed_rewards,
+        });
+
+        Ok(())
+    }
+
+    /// Claim a pool settlement—collect what a pool still owes after you left!
+    /// Pays what the pool's budget or the reserve covers; the record closes once nothing is owed.
+    pub fn claim_pool_settlement(ctx: Context<ClaimPoolSettlement>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::ProgramPaused);
+
+        let clock = Clock::get()?;
+        let owed = ctx.accounts.pool_settlement.owed_rewards;
+        // Leaving with nothing owed still opens a record; claiming it just hands back the rent
+        if owed == 0 {
+            return ctx.accounts.pool_settlement.close(ctx.accounts.user.to_account_info());
+        }
+
+        let data_pool = &mut ctx.accounts.data_pool;
+        let funded = data_pool.budget_funded > 0;
+        let draw = if funded {
+            let budget_vault = ctx.accounts.budget_vault.as_ref().ok_or(GreylockerError::MissingBudgetVault)?;
+            require!(
+                budget_vault.key() == Pubkey::find_program_address(&[b"data-pool-vault", data_pool.key().as_ref()], &crate::ID).0,
+                GreylockerError::NotAuthorized
+            );
+            data_pool.draw_budget(budget_vault.amount, owed)?
+        } else {
+            draw_from_reserve(
+                &mut ctx.accounts.reward_reserve,
+                ctx.accounts.reserve_vault.amount,
+                owed,
+                clock.unix_timestamp,
+            )?
+        };
+        data_pool.total_rewards_paid = data_pool.total_rewards_paid
+            .checked_add(draw.paid)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        data_pool.owed_rewards = data_pool.owed_rewards
+            .saturating_sub(owed)
+            .checked_add(draw.deferred)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let params = &ctx.accounts.greylocker_state.governance_parameters;
+        let fee_bps = if data_pool.service_provider == Pubkey::default() {
+            params.data_reward_fee_bps
+        } else {
+            params.protocol_fee_bps
+        };
+        let split = split_protocol_fee(draw.paid, fee_bps, params.fee_burn_share_bps)?;
+
+        let state_bump = [ctx.accounts.greylocker_state.bump];
+        let pool_bump = [data_pool.bump];
+        let pool_creator = ctx.accounts.data_pool_creator.key();
+        let pool_name = data_pool.name.clone();
+        let state_seeds: &[&[u8]] = &[b"greylocker-state", &state_bump];
+        let pool_seeds: &[&[u8]] = &[b"data-pool", pool_creator.as_ref(), pool_name.as_bytes(), &pool_bump];
+        let (from, authority, seeds) = match ctx.accounts.budget_vault.as_ref().filter(|_| funded) {
+            Some(budget_vault) => (budget_vault.to_account_info(), ctx.accounts.data_pool.to_account_info(), pool_seeds),
+            None => (ctx.accounts.reserve_vault.to_account_info(), ctx.accounts.greylocker_state.to_account_info(), state_seeds),
+        };
+        pay_with_protocol_fee(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.grey_mint.to_account_info(),
+            &from,
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            &authority,
+            &[seeds],
+            &split,
+        )?;
+        ctx.accounts.greylocker_state.total_supply = ctx.accounts.greylocker_state.total_supply
+            .checked_sub(split.burned)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        // Pro-rata write-offs are gone for good; only the deferred part stays owed
+        let remaining = draw.deferred;
+        ctx.accounts.pool_settlement.owed_rewards = remaining;
+        if remaining == 0 {
+            ctx.accounts.pool_settlement.close(ctx.accounts.user.to_account_info())?;
+        }
+
+        emit!(ClaimPoolSettlementEvent {
+            user: ctx.accounts.user.key(),
+            pool_id: ctx.accounts.data_pool.key(),
+            amount: split.net,
+            burned_amount: split.burned,
+            treasury_amount: split.to_treasury,
+            deferred_amount: draw.deferred,
+            forfeited_amount: draw.forfeited,
+        });
+
+        Ok(())
+    }
+
+    /// Forfeit a contribution—clear unvetted work whose contributor withdrew consent!
+    /// Anyone may call; accepted work is settled with settle_contribution instead.
+    pub fn forfeit_contribution(ctx: Context<ForfeitContribution>) -> Result<()> {
+        let contribution = &mut ctx.accounts.contribution;
+        require!(contribution.status == ContributionStatus::Pending, GreylockerError::InvalidContributionPhase);
+
+        // The contributor either left, or left and rejoined under fresh consent
+        let participant_info = ctx.accounts.participant.to_account_info();
+        let withdrawn = participant_info.data_is_empty()
+            || Account::<PoolParticipant>::try_from(&participant_info)?.joined_at > contribution.submitted_at;
+        require!(withdrawn, GreylockerError::InvalidContributionPhase);
+
+        let released = contribution.reward;
+        contribution.status = ContributionStatus::Forfeited;
+        let data_pool = &mut ctx.accounts.data_pool;
+        if contribution.escrowed {
+            data_pool.release_budget(released);
+        }
+        data_pool.open_contributions = data_pool.open_contributions.saturating_sub(1);
+        contribution.reward = 0;
+
+        emit!(ForfeitContributionEvent {
+            contribution: contribution.key(),
+            pool_id: contribution.pool,
+            contributor: contribution.contributor,
+            reward_released: if contribution.escrowed { released } else { 0 },
+        });
+
+        Ok(())
+    }
+
+    /// Pause the program—freeze the grid!
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        require!(!ctx.accounts.greylocker_state.paused, GreylockerError::AlreadyPaused);
+        require!(
+            ctx.accounts.pauser.key() == ctx.accounts.greylocker_state.authority,
+            GreylockerError::NotAuthorized
+        );
+        ctx.accounts.greylocker_state.paused = true;
+
+        emit!(PauseEvent {
+            authority: ctx.accounts.pauser.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Seat the governance council—no more lone hands on the levers!
+    /// One-shot bootstrap by the current governance authority; every later change goes through proposals.
+    pub fn initialize_governance(ctx: Context<InitializeGovernance>, signers: Vec<Pubkey>, threshold: u8, timelock: i64) -> Result<()> {
+        require!(
+            ctx.accounts.governance_authority.key() == ctx.accounts.greylocker_state.governance_authority,
+            GreylockerError::NotAuthorized
+        );
+        validate_council(&signers, threshold, timelock)?;
+
+        let council = &mut ctx.accounts.governance_council;
+        council.signers = signers.clone();
+        council.threshold = threshold;
+        council.timelock = timelock;
+        council.next_proposal_id = 0;
+        council.config_version = 0;
+        council.bump = *ctx.bumps.get("governance_council").unwrap();
+
+        emit!(InitializeGovernanceEvent {
+            council: council.key(),
+            signers,
+            threshold,
+            timelock,
+        });
+
+        Ok(())
+    }
+
+    /// Table a proposal—put your idea before the council!
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+        let council = &mut ctx.accounts.governance_council;
+        let proposer = ctx.accounts.proposer.key();
+        require!(council.is_signer(&proposer), GreylockerError::NotCouncilSigner);
+        validate_proposal_action(&action)?;
+
+        let clock = Clock::get()?;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.proposal_id = council.next_proposal_id;
+        proposal.proposer = proposer;
+        proposal.action = action.clone();
+        proposal.approvals = vec![proposer]; // Proposing counts as approving
+        proposal.council_version = council.config_version;
+        proposal.status = ProposalStatus::Pending;
+        proposal.created_at = clock.unix_timestamp;
+        proposal.eta = 0;
+        proposal.kind = ProposalKind::Council;
+        proposal.bump = *ctx.bumps.get("proposal").unwrap();
+        if proposal.approvals.len() >= council.threshold as usize {
+            proposal.queue(council.timelock, clock.unix_timestamp)?;
+        }
+
+        council.next_proposal_id = council.next_proposal_id
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        emit!(CreateProposalEvent {
+            proposal: proposal.key(),
+            proposal_id: proposal.proposal_id,
+            proposer,
+            action,
+            eta: proposal.eta,
+        });
+
+        Ok(())
+    }
+
+    /// Approve a proposal—add your signature to the pile!
+    /// Reaching the threshold starts the timelock.
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        let council = &ctx.accounts.governance_council;
+        let approver = ctx.accounts.approver.key();
+        require!(council.is_signer(&approver), GreylockerError::NotCouncilSigner);
+
+        let clock = Clock::get()?;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.kind == ProposalKind::Council, GreylockerError::WrongProposalKind);
+        require!(proposal.status == ProposalStatus::Pending, GreylockerError::ProposalNotPending);
+        require!(proposal.council_version == council.config_version, GreylockerError::StaleProposal);
+        require!(!proposal.approvals.contains(&approver), GreylockerError::AlreadyApproved);
+
+        proposal.approvals.push(approver);
+        if proposal.approvals.len() >= council.threshold as usize {
+            proposal.queue(council.timelock, clock.unix_timestamp)?;
+        }
+
+        emit!(ApproveProposalEvent {
+            proposal: proposal.key(),
+            approver,
+            approvals: proposal.approvals.len() as u8,
+            eta: proposal.eta,
+        });
+
+        Ok(())
+    }
+
+    /// Execute a proposal—the timelock has spoken!
+    /// Permissionless once queued and past its eta, so a missing signer can't stall the outcome.
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let clock = Clock::get()?;
+        let council_key = ctx.accounts.governance_council.key();
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.status == ProposalStatus::Queued, GreylockerError::ProposalNotQueued);
+        require!(
+            proposal.kind == ProposalKind::TokenVote
+                || proposal.council_version == ctx.accounts.governance_council.config_version,
+            GreylockerError::StaleProposal
+        );
+        require!(clock.unix_timestamp >= proposal.eta, GreylockerError::TimelockNotElapsed);
+
+        let action = proposal.action.clone();
+        match action.clone() {
+            ProposalAction::UpdateParameter { parameter, value } => {
+                apply_governance_parameter(&mut ctx.accounts.greylocker_state, &parameter, value, clock.unix_timestamp)?;
+                emit!(UpdateGovernanceParameterEvent {
+                    parameter,
+                    new_value: value,
+                    authority: council_key,
+                });
+            },
+            ProposalAction::Mint { recipient, amount } => {
+                let recipient_account = ctx.accounts.recipient.as_ref().ok_or(GreylockerError::MissingProposalAccount)?;
+                require!(
+                    recipient_account.key() == recipient && recipient_account.mint == ctx.accounts.grey_mint.key(),
+                    GreylockerError::MissingProposalAccount
+                );
+
+                let new_total_supply = ctx.accounts.greylocker_state.total_supply
+                    .checked_add(amount)
+                    .ok_or(GreylockerError::ArithmeticError)?;
+                require!(new_total_supply <= ctx.accounts.greylocker_state.max_supply, GreylockerError::MaxSupplyReached);
+
+                let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
+                let signer = &[&state_seeds[..]];
+                token::mint_to(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        MintTo {
+                            mint: ctx.accounts.grey_mint.to_account_info(),
+                            to: recipient_account.to_account_info(),
+                            authority: ctx.accounts.greylocker_state.to_account_info(),
+                        },
+                        signer,
+                    ),
+                    amount,
+                )?;
+                ctx.accounts.greylocker_state.total_supply = new_total_supply;
+
+                emit!(GovernanceMintEvent {
+                    recipient,
+                    amount,
+                    new_total_supply,
+                });
+            },
+            ProposalAction::SetPauseAuthority { new_authority } => {
+                ctx.accounts.greylocker_state.authority = new_authority;
+            },
+            ProposalAction::SetGovernanceAuthority { new_authority } => {
+                ctx.accounts.greylocker_state.governance_authority = new_authority;
+            },
+            ProposalAction::Unpause => {
+                require!(ctx.accounts.greylocker_state.paused, GreylockerError::NotPaused);
+                ctx.accounts.greylocker_state.paused = false;
+                emit!(UnpauseEvent {
+                    authority: council_key,
+                });
+            },
+            ProposalAction::ConfigureRewardReserve { epoch_emission_budget, epoch_duration, shortfall_mode } => {
+                let reserve = ctx.accounts.reward_reserve.as_mut().ok_or(GreylockerError::MissingProposalAccount)?;
+                let reserve_vault = ctx.accounts.reserve_vault.as_ref().ok_or(GreylockerError::MissingProposalAccount)?;
+                require!(reserve_vault.key() == reserve.vault, GreylockerError::MissingProposalAccount);
+
+                // Takes effect from the next epoch boundary; the current epoch keeps its budget
+                reserve.roll_epoch(reserve_vault.amount, clock.unix_timestamp)?;
+                reserve.epoch_emission_budget = epoch_emission_budget;
+                reserve.epoch_duration = epoch_duration;
+                reserve.shortfall_mode = shortfall_mode.clone();
+
+                emit!(ConfigureRewardReserveEvent {
+                    epoch_emission_budget,
+                    epoch_duration,
+                    shortfall_mode,
+                    authority: council_key,
+                });
+            },
+            ProposalAction::UpdateCouncil { signers, threshold, timelock } => {
+                // Bumping the version strands every other in-flight proposal under the old signer set
+                let council = &mut ctx.accounts.governance_council;
+                council.signers = signers;
+                council.threshold = threshold;
+                council.timelock = timelock;
+                council.config_version = council.config_version
+                    .checked_add(1)
+                    .ok_or(GreylockerError::ArithmeticError)?;
+            },
+            ProposalAction::SetLockTiers { tiers } => {
+                // Live positions keep the multiplier they locked in until they extend
+                ctx.accounts.greylocker_state.lock_tiers = tiers.clone();
+                emit!(LockTiersUpdatedEvent {
+                    tiers,
+                    authority: council_key,
+                });
+            },
+            ProposalAction::RevokeVesting { schedule } => {
+                let vesting_schedule = ctx.accounts.vesting_schedule.as_mut().ok_or(GreylockerError::MissingProposalAccount)?;
+                let vesting_escrow = ctx.accounts.vesting_escrow.as_ref().ok_or(GreylockerError::MissingProposalAccount)?;
+                let treasury = ctx.accounts.treasury.as_ref().ok_or(GreylockerError::MissingProposalAccount)?;
+                require!(vesting_schedule.key() == schedule, GreylockerError::MissingProposalAccount);
+                require!(
+                    vesting_escrow.key() == Pubkey::find_program_address(&[b"vesting-escrow"], &crate::ID).0
+                        && treasury.key() == ctx.accounts.greylocker_state.treasury,
+                    GreylockerError::MissingProposalAccount
+                );
+                require!(vesting_schedule.revocable, GreylockerError::VestingNotRevocable);
+                require!(!vesting_schedule.revoked, GreylockerError::VestingNotRevocable);
+
+                // Whatever has vested stays claimable; only the remainder goes back
+                let vested = vesting_schedule.vested_amount(clock.unix_timestamp)?;
+                let unvested = vesting_schedule.total_amount - vested;
+                vesting_schedule.total_amount = vested;
+                vesting_schedule.revoked = true;
+                ctx.accounts.greylocker_state.total_vesting_revoked = ctx.accounts.greylocker_state.total_vesting_revoked
+                    .checked_add(unvested)
+                    .ok_or(GreylockerError::ArithmeticError)?;
+
+                if unvested > 0 {
+                    let state_seeds = &[b"greylocker-state".as_ref(), &[ctx.accounts.greylocker_state.bump]];
+                    let signer = &[&state_seeds[..]];
+                    token::transfer(
+                        CpiContext::new_with_signer(
+                            ctx.accounts.token_program.to_account_info(),
+                            Transfer {
+                                from: vesting_escrow.to_account_info(),
+                                to: treasury.to_account_info(),
+                                authority: ctx.accounts.greylocker_state.to_account_info(),
+                            },
+                            signer,
+                        ),
+                        unvested,
+                    )?;
+                }
+
+                emit!(RevokeVestingEvent {
+                    vesting_schedule: schedule,
+                    beneficiary: vesting_schedule.beneficiary,
+                    vested_amount: vested,
+                    returned_amount: unvested,
+                    authority: council_key,
+                });
+            },
+            ProposalAction::CreateVestingSchedule { .. }
+            | ProposalAction::CreateAirdrop { .. }
+            | ProposalAction::CreateDataPool { .. } => return err!(GreylockerError::ProposalRunsInline),
+        }
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.status = ProposalStatus::Executed;
+
+        emit!(ExecuteProposalEvent {
+            proposal: proposal.key(),
+            proposal_id: proposal.proposal_id,
+            action,
+            executor: ctx.accounts.executor.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a proposal—any council signer can pull the emergency brake before it lands!
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        let canceller = ctx.accounts.canceller.key();
+        require!(ctx.accounts.governance_council.is_signer(&canceller), GreylockerError::NotCouncilSigner);
+
+        let proposal = &mut ctx.accounts.proposal;
+        require!(
+            proposal.status == ProposalStatus::Pending || proposal.status == ProposalStatus::Queued,
+            GreylockerError::ProposalNotPending
+        );
+        proposal.status = ProposalStatus::Cancelled;
+
+        emit!(CancelProposalEvent {
+            proposal: proposal.key(),
+            proposal_id: proposal.proposal_id,
+            canceller,
+        });
+
+        Ok(())
+    }
+
+    /// Put a parameter change to a GREY vote—let the stakers decide!
+    /// Passed votes still sit out the council timelock, and the council can cancel them before execution.
+    pub fn create_token_proposal(ctx: Context<CreateTokenProposal>, parameter: GovernanceParameter, value: u64) -> Result<()> {
+        require!(ctx.accounts.stake_account.amount > 0, GreylockerError::InsufficientStakeAmount);
+        let action = ProposalAction::UpdateParameter { parameter, value };
+        validate_proposal_action(&action)?;
+
+        let clock = Clock::get()?;
+        let state = &ctx.accounts.greylocker_state;
+        let council = &mut ctx.accounts.governance_council;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.proposal_id = council.next_proposal_id;
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.action = action.clone();
+        proposal.approvals = Vec::new();
+        proposal.council_version = council.config_version;
+        proposal.status = ProposalStatus::Pending;
+        proposal.created_at = clock.unix_timestamp;
+        proposal.eta = 0;
+        proposal.kind = ProposalKind::TokenVote;
+        proposal.voting_ends_at = clock.unix_timestamp
+            .checked_add(state.governance_parameters.voting_period)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        proposal.votes_for = 0;
+        proposal.votes_against = 0;
+        proposal.participating_stake = 0;
+        proposal.total_staked_snapshot = state.total_staked;
+        proposal.quorum_bps = state.governance_parameters.vote_quorum_bps;
+        proposal.threshold_bps = state.governance_parameters.vote_threshold_bps;
+        proposal.bump = *ctx.bumps.get("proposal").unwrap();
+
+        council.next_proposal_id = council.next_proposal_id
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        emit!(CreateProposalEvent {
+            proposal: proposal.key(),
+            proposal_id: proposal.proposal_id,
+            proposer: proposal.proposer,
+            action,
+            eta: proposal.voting_ends_at,
+        });
+
+        Ok(())
+    }
+
+    /// Cast a stake-weighted vote—one position, one say!
+    /// Only stake that hasn't grown since the proposal opened can vote, so restaking can't double-count.
+    pub fn cast_vote(ctx: Context<CastVote>, support: bool) -> Result<()> {
+        let clock = Clock::get()?;
+        let stake_account = &ctx.accounts.stake_account;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.kind == ProposalKind::TokenVote, GreylockerError::WrongProposalKind);
+        require!(proposal.status == ProposalStatus::Pending, GreylockerError::ProposalNotPending);
+        require!(clock.unix_timestamp < proposal.voting_ends_at, GreylockerError::VotingClosed);
+        require!(stake_account.last_increase_at < proposal.created_at, GreylockerError::StakeNotEligible);
+
+        let weight = vote_weight(stake_account, clock.unix_timestamp)?;
+        require!(weight > 0, GreylockerError::InsufficientStakeAmount);
+        if support {
+            proposal.votes_for = proposal.votes_for
+                .checked_add(weight)
+                .ok_or(GreylockerError::ArithmeticError)?;
+        } else {
+            proposal.votes_against = proposal.votes_against
+                .checked_add(weight)
+                .ok_or(GreylockerError::ArithmeticError)?;
+        }
+        proposal.participating_stake = proposal.participating_stake
+            .checked_add(stake_account.amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.stake_account = stake_account.key();
+        vote_record.voter = ctx.accounts.voter.key();
+        vote_record.support = support;
+        vote_record.weight = weight;
+        vote_record.bump = *ctx.bumps.get("vote_record").unwrap();
+
+        emit!(CastVoteEvent {
+            proposal: proposal.key(),
+            stake_account: stake_account.key(),
+            voter: vote_record.voter,
+            support,
+            weight,
+        });
+
+        Ok(())
+    }
+
+    /// Tally a token vote—count the ballots and start the timelock if it passed!
+    pub fn finalize_vote(ctx: Context<FinalizeVote>) -> Result<()> {
+        let clock = Clock::get()?;
+        let timelock = ctx.accounts.governance_council.timelock;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.kind == ProposalKind::TokenVote, GreylockerError::WrongProposalKind);
+        require!(proposal.status == ProposalStatus::Pending, GreylockerError::ProposalNotPending);
+        require!(clock.unix_timestamp >= proposal.voting_ends_at, GreylockerError::VotingStillOpen);
+
+        let quorum = (proposal.total_staked_snapshot as u128) * (proposal.quorum_bps as u128) / 10_000;
+        let votes_cast = (proposal.votes_for as u128) + (proposal.votes_against as u128);
+        let passed = (proposal.participating_stake as u128) >= quorum
+            && votes_cast > 0
+            && (proposal.votes_for as u128) * 10_000 > votes_cast * (proposal.threshold_bps as u128);
+        if passed {
+            proposal.queue(timelock, clock.unix_timestamp)?;
+        } else {
+            proposal.status = ProposalStatus::Defeated;
+        }
+
+        emit!(FinalizeVoteEvent {
+            proposal: proposal.key(),
+            proposal_id: proposal.proposal_id,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            participating_stake: proposal.participating_stake,
+            passed,
+            eta: proposal.eta,
+        });
+
+        Ok(())
+    }
+}
+
+// Helper function to advance a reward-per-token index by `elapsed` seconds at an APR `rate`
+pub fn advance_reward_index(index: u128, rate: u8, elapsed: u128) -> Result<u128> {
+    // Index grows by rate * elapsed * PRECISION / (365 days * 100) per staked token
+    (rate as u128)
+        .checked_mul(elapsed)
+        .and_then(|val| val.checked_mul(REWARD_INDEX_PRECISION))
+        .and_then(|val| val.checked_div(SECONDS_PER_YEAR * 100))
+        .and_then(|delta| index.checked_add(delta))
+        .ok_or(GreylockerError::ArithmeticError.into())
+}
+
+// Helper function to read a stake type's reward index as of `current_time` without writing it
+pub fn current_reward_index(state: &GreylockerState, stake_type: &StakeType, current_time: i64) -> Result<u128> {
+    let index = &state.reward_index;
+    let params = &state.governance_parameters;
+    let elapsed = current_time.saturating_sub(index.last_updated).max(0) as u128;
+    match stake_type {
+        StakeType::Security => advance_reward_index(index.security, params.security_reward_rate, elapsed),
+        StakeType::DataValidator => advance_reward_index(index.data_validator, params.data_validator_reward_rate, elapsed),
+        StakeType::Liquidity => advance_reward_index(index.liquidity, params.liquidity_reward_rate, elapsed),
+        StakeType::Service => Ok(0), // No rewards for service stakes
+    }
+}
+
+// Helper function to persist every reward index up to `current_time`; call before any rate changes
+pub fn checkpoint_reward_indices(state: &mut GreylockerState, current_time: i64) -> Result<()> {
+    let security = current_reward_index(state, &StakeType::Security, current_time)?;
+    let data_validator = current_reward_index(state, &StakeType::DataValidator, current_time)?;
+    let liquidity = current_reward_index(state, &StakeType::Liquidity, current_time)?;
+    state.reward_index.security = security;
+    state.reward_index.data_validator = data_validator;
+    state.reward_index.liquidity = liquidity;
+    state.reward_index.last_updated = state.reward_index.last_updated.max(current_time);
+    Ok(())
+}
+
+// Helper function to calculate staking rewards accrued since the position's last snapshot
+pub fn calculate_rewards(state: &GreylockerState, stake_account: &StakeAccount, current_time: i64) -> Result<u64> {
+    let index = current_reward_index(state, &stake_account.stake_type, current_time)?;
+    let delta = index.saturating_sub(stake_account.reward_index_snapshot);
+    if delta == 0 || stake_account.amount == 0 {
+        return Ok(0);
+    }
+
+    // The tier multiplier only covers the time up to lock_until; whatever accrued after it earns 1x
+    let elapsed = current_time.saturating_sub(stake_account.last_reward_claim).max(0);
+    let boosted = stake_account.lock_until
+        .min(current_time)
+        .saturating_sub(stake_account.last_reward_claim)
+        .clamp(0, elapsed);
+    let multiplier_bps = if elapsed == 0 {
+        if current_time < stake_account.lock_until { stake_account.reward_multiplier_bps as u128 } else { 10_000 }
+    } else {
+        (boosted as u128 * stake_account.reward_multiplier_bps as u128 + (elapsed - boosted) as u128 * 10_000)
+            / elapsed as u128
+    };
+
+    // Rewards = amount * (index - snapshot) * effective multiplier / PRECISION
+    let rewards = (stake_account.amount as u128)
+        .checked_mul(delta)
+        .and_then(|val| val.checked_mul(multiplier_bps))
+        .and_then(|val| val.checked_div(REWARD_INDEX_PRECISION * 10_000))
+        .and_then(|val| u64::try_from(val).ok())
+        .ok_or(GreylockerError::ArithmeticError)?;
+
+    Ok(rewards)
+}
+
+// Helper function to fold pending rewards into a position before its balance changes
+pub fn settle_rewards(state: &GreylockerState, stake_account: &mut StakeAccount, current_time: i64) -> Result<()> {
+    let rewards = calculate_rewards(state, stake_account, current_time)?;
+    stake_account.accumulated_rewards = stake_account.accumulated_rewards
+        .checked_add(rewards)
+        .ok_or(GreylockerError::ArithmeticError)?;
+    stake_account.reward_index_snapshot = current_reward_index(state, &stake_account.stake_type, current_time)?;
+    stake_account.last_reward_claim = current_time;
+    // Lock served: the position drops back to the unlocked tier until it locks again
+    if current_time >= stake_account.lock_until && stake_account.lock_tier != 0 {
+        stake_account.lock_tier = 0;
+        stake_account.reward_multiplier_bps = 10_000;
+    }
+    Ok(())
+}
+
+// Outcome of sizing a payout against the reward reserve
+pub struct ReserveDraw {
+    pub paid: u64,      // Transferred out of the reserve now
+    pub deferred: u64,  // Left owed to the recipient for a later epoch
+    pub forfeited: u64, // Written off under pro-rata shortfall
+}
+
+// Helper function to size a payout against the epoch emission budget and reserve balance
+pub fn draw_from_reserve(reserve: &mut RewardReserve, reserve_balance: u64, requested: u64, current_time: i64) -> Result<ReserveDraw> {
+    reserve.roll_epoch(reserve_balance, current_time)?;
+
+    // Pro-rata scales each claim by the coverage fixed at the epoch's start, so claim order doesn't matter;
+    // an empty reserve has no share to hand out, so it writes nothing off. Queued honours claims in full, later
+    let entitled = match reserve.shortfall_mode {
+        ReserveShortfallMode::ProRata if reserve_balance > 0 => (requested as u128)
+            .checked_mul(reserve.coverage_bps as u128)
+            .and_then(|val| val.checked_div(10_000))
+            .and_then(|val| u64::try_from(val).ok())
+            .ok_or(GreylockerError::ArithmeticError)?,
+        _ => requested,
+    };
+    let budget_left = reserve.epoch_emission_budget.saturating_sub(reserve.emitted_this_epoch);
+    // An exhausted epoch or drained reserve pays what it can now and defers the rest of the entitlement
+    let paid = entitled.min(budget_left).min(reserve_balance);
+
+    let forfeited = requested - entitled;
+    reserve.emitted_this_epoch = reserve.emitted_this_epoch
+        .checked_add(paid)
+        .ok_or(GreylockerError::ArithmeticError)?;
+    reserve.total_emitted = reserve.total_emitted
+        .checked_add(paid)
+        .ok_or(GreylockerError::ArithmeticError)?;
+    reserve.total_forfeited = reserve.total_forfeited
+        .checked_add(forfeited)
+        .ok_or(GreylockerError::ArithmeticError)?;
+
+    Ok(ReserveDraw { paid, deferred: entitled - paid, forfeited })
+}
+
+// Helper function to write an access record; shared by direct payments and vault grants
+#[allow(clippy::too_many_arguments)]
+pub fn record_access(
+    access_record: &mut AccessRecord,
+    service_provider: Pubkey,
+    user: Pubkey,
+    data_type: DataType,
+    amount: u64,
+    granted_at: i64,
+    duration: i64,
+    access_grant: Pubkey,
+    grant_id: u64,
+    bump: u8,
+) -> Result<i64> {
+    if let DataType::Custom(name) = &data_type {
+        require!(name.len() <= MAX_CUSTOM_DATA_TYPE_LEN, GreylockerError::InvalidParameterValue);
+    }
+    let expires_at = granted_at
+        .checked_add(duration)
+        .ok_or(GreylockerError::ArithmeticError)?;
+    access_record.service_provider = service_provider;
+    access_record.user = user;
+    access_record.data_type = data_type;
+    access_record.amount = amount;
+    access_record.granted_at = granted_at;
+    access_record.expires_at = expires_at;
+    access_record.access_grant = access_grant;
+    access_record.grant_id = grant_id;
+    access_record.expiry_recorded = false;
+    access_record.bump = bump;
+    Ok(expires_at)
+}
+
+// Helper function to apply a GovernanceParameter change; rate changes checkpoint accrual first
+pub fn apply_governance_parameter(state: &mut GreylockerState, parameter: &GovernanceParameter, value: u64, current_time: i64) -> Result<()> {
+    // Checkpoint accrual at the old rates so a rate change only applies going forward
+    checkpoint_reward_indices(state, current_time)?;
+    let params = &mut state.governance_parameters;
+    match parameter {
+        GovernanceParameter::SecurityStakeMinimum => params.security_stake_minimum = value,
+        GovernanceParameter::ServiceStakeMinimum => params.service_stake_minimum = value,
+        GovernanceParameter::DataValidatorStakeMinimum => params.data_validator_stake_minimum = value,
+        GovernanceParameter::LiquidityStakeMinimum => params.liquidity_stake_minimum = value,
+        GovernanceParameter::SecurityRewardRate => params.security_reward_rate = percentage(value)?,
+        GovernanceParameter::DataValidatorRewardRate => params.data_validator_reward_rate = percentage(value)?,
+        GovernanceParameter::LiquidityRewardRate => params.liquidity_reward_rate = percentage(value)?,
+        GovernanceParameter::SlashingPercentage => params.slashing_percentage = percentage(value)?,
+        GovernanceParameter::EarlyUnstakePenalty => params.early_unstake_penalty = percentage(value)?,
+        GovernanceParameter::RewardUpdateInterval => params.reward_update_interval = value,
+        GovernanceParameter::VoteQuorum => params.vote_quorum_bps = basis_points(value)?,
+        GovernanceParameter::VoteThreshold => params.vote_threshold_bps = basis_points(value)?,
+        GovernanceParameter::VotingPeriod => params.voting_period = voting_period(value)?,
+        GovernanceParameter::AppealBond => params.appeal_bond = value,
+        GovernanceParameter::ReporterBond => params.reporter_bond = value,
+        GovernanceParameter::ServiceViolationSlash => params.service_violation_slash = percentage(value)?,
+        GovernanceParameter::DataMisuseSlash => params.data_misuse_slash = percentage(value)?,
+        GovernanceParameter::ImpersonationSlash => params.impersonation_slash = percentage(value)?,
+        GovernanceParameter::RepeatOffenseMultiplier => params.repeat_offense_multiplier_bps = basis_points(value)?,
+        GovernanceParameter::UnbondingPeriod => params.unbonding_period = unbonding_period(value)?,
+        GovernanceParameter::CompoundTip => params.compound_tip_bps = compound_tip(value)?,
+        GovernanceParameter::TreasuryEpochCap => params.treasury_epoch_cap = value,
+        GovernanceParameter::TreasuryEpochDuration => params.treasury_epoch_duration = treasury_epoch_duration(value)?,
+        GovernanceParameter::ProtocolFee => params.protocol_fee_bps = protocol_fee(value)?,
+        GovernanceParameter::FeeBurnShare => params.fee_burn_share_bps = basis_points(value)?,
+        GovernanceParameter::DataRewardFee => params.data_reward_fee_bps = protocol_fee(value)?,
+        GovernanceParameter::AttestationSlash => params.attestation_slash = percentage(value)?,
+        GovernanceParameter::PoolCreatorMinReputation => params.pool_creator_min_reputation = percentage(value)?,
+    }
+    Ok(())
+}
+
+// Helper function to narrow a governance value to a 0-100 percentage
+pub fn percentage(value: u64) -> Result<u8> {
+    require!(value <= 100, GreylockerError::InvalidParameterValue);
+    Ok(value as u8)
+}
+
+// Helper function to narrow a governance value to 0-10_000 basis points
+pub fn basis_points(value: u64) -> Result<u16> {
+    require!(value <= 10_000, GreylockerError::InvalidParameterValue);
+    Ok(value as u16)
+}
+
+// Helper function to bound a token-vote window
+pub fn voting_period(value: u64) -> Result<i64> {
+    require!(
+        value >= MIN_VOTING_PERIOD as u64 && value <= MAX_VOTING_PERIOD as u64,
+        GreylockerError::InvalidParameterValue
+    );
+    Ok(value as i64)
+}
+
+// Helper function to weigh a position's vote: amount, boosted linearly by remaining lock up to 2x
+pub fn vote_weight(stake_account: &StakeAccount, current_time: i64) -> Result<u64> {
+    let remaining_lock = stake_account.lock_until
+        .saturating_sub(current_time)
+        .clamp(0, VOTE_BOOST_MAX_LOCK) as u128;
+    let boost_bps = remaining_lock * VOTE_BOOST_MAX_BPS / VOTE_BOOST_MAX_LOCK as u128;
+    (stake_account.amount as u128)
+        .checked_mul(10_000 + boost_bps)
+        .and_then(|val| val.checked_div(10_000))
+        .and_then(|val| u64::try_from(val).ok())
+        .ok_or(GreylockerError::ArithmeticError.into())
+}
+
+// Helper function to check a merkle proof; pairs are hashed in sorted order, so proofs carry no sides
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        }
+    });
+    computed == root
+}
+
+// Helper function to bound a protocol fee rate
+pub fn protocol_fee(value: u64) -> Result<u16> {
+    require!(value <= MAX_PROTOCOL_FEE_BPS, GreylockerError::InvalidParameterValue);
+    Ok(value as u16)
+}
+
+// How a gross payment divides once the protocol takes its cut
+pub struct FeeSplit {
+    pub net: u64,         // What the recipient receives
+    pub burned: u64,
+    pub to_treasury: u64,
+}
+
+// Helper function to carve the protocol fee out of a gross payment and split it between burn and treasury
+pub fn split_protocol_fee(gross: u64, fee_bps: u16, burn_share_bps: u16) -> Result<FeeSplit> {
+    let fee = gross
+        .checked_mul(fee_bps as u64)
+        .and_then(|val| val.checked_div(10_000))
+        .ok_or(GreylockerError::ArithmeticError)?;
+    let burned = fee
+        .checked_mul(burn_share_bps as u64)
+        .and_then(|val| val.checked_div(10_000))
+        .ok_or(GreylockerError::ArithmeticError)?;
+    Ok(FeeSplit { net: gross - fee, burned, to_treasury: fee - burned })
+}
+
+// Helper function to make a payment: net to the user, the protocol's cut burned and banked
+// Wallet authorities pass no signer seeds; PDA authorities pass theirs.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_with_protocol_fee<'info>(
+    token_program: &AccountInfo<'info>,
+    grey_mint: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    user: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    split: &FeeSplit,
+) -> Result<()> {
+    for (amount, to) in [(split.net, user), (split.to_treasury, treasury)] {
+        if amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    Transfer { from: from.clone(), to: to.clone(), authority: authority.clone() },
+                    signer,
+                ),
+                amount,
+            )?;
+        }
+    }
+    if split.burned > 0 {
+        token::burn(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Burn { mint: grey_mint.clone(), from: from.clone(), authority: authority.clone() },
+                signer,
+            ),
+            split.burned,
+        )?;
+    }
+    Ok(())
+}
+
+// Helper function to bound the treasury spend epoch
+pub fn treasury_epoch_duration(value: u64) -> Result<i64> {
+    require!(
+        value >= MIN_TREASURY_EPOCH as u64 && value <= MAX_TREASURY_EPOCH as u64,
+        GreylockerError::InvalidParameterValue
+    );
+    Ok(value as i64)
+}
+
+// Helper function to bound the compounding tip
+pub fn compound_tip(value: u64) -> Result<u16> {
+    require!(value <= MAX_COMPOUND_TIP_BPS, GreylockerError::InvalidParameterValue);
+    Ok(value as u16)
+}
+
+// Helper function to validate a lock period and find the best tier it qualifies for
+pub fn lock_tier_for(tiers: &[LockTier], lock_period: i64) -> Result<(u8, u16)> {
+    require!((0..=MAX_LOCK_PERIOD).contains(&lock_period), GreylockerError::InvalidLockPeriod);
+    tiers
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, tier)| tier.duration <= lock_period)
+        .map(|(index, tier)| (index as u8, tier.multiplier_bps))
+        .ok_or(GreylockerError::InvalidLockPeriod.into())
+}
+
+// Helper function to sanity-check a lock tier table: starts unlocked at 1x, strictly longer and never cheaper
+pub fn validate_lock_tiers(tiers: &[LockTier]) -> Result<()> {
+    require!(!tiers.is_empty() && tiers.len() <= MAX_LOCK_TIERS, GreylockerError::InvalidParameterValue);
+    require!(tiers[0].duration == 0 && tiers[0].multiplier_bps == 10_000, GreylockerError::InvalidParameterValue);
+    for pair in tiers.windows(2) {
+        require!(pair[1].duration > pair[0].duration, GreylockerError::InvalidParameterValue);
+        require!(pair[1].multiplier_bps >= pair[0].multiplier_bps, GreylockerError::InvalidParameterValue);
+    }
+    let last = &tiers[tiers.len() - 1];
+    require!(
+        last.duration <= MAX_LOCK_PERIOD && last.multiplier_bps <= MAX_LOCK_MULTIPLIER_BPS,
+        GreylockerError::InvalidParameterValue
+    );
+    Ok(())
+}
+
+// Helper function to bound the unbonding cooldown
+pub fn unbonding_period(value: u64) -> Result<i64> {
+    require!(value <= MAX_UNBONDING_PERIOD as u64, GreylockerError::InvalidParameterValue);
+    Ok(value as i64)
+}
+
+// Helper function to sanity-check an M-of-N signer set and its timelock
+pub fn validate_council(signers: &[Pubkey], threshold: u8, timelock: i64) -> Result<()> {
+    require!(
+        !signers.is_empty() && signers.len() <= MAX_COUNCIL_SIGNERS,
+        GreylockerError::InvalidCouncilConfig
+    );
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        GreylockerError::InvalidCouncilConfig
+    );
+    require!(timelock >= MIN_GOVERNANCE_TIMELOCK, GreylockerError::InvalidCouncilConfig);
+    for (i, signer) in signers.iter().enumerate() {
+        require!(!signers[..i].contains(signer), GreylockerError::InvalidCouncilConfig);
+    }
+    Ok(())
+}
+
+// Helper function to spend a queued proposal on the instruction that carries out its action
+pub fn carry_out_proposal(
+    proposal: &mut Account<Proposal>,
+    council: &GovernanceCouncil,
+    action: ProposalAction,
+    executor: Pubkey,
+    current_time: i64,
+) -> Result<()> {
+    require!(proposal.status == ProposalStatus::Queued, GreylockerError::ProposalNotQueued);
+    require!(proposal.council_version == council.config_version, GreylockerError::StaleProposal);
+    require!(current_time >= proposal.eta, GreylockerError::TimelockNotElapsed);
+    require!(proposal.action == action, GreylockerError::ProposalActionMismatch);
+    proposal.status = ProposalStatus::Executed;
+
+    emit!(ExecuteProposalEvent {
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        action,
+        executor,
+    });
+    Ok(())
+}
+
+// Helper function to reject proposals that could never execute
+pub fn validate_proposal_action(action: &ProposalAction) -> Result<()> {
+    match action {
+        ProposalAction::UpdateParameter { parameter, value } => match parameter {
+            GovernanceParameter::SecurityRewardRate
+            | GovernanceParameter::DataValidatorRewardRate
+            | GovernanceParameter::LiquidityRewardRate
+            | GovernanceParameter::SlashingPercentage
+            | GovernanceParameter::EarlyUnstakePenalty
+            | GovernanceParameter::ServiceViolationSlash
+            | GovernanceParameter::DataMisuseSlash
+            | GovernanceParameter::ImpersonationSlash
+            | GovernanceParameter::AttestationSlash
+            | GovernanceParameter::PoolCreatorMinReputation => percentage(*value).map(|_| ()),
+            GovernanceParameter::VoteQuorum
+            | GovernanceParameter::VoteThreshold
+            | GovernanceParameter::RepeatOffenseMultiplier
+            | GovernanceParameter::FeeBurnShare => basis_points(*value).map(|_| ()),
+            GovernanceParameter::ProtocolFee | GovernanceParameter::DataRewardFee => protocol_fee(*value).map(|_| ()),
+            GovernanceParameter::VotingPeriod => voting_period(*value).map(|_| ()),
+            GovernanceParameter::UnbondingPeriod => unbonding_period(*value).map(|_| ()),
+            GovernanceParameter::CompoundTip => compound_tip(*value).map(|_| ()),
+            GovernanceParameter::TreasuryEpochDuration => treasury_epoch_duration(*value).map(|_| ()),
+            _ => Ok(()),
+        },
+        ProposalAction::Mint { amount, .. } => {
+            require!(*amount > 0, GreylockerError::InvalidParameterValue);
+            Ok(())
+        },
+        ProposalAction::ConfigureRewardReserve { epoch_duration, .. } => {
+            require!(*epoch_duration > 0, GreylockerError::InvalidParameterValue);
+            Ok(())
+        },
+        ProposalAction::UpdateCouncil { signers, threshold, timelock } => validate_council(signers, *threshold, *timelock),
+        ProposalAction::SetLockTiers { tiers } => validate_lock_tiers(tiers),
+        ProposalAction::RevokeVesting { .. } => Ok(()),
+        ProposalAction::CreateVestingSchedule { total_amount, .. } | ProposalAction::CreateAirdrop { total_amount, .. } => {
+            require!(*total_amount > 0, GreylockerError::InvalidParameterValue);
+            Ok(())
+        },
+        ProposalAction::CreateDataPool { pool_info } => {
+            require!(pool_info.fits(), GreylockerError::InvalidDataPoolConfig);
+            Ok(())
+        },
+        ProposalAction::SetPauseAuthority { .. }
+        | ProposalAction::SetGovernanceAuthority { .. }
+        | ProposalAction::Unpause => Ok(()),
+    }
+}
+
+// Helper function to size a slash: the dispute type's base rate, escalated per prior upheld dispute, capped at 100%
+pub fn slash_percentage_for(params: &GovernanceParameters, dispute_type: &DisputeType, prior_offenses: u32) -> u64 {
+    let base = match dispute_type {
+        DisputeType::ServiceViolation => params.service_violation_slash,
+        DisputeType::DataMisuse => params.data_misuse_slash,
+        DisputeType::Impersonation => params.impersonation_slash,
+        DisputeType::Other => params.slashing_percentage,
+    } as u128;
+    let multiplier_bps = 10_000u128
+        .saturating_add((prior_offenses as u128).saturating_mul(params.repeat_offense_multiplier_bps as u128));
+    (base.saturating_mul(multiplier_bps) / 10_000).min(100) as u64
+}
+
+// Helper function to size the reputation hit: heavier offenses cost more, and repeats scale it up
+pub fn reputation_penalty_for(dispute_type: &DisputeType, prior_offenses: u32) -> u8 {
+    let base: u32 = match dispute_type {
+        DisputeType::Other => 5,
+        DisputeType::ServiceViolation => 10,
+        DisputeType::DataMisuse => 20,
+        DisputeType::Impersonation => 30,
+    };
+    base.saturating_mul(prior_offenses.saturating_add(1)).min(100) as u8
+}
+
+// Helper function to halve a reputation penalty once per elapsed half-life
+pub fn decayed_penalty(penalty_points: u32, updated_at: i64, current_time: i64) -> u32 {
+    let halvings = current_time.saturating_sub(updated_at).max(0) / REPUTATION_PENALTY_HALF_LIFE;
+    if halvings >= 32 {
+        0
+    } else {
+        penalty_points >> halvings
+    }
+}
+
+// Helper function to score a provider from its on-chain record—same record and time, same score
+pub fn compute_reputation(provider: &ServiceProvider, current_time: i64) -> u8 {
+    let tenure_days = current_time.saturating_sub(provider.registered_at).max(0) as u64 / (24 * 60 * 60);
+    let fee_points = (provider.reputation_fees / REPUTATION_FEE_UNIT).min(REPUTATION_MAX_FEE_POINTS);
+    let tenure_points = (tenure_days / REPUTATION_TENURE_DAYS_PER_POINT).min(REPUTATION_MAX_TENURE_POINTS);
+    let vindication_points = (provider.dismissed_disputes as u64 * 2).min(REPUTATION_MAX_VINDICATION_POINTS);
+    let completion_points = (provider.completed_access_grants / 10).min(REPUTATION_MAX_COMPLETION_POINTS);
+    let penalty = decayed_penalty(provider.penalty_points, provider.penalty_updated_at, current_time) as u64;
+
+    (REPUTATION_BASE + fee_points + tenure_points + vindication_points + completion_points)
+        .saturating_sub(penalty)
+        .min(100) as u8
+}
+
+// Helper function to check whether a grant is large and long enough to count toward reputation
+pub fn earns_reputation(amount: u64, duration: i64) -> bool {
+    amount >= REPUTATION_MIN_ACCESS_FEE && duration >= REPUTATION_MIN_ACCESS_DURATION
+}
+
+// Helper function to recompute the cached score and announce any movement
+pub fn refresh_reputation(provider: &mut ServiceProvider, provider_key: Pubkey, current_time: i64, reason: ReputationReason) {
+    let old_score = provider.reputation_score;
+    let new_score = compute_reputation(provider, current_time);
+    provider.reputation_score = new_score;
+    if old_score != new_score {
+        emit!(ReputationChangedEvent {
+            service_provider: provider_key,
+            old_score,
+            new_score,
+            reason,
+        });
+    }
+}
+
+// Helper function to read GreylockerState in either the current or the launch layout
+pub fn load_greylocker_state(state_info: &AccountInfo, current_time: i64) -> Result<GreylockerState> {
+    require!(state_info.owner == &crate::ID, GreylockerError::NotAuthorized);
+    let data = state_info.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == <GreylockerState as anchor_lang::Discriminator>::discriminator(),
+        GreylockerError::NotAuthorized
+    );
+    if data.len() >= GreylockerState::LEN {
+        return GreylockerState::try_deserialize(&mut &data[..]);
+    }
+
+    // Launch layout: keep what it knew, default everything added since
+    let legacy = LegacyGreylockerState::deserialize(&mut &data[8..])?;
+    let mut governance_parameters = default_governance_parameters();
+    governance_parameters.security_stake_minimum = legacy.governance_parameters.security_stake_minimum;
+    governance_parameters.service_stake_minimum = legacy.governance_parameters.service_stake_minimum;
+    governance_parameters.data_validator_stake_minimum = legacy.governance_parameters.data_validator_stake_minimum;
+    governance_parameters.liquidity_stake_minimum = legacy.governance_parameters.liquidity_stake_minimum;
+    governance_parameters.security_reward_rate = legacy.governance_parameters.security_reward_rate;
+    governance_parameters.data_validator_reward_rate = legacy.governance_parameters.data_validator_reward_rate;
+    governance_parameters.liquidity_reward_rate = legacy.governance_parameters.liquidity_reward_rate;
+    governance_parameters.slashing_percentage = legacy.governance_parameters.slashing_percentage;
+    governance_parameters.early_unstake_penalty = legacy.governance_parameters.early_unstake_penalty;
+    governance_parameters.reward_update_interval = legacy.governance_parameters.reward_update_interval;
+
+    Ok(GreylockerState {
+        grey_mint: legacy.grey_mint,
+        treasury: legacy.treasury,
+        paused: legacy.paused,
+        total_staked: legacy.total_staked,
+        total_supply: legacy.total_supply,
+        max_supply: legacy.max_supply,
+        authority: legacy.authority,
+        governance_authority: legacy.governance_authority,
+        governance_parameters,
+        reward_index: RewardIndex {
+            security: 0,
+            data_validator: 0,
+            liquidity: 0,
+            last_updated: current_time,
+        },
+        lock_tiers: default_lock_tiers(),
+        next_vesting_id: 0,
+        total_vesting_allocated: 0,
+        total_vesting_claimed: 0,
+        total_vesting_revoked: 0,
+        treasury_epoch_started_at: current_time,
+        treasury_spent_this_epoch: 0,
+        total_treasury_spent: 0,
+        bump: legacy.bump,
+    })
+}
+
+// Helper function to write GreylockerState back, growing the account (payer tops up rent) if it is still the launch size
+pub fn store_greylocker_state<'info>(
+    state_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    state: &GreylockerState,
+) -> Result<()> {
+    if state_info.data_len() < GreylockerState::LEN {
+        let rent_due = Rent::get()?
+            .minimum_balance(GreylockerState::LEN)
+            .saturating_sub(state_info.lamports());
+        if rent_due > 0 {
+            invoke(
+                &system_instruction::transfer(payer.key, state_info.key, rent_due),
+                &[payer.clone(), state_info.clone(), system_program.clone()],
+            )?;
+        }
+        state_info.realloc(GreylockerState::LEN, true)?;
+    }
+    let mut data = state_info.try_borrow_mut_data()?;
+    state.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+// Helper function to build the launch governance parameters; shared by initialize and the state migration
+pub fn default_governance_parameters() -> GovernanceParameters {
+    GovernanceParameters {
+        security_stake_minimum: 100 * 10u64.pow(9),         // 100 GREY
+        service_stake_minimum: 10_000 * 10u64.pow(9),      // 10K GREY
+        data_validator_stake_minimum: 5_000 * 10u64.pow(9), // 5K GREY
+        liquidity_stake_minimum: 1_000 * 10u64.pow(9),     // 1K GREY
+        security_reward_rate: 5,                           // 5% APR
+        data_validator_reward_rate: 10,                    // 10% APR
+        liquidity_reward_rate: 8,                          // 8% APR
+        slashing_percentage: 50,                           // 50% slash (DisputeType::Other)
+        early_unstake_penalty: 10,                         // 10% penalty
+        reward_update_interval: 7 * 24 * 60 * 60,          // 7 days in seconds
+        vote_quorum_bps: 1_000,                            // 10% of staked GREY must turn out
+        vote_threshold_bps: 5_000,                         // Simple majority of weighted votes
+        voting_period: 3 * 24 * 60 * 60,                   // 3 days in seconds
+        appeal_bond: 1_000 * 10u64.pow(9),                 // 1K GREY per side
+        reporter_bond: 500 * 10u64.pow(9),                 // 500 GREY to file a dispute
+        service_violation_slash: 30,                       // 30% slash
+        data_misuse_slash: 60,                             // 60% slash
+        impersonation_slash: 80,                           // 80% slash
+        repeat_offense_multiplier_bps: 5_000,              // +50% per prior upheld dispute
+        unbonding_period: 7 * 24 * 60 * 60,                // 7 days in seconds
+        compound_tip_bps: 50,                              // 0.5% of compounded rewards to the cranker
+        treasury_epoch_cap: 10_000_000 * 10u64.pow(9),     // 10M GREY per treasury epoch
+        treasury_epoch_duration: 30 * 24 * 60 * 60,        // 30 days in seconds
+        protocol_fee_bps: 200,                             // 2% of every access fee
+        fee_burn_share_bps: 5_000,                         // Half of each fee burned, half to treasury
+        data_reward_fee_bps: 0,                            // Data rewards untaxed until governance opts in
+        attestation_slash: 10,                             // 10% of a validator's stake per bad attestation
+        pool_creator_min_reputation: 60,                   // Providers must be above the starting score to open pools
+    }
+}
+
+// Helper function to build the launch lock tiers: none, 30, 90, 180 and 365 days
+pub fn default_lock_tiers() -> Vec<LockTier> {
+    vec![
+        LockTier { duration: 0, multiplier_bps: 10_000 },                    // 1x
+        LockTier { duration: 30 * 24 * 60 * 60, multiplier_bps: 11_000 },    // 1.1x
+        LockTier { duration: 90 * 24 * 60 * 60, multiplier_bps: 12_500 },    // 1.25x
+        LockTier { duration: 180 * 24 * 60 * 60, multiplier_bps: 15_000 },   // 1.5x
+        LockTier { duration: 365 * 24 * 60 * 60, multiplier_bps: 20_000 },   // 2x
+    ]
+}
+
+// Helper function to look up the minimum stake for a stake type
+pub fn min_stake_for(state: &GreylockerState, stake_type: &StakeType) -> u64 {
+    match stake_type {
+        StakeType::Security => state.governance_parameters.security_stake_minimum,
+        StakeType::Service => state.governance_parameters.service_stake_minimum,
+        StakeType::DataValidator => state.governance_parameters.data_validator_stake_minimum,
+        StakeType::Liquidity => state.governance_parameters.liquidity_stake_minimum,
+    }
+}
+
+// Helper function to check whether a position is tied up in the juror registry or an unexecuted jury
+pub fn juror_locked(stake_account: &StakeAccount) -> bool {
+    stake_account.juror_registered || stake_account.jury_duties > 0
+}
+
+// Helper function to read an owner's ServiceProvider through its PDA, which may not exist
+pub fn read_service_provider(service_info: &AccountInfo) -> Result<Option<ServiceProvider>> {
+    if service_info.owner == &crate::ID && !service_info.data_is_empty() {
+        return Ok(Some(ServiceProvider::try_deserialize(&mut &service_info.data.borrow()[..])?));
+    }
+    Ok(None)
+}
+
+// Helper function to check whether a position holds nothing left to withdraw, claim or answer for
+pub fn position_emptied(stake_account: &StakeAccount) -> bool {
+    stake_account.amount == 0
+        && stake_account.unbonding_amount == 0
+        && stake_account.open_unbondings == 0
+        && stake_account.accumulated_rewards == 0
+        && !stake_account.delegation_open
+        && !juror_locked(stake_account)
+        && stake_account.open_attestations == 0
+}
+
+// Helper function to find a slot's hash in the SlotHashes sysvar without deserializing all 512 entries
+pub fn find_slot_hash(slot_hashes: &AccountInfo, slot: u64) -> Result<Option<[u8; 32]>> {
+    let data = slot_hashes.try_borrow_data()?;
+    require!(data.len() >= 8, GreylockerError::InvalidParameterValue);
+    let mut len_bytes = [0u8; 8];
+    len_bytes.copy_from_slice(&data[0..8]);
+    let len = u64::from_le_bytes(len_bytes) as usize;
+
+    // Entries are (slot, hash) pairs, newest first
+    for i in 0..len {
+        let offset = 8 + i * 40;
+        if data.len() < offset + 40 {
+            break;
+        }
+        let mut slot_bytes = [0u8; 8];
+        slot_bytes.copy_from_slice(&data[offset..offset + 8]);
+        let entry_slot = u64::from_le_bytes(slot_bytes);
+        if entry_slot == slot {
+            let mut hash = [0u8; 32];
+            hash.copy_from_slice(&data[offset + 8..offset + 40]);
+            return Ok(Some(hash));
+        }
+        if entry_slot < slot {
+            break;
+        }
+    }
+    Ok(None)
+}
+
+// Helper function to draw up to `seats` distinct jurors, each pick weighted by registered stake
+pub fn draw_jurors(registry: &JurorRegistry, seed: [u8; 32], seats: u8, excluded: &[Pubkey]) -> Vec<Pubkey> {
+    let mut drawn: Vec<Pubkey> = Vec::new();
+    for seat in 0..seats {
+        let eligible = |entry: &&JurorEntry| !excluded.contains(&entry.juror) && !drawn.contains(&entry.juror);
+        let remaining_weight: u64 = registry.entries.iter().filter(eligible).map(|entry| entry.weight).sum();
+        if remaining_weight == 0 {
+            break;
+        }
+
+        let roll = keccak::hashv(&[&seed, &[seat]]).0;
+        let mut roll_bytes = [0u8; 8];
+        roll_bytes.copy_from_slice(&roll[0..8]);
+        let mut target = u64::from_le_bytes(roll_bytes) % remaining_weight;
+
+        let mut pick = None;
+        for entry in registry.entries.iter().filter(eligible) {
+            if target < entry.weight {
+                pick = Some(entry.juror);
+                break;
+            }
+            target -= entry.weight;
+        }
+        match pick {
+            Some(juror) => drawn.push(juror),
+            None => break,
+        }
+    }
+    drawn
+}
+
+// Helper function to total the stake behind a position, counting GREY delegated to it
+pub fn backing_stake(stake_account: &StakeAccount) -> u64 {
+    stake_account.amount.saturating_add(stake_account.delegated_amount)
+}
+
+// Helper function to pick the reward index delegations accrue on; service stakes earn nothing
+// themselves, so their delegators are paid at the security rate for underwriting the service
+pub fn delegation_reward_type(stake_type: &StakeType) -> StakeType {
+    match stake_type {
+        StakeType::Service => StakeType::Security,
+        other => other.clone(),
+    }
+}
+
+// Account structs
+#[account]
+pub struct GreylockerState {
+    pub grey_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub paused: bool,
+    pub total_staked: u64,
+    pub total_supply: u64,
+    pub max_supply: u64,
+    pub authority: Pubkey,
+    pub governance_authority: Pubkey,
+    pub governance_parameters: GovernanceParameters,
+    pub reward_index: RewardIndex,
+    pub lock_tiers: Vec<LockTier>,
+    pub next_vesting_id: u64,
+    pub total_vesting_allocated: u64, // GREY ever placed under vesting
+    pub total_vesting_claimed: u64,
+    pub total_vesting_revoked: u64,   // Unvested GREY returned to the treasury
+    pub treasury_epoch_started_at: i64,
+    pub treasury_spent_this_epoch: u64,
+    pub total_treasury_spent: u64,
+    pub bump: u8,
+}
+
+// Launch layout of GreylockerState, read once by migrate_treasury
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyGreylockerState {
+    pub grey_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub paused: bool,
+    pub total_staked: u64,
+    pub total_supply: u64,
+    pub max_supply: u64,
+    pub authority: Pubkey,
+    pub governance_authority: Pubkey,
+    pub governance_parameters: LegacyGovernanceParameters,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyGovernanceParameters {
+    pub security_stake_minimum: u64,
+    pub service_stake_minimum: u64,
+    pub data_validator_stake_minimum: u64,
+    pub liquidity_stake_minimum: u64,
+    pub security_reward_rate: u8,
+    pub data_validator_reward_rate: u8,
+    pub liquidity_reward_rate: u8,
+    pub slashing_percentage: u8,
+    pub early_unstake_penalty: u8,
+    pub reward_update_interval: u64,
+}
+
+#[account]
+pub struct RewardReserve {
+    pub vault: Pubkey,
+    pub epoch_emission_budget: u64,
+    pub epoch_duration: i64,
+    pub current_epoch: u64,
+    pub epoch_started_at: i64,
+    pub emitted_this_epoch: u64,
+    pub coverage_bps: u16, // Share of each claim honoured this epoch under pro-rata
+    pub shortfall_mode: ReserveShortfallMode,
+    pub total_funded: u64,
+    pub total_emitted: u64,
+    pub total_forfeited: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct GovernanceCouncil {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock: i64,
+    pub next_proposal_id: u64,
+    pub config_version: u64, // Bumped on every signer-set change
+    pub bump: u8,
+}
+
+#[account]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub approvals: Vec<Pubkey>,
+    pub council_version: u64,
+    pub status: ProposalStatus,
+    pub created_at: i64,
+    pub eta: i64, // Earliest execution time, set once the threshold is met
+    pub kind: ProposalKind,
+    pub voting_ends_at: i64,         // Token votes only
+    pub votes_for: u64,              // Lock-boosted weight
+    pub votes_against: u64,          // Lock-boosted weight
+    pub participating_stake: u64,    // Raw staked amount that voted, for quorum
+    pub total_staked_snapshot: u64,  // Quorum denominator at creation
+    pub quorum_bps: u16,             // Snapshotted so mid-vote parameter changes can't move the goalposts
+    pub threshold_bps: u16,
+    pub bump: u8,
+}
+
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub stake_account: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct StakeRegistry {
+    pub owner: Pubkey,
+    pub next_position_id: u64,
+    pub open_positions: u32,
+    pub bump: u8,
+}
+
+#[account]
+pub struct StakeAccount {
+    pub owner: Pubkey,
+    pub position_id: u64,
+    pub stake_type: StakeType,
+    pub amount: u64,
+    pub lock_until: i64,
+    pub lock_tier: u8,               // Index into GreylockerState::lock_tiers when locked
+    pub reward_multiplier_bps: u16,  // Tier multiplier snapshotted at lock time
+    pub last_reward_claim: i64,
+    pub accumulated_rewards: u64,
+    pub reward_index_snapshot: u128, // Reward index at the last settlement
+    pub staked_at: i64,
+    pub last_increase_at: i64, // Last time amount grew; gates voting on older proposals
+    pub unbonding_amount: u64, // Requested out but still cooling down—and still slashable
+    pub delegated_amount: u64, // GREY delegated to this position through its delegation pool
+    pub delegation_open: bool, // Set once a delegation pool backs this position
+    pub auto_compound: bool,   // Lets anyone crank rewards back into the position
+    pub juror_registered: bool, // Listed in the juror registry at its current amount
+    pub jury_duties: u32,      // Jury seats held on unexecuted disputes; stake is locked while non-zero
+    pub open_attestations: u32, // Attestations still open to invalidation or slashing; stake is locked while non-zero
+    pub next_unbonding_id: u64, // Seeds the next UnbondingRequest, so several cooldowns can run at once
+    pub open_unbondings: u32,  // UnbondingRequests not yet withdrawn; the position can't close while non-zero
+    pub bump: u8,
+}
+
+#[account]
+pub struct UnbondingRequest {
+    pub stake_account: Pubkey,
+    pub owner: Pubkey,
+    pub request_id: u64,
+    pub amount: u64,
+    pub requested_at: i64,
+    pub available_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct MerkleDistributor {
+    pub merkle_root: [u8; 32],
+    pub vault: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub num_leaves: u32,
+    pub claimed_count: u32,
+    pub expires_at: i64,         // Claims close and clawback opens
+    pub clawed_back: bool,
+    pub bump: u8,
+    pub claimed_bitmap: Vec<u8>, // One bit per leaf index
+}
+
+#[account]
+pub struct VestingSchedule {
+    pub schedule_id: u64,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,   // Shrinks to the vested amount on revocation
+    pub claimed_amount: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,       // Nothing is claimable before the cliff
+    pub end_ts: i64,
+    pub kind: VestingKind,
+    pub revocable: bool,
+    pub revoked: bool,
+    pub bump: u8,
+}
+
+#[account]
+pub struct LiquidStakingPool {
+    pub st_mint: Pubkey,
+    pub vault: Pubkey,
+    pub total_backing: u64,          // Harvested GREY held in the vault behind every stGREY
+    pub pending_rewards: u64,        // Accrued but not yet harvested from the reward reserve
+    pub reward_index_snapshot: u128, // Liquidity reward index at the last accrual
+    pub bump: u8,
+}
+
+#[account]
+pub struct DelegationPool {
+    pub operator: Pubkey,
+    pub operator_stake_account: Pubkey,
+    pub stake_type: StakeType,
+    pub vault: Pubkey,
+    pub commission_bps: u16,         // Operator's cut of delegator rewards
+    pub total_delegated: u64,        // Active delegated GREY, backing the operator
+    pub total_shares: u64,           // Claims on total_delegated; slashing shrinks the GREY, not the shares
+    pub total_unbonding: u64,        // Undelegated GREY still cooling down—and still slashable
+    pub unbonding_shares: u64,       // Claims on total_unbonding
+    pub reward_index_snapshot: u128, // Reward index at the last accrual
+    pub acc_reward_per_share: u128,  // Delegator rewards per share, scaled by REWARD_INDEX_PRECISION
+    pub operator_rewards: u64,       // Commission owed to the operator
+    pub bump: u8,
+}
+
+#[account]
+pub struct Delegation {
+    pub delegator: Pubkey,
+    pub pool: Pubkey,
+    pub shares: u64,
+    pub reward_debt: u128, // shares * acc_reward_per_share at the last settlement
+    pub pending_rewards: u64,
+    pub unbonding_shares: u64,
+    pub unbonding_available_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct ServiceProvider {
+    pub owner: Pubkey,
+    pub stake_account: Pubkey,
+    pub service_info: ServiceInfo,
+    pub registered_at: i64,
+    pub total_access_fees_paid: u64,
+    pub reputation_fees: u64,      // Fees from grants that cleared the reputation minimums
+    pub reputation_score: u8,
+    pub upheld_disputes: u32,      // Repeat-offense counter for graduated slashing
+    pub last_offense_at: i64,
+    pub frozen: bool,              // Set when a slash drops stake below service_stake_minimum
+    pub dismissed_disputes: u32,   // Disputes against this provider that were rejected
+    pub completed_access_grants: u64,
+    pub penalty_points: u32,       // Reputation penalty, halving every REPUTATION_PENALTY_HALF_LIFE
+    pub penalty_updated_at: i64,
+    pub open_disputes: u32,        // Blocks unbonded withdrawals while non-zero
+    pub access_grants_issued: u64, // Next AccessRecord grant id; every grant gets its own record
+    pub bump: u8,
+}
+
+#[account]
+pub struct AccessRecord {
+    pub service_provider: Pubkey,
+    pub user: Pubkey,
+    pub data_type: DataType,
+    pub amount: u64,
+    pub granted_at: i64,
+    pub expires_at: i64,
+    pub access_grant: Pubkey, // Linked vault AccessGrant, default when paid directly
+    pub grant_id: u64,        // Per-provider sequence number, part of the PDA seeds
+    pub expiry_recorded: bool, // Counted toward the provider's reputation once it ran its course
+    pub bump: u8,
+}
+
+#[account]
+pub struct Dispute {
+    pub dispute_id: Pubkey,
+    pub reporter: Pubkey,
+    pub reported_service: Pubkey,
+    pub accused: Pubkey,           // Owner of the reported ServiceProvider
+    pub evidence: String,
+    pub counter_evidence: String,  // Accused's response URI
+    pub created_at: i64,
+    pub status: DisputeStatus,
+    pub resolution: Option<DisputeResolution>, // Standing ruling once voting closes
+    pub resolved_at: Option<i64>,              // Set when the final ruling is executed
+    pub dispute_type: DisputeType,
+    pub round: u8,                 // 0 for the first hearing, +1 per appeal
+    pub jury_size: u8,
+    pub jurors_seated: u8,
+    pub votes_upheld: u8,
+    pub votes_dismissed: u8,
+    pub phase_deadline: i64,       // End of the current phase
+    pub appellant: Pubkey,
+    pub appeal_bond: u64,          // Per side
+    pub appeal_bonds_posted: u8,
+    pub executed: bool,
+    pub reporter_bond: u64,        // Escrowed at filing
+    pub data_pool: Option<Pubkey>, // Provider pool the dispute was filed through
+    pub drawn_jurors: Vec<Pubkey>, // This round's draw, at most APPEAL_JURY_SIZE
+    pub draw_slot: u64,            // Slot whose hash seeds this round's draw
+    pub bump: u8,
+}
+
+#[account]
+pub struct ReporterRecord {
+    pub reporter: Pubkey,
+    pub window_started_at: i64,
+    pub disputes_in_window: u32,
+    pub open_disputes: u32,
+    pub total_filed: u64,
+    pub total_rejected: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct JurorRegistry {
+    pub entries: Vec<JurorEntry>,
+    pub total_weight: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct JurorRecord {
+    pub dispute: Pubkey,
+    pub juror: Pubkey,
+    pub stake_account: Pubkey,
+    pub round: u8,
+    pub vote: Option<DisputeResolution>,
+    pub bump: u8,
+}
+
+#[account]
+pub struct DataPool {
+    pub pool_id: Pubkey,
+    pub name: String,
+    pub data_type: String,
+    pub reward_rate: u64,
+    pub description: String,
+    pub created_at: i64,
+    pub total_participants: u64,
+    pub total_rewards_paid: u64,
+    pub total_contributions: u64,
+    pub accepted_data_points: u64,
+    pub max_participants: u64,   // 0 for no cap
+    pub expires_at: i64,         // 0 for no expiry
+    pub budget_funded: u64,      // GREY ever moved into the pool's budget vault; 0 draws on the reward reserve
+    pub budget_spent: u64,
+    pub open_contributions: u64, // Submitted but not yet rejected, invalidated or paid
+    pub budget_committed: u64,   // Escrowed for contributions still in flight
+    pub owed_rewards: u64,       // Deferred rewards owed to participants and settlements; blocks closing
+    pub service_provider: Pubkey, // Sponsoring provider; default for governance pools
+    pub open_disputes: u32,      // Disputes filed against the sponsor through this pool
+    pub epsilon_budget: u64,     // Privacy loss any one participant may bear, in thousandths of epsilon
+    pub max_query_epsilon: u64,  // Most a single query may spend
+    pub total_queries: u64,
+    pub paused: bool,
+    pub active: bool,            // Cleared for good by deactivate_data_pool
+    pub bump: u8,
+}
+
+#[account]
+pub struct PoolParticipant {
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub joined_at: i64,
+    pub total_rewards_received: u64,
+    pub last_reward_at: i64,
+    pub owed_rewards: u64, // Awarded but not yet paid from the reserve
+    pub accepted_data_points: u64,
+    pub consent_hash: [u8; 32], // Terms the user consented to on joining
+    pub consented_at: i64,
+    pub queries_answered: u64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct Contribution {
+    pub pool: Pubkey,
+    pub participant: Pubkey,
+    pub contributor: Pubkey,
+    pub epoch: u64,
+    pub data_hash: [u8; 32], // Commitment to the off-chain data; validators check against it
+    pub data_points: u64,
+    pub submitted_at: i64,
+    pub approvals: u16,
+    pub rejections: u16,
+    pub status: ContributionStatus,
+    pub finalized_at: i64,
+    pub reward: u64,         // reward_rate x data_points, fixed at submission
+    pub escrowed: bool,      // Reward held against the pool's budget
+    pub bump: u8,
+}
+
+#[account]
+pub struct PrivacyLedger {
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub epsilon_spent: u64, // Privacy budget consumed by recorded queries, across every membership
+    pub bump: u8,
+}
+
+#[account]
+pub struct PoolSettlement {
+    pub user: Pubkey,
+    pub pool_id: Pubkey,
+    pub owed_rewards: u64, // Left behind on leaving, or earned by work accepted after leaving
+    pub bump: u8,
+}
+
+#[account]
+pub struct ContributionAttestation {
+    pub contribution: Pubkey,
+    pub validator: Pubkey,
+    pub stake_account: Pubkey,
+    pub approve: bool,
+    pub slashed: bool,
+    pub bump: u8,
+}
+
+// Data structs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GovernanceParameters {
+    pub security_stake_minimum: u64,
+    pub service_stake_minimum: u64,
+    pub data_validator_stake_minimum: u64,
+    pub liquidity_stake_minimum: u64,
+    pub security_reward_rate: u8,
+    pub data_validator_reward_rate: u8,
+    pub liquidity_reward_rate: u8,
+    pub slashing_percentage: u8,     // Also the slash for DisputeType::Other
+    pub early_unstake_penalty: u8,
+    pub reward_update_interval: u64, // Legacy; accrual is continuous under the reward index
+    pub vote_quorum_bps: u16,        // Turnout needed, as a share of total staked
+    pub vote_threshold_bps: u16,     // Weighted "for" share needed to pass
+    pub voting_period: i64,
+    pub appeal_bond: u64,            // Posted by each side of a dispute appeal
+    pub reporter_bond: u64,          // Escrowed by a reporter when filing a dispute
+    pub service_violation_slash: u8,
+    pub data_misuse_slash: u8,
+    pub impersonation_slash: u8,
+    pub repeat_offense_multiplier_bps: u16, // Added to the slash per prior upheld dispute
+    pub unbonding_period: i64,       // Cooldown during which unstaked GREY stays slashable
+    pub compound_tip_bps: u16,       // Cranker's cut of rewards rolled back into a position
+    pub treasury_epoch_cap: u64,     // Most treasury_spend may move per epoch
+    pub treasury_epoch_duration: i64,
+    pub protocol_fee_bps: u16,       // Protocol's cut of access fees
+    pub fee_burn_share_bps: u16,     // Share of each protocol fee burned; the rest goes to the treasury
+    pub data_reward_fee_bps: u16,    // Protocol's cut of data-pool rewards
+    pub attestation_slash: u8,       // Slash for a validator who attested to invalidated data
+    pub pool_creator_min_reputation: u8, // Reputation a ServiceProvider needs to open its own data pool
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RewardIndex {
+    pub security: u128,
+    pub data_validator: u128,
+    pub liquidity: u128,
+    pub last_updated: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct LockTier {
+    pub duration: i64,        // Minimum lock, in seconds, to qualify
+    pub multiplier_bps: u16,  // Reward multiplier; 10_000 is 1x
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ServiceInfo {
+    pub name: String,
+    pub description: String,
+    pub metadata_uri: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct JurorEntry {
+    pub juror: Pubkey,
+    pub stake_account: Pubkey,
+    pub weight: u64, // Own stake snapshotted at registration
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct DataPoolInfo {
+    pub name: String,
+    pub data_type: String,
+    pub reward_rate: u64,
+    pub description: String,
+    pub max_participants: u64, // 0 for no cap
+    pub expires_at: i64,       // 0 for no expiry
+    pub epsilon_budget: u64,   // Per participant, in thousandths of epsilon
+    pub max_query_epsilon: u64,
+}
+
+// Enums
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum SpendCategory {
+    Grants,
+    Operations,
+    Liquidity,
+    Marketing,
+    Security,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum VestingKind {
+    Linear,
+    Step { interval: i64 }, // Releases in equal tranches every `interval` seconds
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ContributionStatus {
+    Pending,     // Collecting attestations
+    Accepted,    // Awaiting the challenge period before payout
+    Rejected,
+    Invalidated, // Accepted, then struck down—approving validators are slashable
+    Paid,
+    Forfeited,   // Contributor withdrew consent before payout
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum StakeType {
+    Security,
+    Service,
+    DataValidator,
+    Liquidity,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ProposalAction {
+    UpdateParameter { parameter: GovernanceParameter, value: u64 },
+    Mint { recipient: Pubkey, amount: u64 },
+    SetPauseAuthority { new_authority: Pubkey },
+    SetGovernanceAuthority { new_authority: Pubkey },
+    Unpause,
+    ConfigureRewardReserve { epoch_emission_budget: u64, epoch_duration: i64, shortfall_mode: ReserveShortfallMode },
+    UpdateCouncil { signers: Vec<Pubkey>, threshold: u8, timelock: i64 },
+    SetLockTiers { tiers: Vec<LockTier> },
+    RevokeVesting { schedule: Pubkey },
+    // Carried out by their own instructions, which create accounts execute_proposal can't
+    CreateVestingSchedule {
+        beneficiary: Pubkey,
+        total_amount: u64,
+        start_ts: i64,
+        cliff_ts: i64,
+        end_ts: i64,
+        kind: VestingKind,
+        revocable: bool,
+    },
+    CreateAirdrop { merkle_root: [u8; 32], num_leaves: u32, total_amount: u64, expires_at: i64 },
+    CreateDataPool { pool_info: DataPoolInfo },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ProposalStatus {
+    Pending,
+    Queued,
+    Executed,
+    Cancelled,
+    Defeated,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ProposalKind {
+    Council,   // Passed by M-of-N council approvals
+    TokenVote, // Passed by stake-weighted GREY votes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ReserveShortfallMode {
+    ProRata, // Scale claims by reserve coverage and write off the rest
+    Queued,  // Pay what the reserve allows and keep the rest owed
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum DisputeType {
     ServiceViolation,
     DataMisuse,
     Other,
+    Impersonation,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ReputationReason {
+    AccessFeePaid,
+    AccessCompleted,
+    DisputeUpheld,
+    DisputeDismissed,
+    Refresh,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum DisputeStatus {
+    Pending,        // Jury seating
+    ResponseWindow, // Accused may post counter-evidence
+    Voting,         // Jurors vote
+    Resolved,       // Ruling stands; appeal window runs until phase_deadline
+    Appealed,       // Awaiting the matching bond, then the appeal jury
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum DisputeResolution {
+    Upheld,
+    Dismissed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum DataType {
+    Browsing,
+    Purchase,
+    Location,
+    Custom(String),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum GovernanceParameter {
+    SecurityStakeMinimum,
+    ServiceStakeMinimum,
+    DataValidatorStakeMinimum,
+    LiquidityStakeMinimum,
+    SecurityRewardRate,
+    DataValidatorRewardRate,
+    LiquidityRewardRate,
+    SlashingPercentage,
+    EarlyUnstakePenalty,
+    RewardUpdateInterval,
+    VoteQuorum,
+    VoteThreshold,
+    VotingPeriod,
+    AppealBond,
+    ReporterBond,
+    ServiceViolationSlash,
+    DataMisuseSlash,
+    ImpersonationSlash,
+    RepeatOffenseMultiplier,
+    UnbondingPeriod,
+    CompoundTip,
+    TreasuryEpochCap,
+    TreasuryEpochDuration,
+    ProtocolFee,
+    FeeBurnShare,
+    DataRewardFee,
+    AttestationSlash,
+    PoolCreatorMinReputation,
+}
+
+// Context structs
+#[derive(Accounts)]
+#[instruction(bump: u8)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = initializer,
+        space = GreylockerState::LEN,
+        seeds = [b"greylocker-state"],
+        bump
+    )]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        init,
+        payer = initializer,
+        mint::decimals = 9,
+        mint::authority = greylocker_state,
+    )]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = initializer,
+        seeds = [b"treasury"],
+        bump,
+        token::mint = grey_mint,
+        token::authority = greylocker_state,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct TreasurySpend<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, address = greylocker_state.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = greylocker_state.grey_mint)]
+    pub recipient: Account<'info, TokenAccount>,
+    pub governance_authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTreasury<'info> {
+    /// CHECK: May still hold the launch layout; load_greylocker_state checks owner, discriminator and layout
+    #[account(mut, seeds = [b"greylocker-state"], bump)]
+    pub greylocker_state: UncheckedAccount<'info>,
+    // Checked against the loaded state in the handler
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, token::authority = governance_authority)]
+    pub legacy_treasury: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = governance_authority,
+        seeds = [b"treasury"],
+        bump,
+        token::mint = grey_mint,
+        token::authority = greylocker_state,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 + 4 + 1,
+        seeds = [b"stake-registry", user.key().as_ref()],
+        bump
+    )]
+    pub stake_registry: Account<'info, StakeRegistry>,
+    #[account(
+        init,
+        payer = user,
+        space = StakeAccount::LEN,
+        seeds = [b"stake-account", user.key().as_ref(), stake_registry.next_position_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"stake-vault", stake_account.key().as_ref()],
+        bump,
+        token::mint = grey_mint,
+        token::authority = stake_account,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct TopUpStake<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", user.key().as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"stake-vault", stake_account.key().as_ref()], bump, token::mint = grey_mint, token::authority = stake_account)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", user.key().as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SplitStake<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, seeds = [b"stake-registry", user.key().as_ref()], bump = stake_registry.bump)]
+    pub stake_registry: Account<'info, StakeRegistry>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", user.key().as_ref(), source_stake_account.position_id.to_le_bytes().as_ref()],
+        bump = source_stake_account.bump
+    )]
+    pub source_stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"stake-vault", source_stake_account.key().as_ref()], bump, token::mint = grey_mint, token::authority = source_stake_account)]
+    pub source_stake_vault: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = user,
+        space = StakeAccount::LEN,
+        seeds = [b"stake-account", user.key().as_ref(), stake_registry.next_position_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub new_stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"stake-vault", new_stake_account.key().as_ref()],
+        bump,
+        token::mint = grey_mint,
+        token::authority = new_stake_account,
+    )]
+    pub new_stake_vault: Account<'info, TokenAccount>,
+    /// CHECK: the owner's ServiceProvider PDA, which may not exist; read only to check its linked position's disputes
+    #[account(seeds = [b"service-provider", user.key().as_ref()], bump)]
+    pub service_provider: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MergeStakes<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, seeds = [b"stake-registry", user.key().as_ref()], bump = stake_registry.bump)]
+    pub stake_registry: Account<'info, StakeRegistry>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", user.key().as_ref(), destination_stake_account.position_id.to_le_bytes().as_ref()],
+        bump = destination_stake_account.bump
+    )]
+    pub destination_stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"stake-vault", destination_stake_account.key().as_ref()], bump, token::mint = grey_mint, token::authority = destination_stake_account)]
+    pub destination_stake_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"stake-account", user.key().as_ref(), source_stake_account.position_id.to_le_bytes().as_ref()],
+        bump = source_stake_account.bump,
+        constraint = source_stake_account.key() != destination_stake_account.key() @ GreylockerError::InvalidParameterValue
+    )]
+    pub source_stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"stake-vault", source_stake_account.key().as_ref()], bump, token::mint = grey_mint, token::authority = source_stake_account)]
+    pub source_stake_vault: Account<'info, TokenAccount>,
+    /// CHECK: the owner's ServiceProvider PDA, which may not exist; read only to find its linked position
+    #[account(seeds = [b"service-provider", user.key().as_ref()], bump)]
+    pub service_provider: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", user.key().as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"unbonding", stake_account.key().as_ref(), stake_account.next_unbonding_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub unbonding_request: Account<'info, UnbondingRequest>,
+    #[account(mut, seeds = [b"stake-vault", stake_account.key().as_ref()], bump, token::mint = grey_mint, token::authority = stake_account)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUnbonded<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, seeds = [b"stake-registry", user.key().as_ref()], bump = stake_registry.bump)]
+    pub stake_registry: Account<'info, StakeRegistry>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", user.key().as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        mut,
+        close = user,
+        seeds = [b"unbonding", stake_account.key().as_ref(), unbonding_request.request_id.to_le_bytes().as_ref()],
+        bump = unbonding_request.bump
+    )]
+    pub unbonding_request: Account<'info, UnbondingRequest>,
+    #[account(mut, seeds = [b"stake-vault", stake_account.key().as_ref()], bump, token::mint = grey_mint, token::authority = stake_account)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    /// CHECK: the owner's ServiceProvider PDA, which may not exist; read only to check open disputes
+    #[account(seeds = [b"service-provider", user.key().as_ref()], bump)]
+    pub service_provider: UncheckedAccount<'info>,
+    #[account(mut, token::mint = grey_mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", user.key().as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"reward-reserve"], bump = reward_reserve.bump)]
+    pub reward_reserve: Account<'info, RewardReserve>,
+    #[account(mut, address = reward_reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct OpenDelegationPool<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", operator.key().as_ref(), operator_stake_account.position_id.to_le_bytes().as_ref()],
+        bump = operator_stake_account.bump
+    )]
+    pub operator_stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + 32 + 32 + 1 + 32 + 2 + 8 + 8 + 8 + 8 + 16 + 16 + 8 + 1,
+        seeds = [b"delegation-pool", operator_stake_account.key().as_ref()],
+        bump
+    )]
+    pub delegation_pool: Account<'info, DelegationPool>,
+    #[account(
+        init,
+        payer = operator,
+        seeds = [b"delegation-vault", delegation_pool.key().as_ref()],
+        bump,
+        token::mint = grey_mint,
+        token::authority = delegation_pool,
+    )]
+    pub delegation_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Delegate<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"delegation-pool", operator_stake_account.key().as_ref()], bump = delegation_pool.bump)]
+    pub delegation_pool: Account<'info, DelegationPool>,
+    #[account(mut, address = delegation_pool.operator_stake_account)]
+    pub operator_stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init_if_needed,
+        payer = delegator,
+        space = 8 + 32 + 32 + 8 + 16 + 8 + 8 + 8 + 1,
+        seeds = [b"delegation", delegation_pool.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    #[account(mut, address = delegation_pool.vault)]
+    pub delegation_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = delegation_vault.mint)]
+    pub delegator_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Undelegate<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"delegation-pool", operator_stake_account.key().as_ref()], bump = delegation_pool.bump)]
+    pub delegation_pool: Account<'info, DelegationPool>,
+    #[account(mut, address = delegation_pool.operator_stake_account)]
+    pub operator_stake_account: Account<'info, StakeAccount>,
+    #[account(
+        mut,
+        seeds = [b"delegation", delegation_pool.key().as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    pub delegator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUndelegated<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"delegation-pool", delegation_pool.operator_stake_account.as_ref()], bump = delegation_pool.bump)]
+    pub delegation_pool: Account<'info, DelegationPool>,
+    #[account(
+        mut,
+        seeds = [b"delegation", delegation_pool.key().as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    #[account(mut, address = delegation_pool.vault)]
+    pub delegation_vault: Account<'info, TokenAccount>,
+    /// CHECK: the operator's ServiceProvider PDA, which may not exist; read only to check open disputes
+    #[account(seeds = [b"service-provider", delegation_pool.operator.as_ref()], bump)]
+    pub service_provider: UncheckedAccount<'info>,
+    #[account(mut, token::mint = delegation_vault.mint)]
+    pub delegator_token_account: Account<'info, TokenAccount>,
+    pub delegator: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimDelegationRewards<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"delegation-pool", delegation_pool.operator_stake_account.as_ref()], bump = delegation_pool.bump)]
+    pub delegation_pool: Account<'info, DelegationPool>,
+    #[account(
+        mut,
+        seeds = [b"delegation", delegation_pool.key().as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    #[account(mut, seeds = [b"reward-reserve"], bump = reward_reserve.bump)]
+    pub reward_reserve: Account<'info, RewardReserve>,
+    #[account(mut, address = reward_reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = reserve_vault.mint)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    pub delegator: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimOperatorCommission<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        mut,
+        seeds = [b"delegation-pool", delegation_pool.operator_stake_account.as_ref()],
+        bump = delegation_pool.bump,
+        has_one = operator
+    )]
+    pub delegation_pool: Account<'info, DelegationPool>,
+    #[account(mut, seeds = [b"reward-reserve"], bump = reward_reserve.bump)]
+    pub reward_reserve: Account<'info, RewardReserve>,
+    #[account(mut, address = reward_reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = reserve_vault.mint)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    pub operator: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetAutoCompound<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake-account", user.key().as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CrankCompound<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", stake_account.owner.as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"stake-vault", stake_account.key().as_ref()], bump, token::mint = grey_mint, token::authority = stake_account)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"reward-reserve"], bump = reward_reserve.bump)]
+    pub reward_reserve: Account<'info, RewardReserve>,
+    #[account(mut, address = reward_reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint)]
+    pub cranker_token_account: Account<'info, TokenAccount>,
+    pub cranker: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeLiquidStaking<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = governance_authority,
+        space = 8 + 32 + 32 + 8 + 8 + 16 + 1,
+        seeds = [b"liquid-staking"],
+        bump
+    )]
+    pub liquid_staking_pool: Account<'info, LiquidStakingPool>,
+    #[account(
+        init,
+        payer = governance_authority,
+        seeds = [b"st-grey-mint"],
+        bump,
+        mint::decimals = grey_mint.decimals,
+        mint::authority = liquid_staking_pool,
+    )]
+    pub st_grey_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = governance_authority,
+        seeds = [b"liquid-staking-vault"],
+        bump,
+        token::mint = grey_mint,
+        token::authority = liquid_staking_pool,
+    )]
+    pub liquid_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct StakeLiquid<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"liquid-staking"], bump = liquid_staking_pool.bump)]
+    pub liquid_staking_pool: Account<'info, LiquidStakingPool>,
+    #[account(mut, address = liquid_staking_pool.st_mint)]
+    pub st_grey_mint: Account<'info, Mint>,
+    #[account(mut, address = liquid_staking_pool.vault)]
+    pub liquid_vault: Account<'info, TokenAccount>,
+    // Receives backing orphaned when every stGREY has been burned
+    #[account(mut, address = greylocker_state.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = greylocker_state.grey_mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = st_grey_mint)]
+    pub user_st_token_account: Account<'info, TokenAccount>,
+    pub user: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct HarvestLiquidRewards<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"liquid-staking"], bump = liquid_staking_pool.bump)]
+    pub liquid_staking_pool: Account<'info, LiquidStakingPool>,
+    #[account(address = liquid_staking_pool.st_mint)]
+    pub st_grey_mint: Account<'info, Mint>,
+    #[account(mut, address = liquid_staking_pool.vault)]
+    pub liquid_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"reward-reserve"], bump = reward_reserve.bump)]
+    pub reward_reserve: Account<'info, RewardReserve>,
+    #[account(mut, address = reward_reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UnwrapLiquidStake<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, seeds = [b"liquid-staking"], bump = liquid_staking_pool.bump)]
+    pub liquid_staking_pool: Account<'info, LiquidStakingPool>,
+    #[account(mut, address = liquid_staking_pool.st_mint)]
+    pub st_grey_mint: Account<'info, Mint>,
+    #[account(mut, address = liquid_staking_pool.vault)]
+    pub liquid_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = st_grey_mint)]
+    pub user_st_token_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 + 4 + 1,
+        seeds = [b"stake-registry", user.key().as_ref()],
+        bump
+    )]
+    pub stake_registry: Account<'info, StakeRegistry>,
+    #[account(
+        init,
+        payer = user,
+        space = StakeAccount::LEN,
+        seeds = [b"stake-account", user.key().as_ref(), stake_registry.next_position_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"stake-vault", stake_account.key().as_ref()],
+        bump,
+        token::mint = grey_mint,
+        token::authority = stake_account,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CreateVestingSchedule<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = governance_authority,
+        space = 8 + 8 + 32 + 8 + 8 + 8 + 8 + 8 + 9 + 1 + 1 + 1,
+        seeds = [b"vesting", greylocker_state.next_vesting_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    #[account(
+        init_if_needed,
+        payer = governance_authority,
+        seeds = [b"vesting-escrow"],
+        bump,
+        token::mint = grey_mint,
+        token::authority = greylocker_state,
+    )]
+    pub vesting_escrow: Account<'info, TokenAccount>,
+    #[account(mut, address = greylocker_state.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(seeds = [b"governance-council"], bump = governance_council.bump)]
+    pub governance_council: Account<'info, GovernanceCouncil>,
+    #[account(mut, seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        mut,
+        seeds = [b"vesting", vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = beneficiary
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    #[account(mut, seeds = [b"vesting-escrow"], bump)]
+    pub vesting_escrow: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = greylocker_state.grey_mint)]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+    pub beneficiary: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32], num_leaves: u32)]
+pub struct CreateAirdrop<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = governance_authority,
+        space = 8 + 32 + 32 + 8 + 8 + 4 + 4 + 8 + 1 + 1 + 4 + (num_leaves as usize).div_ceil(8),
+        seeds = [b"distributor", merkle_root.as_ref()],
+        bump
+    )]
+    pub distributor: Account<'info, MerkleDistributor>,
+    #[account(
+        init,
+        payer = governance_authority,
+        seeds = [b"airdrop-vault", distributor.key().as_ref()],
+        bump,
+        token::mint = grey_mint,
+        token::authority = distributor,
+    )]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = greylocker_state.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(seeds = [b"governance-council"], bump = governance_council.bump)]
+    pub governance_council: Account<'info, GovernanceCouncil>,
+    #[account(mut, seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"distributor", distributor.merkle_root.as_ref()], bump = distributor.bump)]
+    pub distributor: Account<'info, MerkleDistributor>,
+    #[account(mut, address = distributor.vault)]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = greylocker_state.grey_mint)]
+    pub claimant_token_account: Account<'info, TokenAccount>,
+    pub claimant: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimAirdropAndStake<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, seeds = [b"distributor", distributor.merkle_root.as_ref()], bump = distributor.bump)]
+    pub distributor: Account<'info, MerkleDistributor>,
+    #[account(mut, address = distributor.vault)]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = claimant,
+        space = 8 + 32 + 8 + 4 + 1,
+        seeds = [b"stake-registry", claimant.key().as_ref()],
+        bump
+    )]
+    pub stake_registry: Account<'info, StakeRegistry>,
+    #[account(
+        init,
+        payer = claimant,
+        space = StakeAccount::LEN,
+        seeds = [b"stake-account", claimant.key().as_ref(), stake_registry.next_position_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = claimant,
+        seeds = [b"stake-vault", stake_account.key().as_ref()],
+        bump,
+        token::mint = grey_mint,
+        token::authority = stake_account,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClawbackAirdrop<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"distributor", distributor.merkle_root.as_ref()], bump = distributor.bump)]
+    pub distributor: Account<'info, MerkleDistributor>,
+    #[account(mut, address = distributor.vault)]
+    pub airdrop_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = greylocker_state.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRewardReserve<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = governance_authority,
+        space = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 2 + 1 + 8 + 8 + 8 + 1,
+        seeds = [b"reward-reserve"],
+        bump
+    )]
+    pub reward_reserve: Account<'info, RewardReserve>,
+    #[account(
+        init,
+        payer = governance_authority,
+        seeds = [b"reward-reserve-vault"],
+        bump,
+        token::mint = grey_mint,
+        token::authority = greylocker_state,
+    )]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct FundRewardReserve<'info> {
+    #[account(mut, seeds = [b"reward-reserve"], bump = reward_reserve.bump)]
+    pub reward_reserve: Account<'info, RewardReserve>,
+    #[account(mut, address = reward_reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = reserve_vault.mint)]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    pub funder: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterService<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32 + 32 + ServiceInfo::LEN + 8 + 8 + 8 + 1 + 4 + 8 + 1 + 4 + 8 + 4 + 8 + 4 + 8 + 1,
+        seeds = [b"service-provider", user.key().as_ref()],
+        bump
+    )]
+    pub service_provider: Account<'info, ServiceProvider>,
+    #[account(
+        seeds = [b"stake-account", user.key().as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ReinstateService<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"service-provider", owner.key().as_ref()], bump = service_provider.bump)]
+    pub service_provider: Account<'info, ServiceProvider>,
+    #[account(
+        seeds = [b"stake-account", owner.key().as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordAccessExpiry<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"access-record",
+            service_provider.key().as_ref(),
+            access_record.user.as_ref(),
+            access_record.grant_id.to_le_bytes().as_ref()
+        ],
+        bump = access_record.bump
+    )]
+    pub access_record: Account<'info, AccessRecord>,
+    #[account(mut, seeds = [b"service-provider", service_provider.owner.as_ref()], bump = service_provider.bump)]
+    pub service_provider: Account<'info, ServiceProvider>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshServiceReputation<'info> {
+    #[account(mut, seeds = [b"service-provider", service_provider.owner.as_ref()], bump = service_provider.bump)]
+    pub service_provider: Account<'info, ServiceProvider>,
+}
+
+#[derive(Accounts)]
+pub struct PayAccessFee<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, address = greylocker_state.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"service-provider", service_owner.key().as_ref()], bump = service_provider.bump)]
+    pub service_provider: Account<'info, ServiceProvider>,
+    #[account(
+        init,
+        payer = service_owner,
+        space = 8 + 32 + 32 + 1 + 4 + MAX_CUSTOM_DATA_TYPE_LEN + 8 + 8 + 8 + 32 + 8 + 1 + 1,
+        seeds = [
+            b"access-record",
+            service_provider.key().as_ref(),
+            user.key().as_ref(),
+            service_provider.access_grants_issued.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub access_record: Account<'info, AccessRecord>,
+    #[account(mut, token::mint = grey_mint)]
+    pub service_token_account: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub service_owner: Signer<'info>,
+    /// CHECK: the user the access record is for; only its key is used, to seed the record
+    pub user: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SettleVaultAccess<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, address = greylocker_state.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"service-provider", service_owner.key().as_ref()],
+        bump = service_provider.bump,
+        constraint = service_provider.owner == service_owner.key() @ GreylockerError::NotAuthorized
+    )]
+    pub service_provider: Account<'info, ServiceProvider>,
+    #[account(
+        seeds = [b"stake-account", service_owner.key().as_ref(), service_stake_account.position_id.to_le_bytes().as_ref()],
+        bump = service_stake_account.bump,
+        constraint = service_stake_account.owner == service_owner.key() @ GreylockerError::NotAuthorized
+    )]
+    pub service_stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = service_owner,
+        space = 8 + 32 + 32 + 1 + 4 + MAX_CUSTOM_DATA_TYPE_LEN + 8 + 8 + 8 + 32 + 8 + 1 + 1,
+        seeds = [
+            b"access-record",
+            service_provider.key().as_ref(),
+            user.key().as_ref(),
+            service_provider.access_grants_issued.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub access_record: Account<'info, AccessRecord>,
+    #[account(mut, token::mint = grey_mint, token::authority = service_owner)]
+    pub service_token_account: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub service_owner: Signer<'info>,
+    /// The vault owner receiving the fee; must sign so grants cannot be settled behind their back.
+    pub user: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CreateDispute<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        init,
+        payer = reporter,
+        space = 8 + 32 + 32 + 32 + 32 + (4 + MAX_EVIDENCE_LEN) * 2 + 8 + 1 + 2 + 9 + 1 + 1 + 1 + 1 + 1 + 1 + 8 + 32 + 8 + 1 + 1 + 8 + 33 + 4 + 32 * APPEAL_JURY_SIZE as usize + 8 + 1,
+        seeds = [b"dispute", reporter.key().as_ref(), service_provider.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        init_if_needed,
+        payer = reporter,
+        space = 8 + 32 + 8 + 4 + 4 + 8 + 8 + 1,
+        seeds = [b"reporter", reporter.key().as_ref()],
+        bump
+    )]
+    pub reporter_record: Account<'info, ReporterRecord>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = reporter,
+        seeds = [b"reporter-bond", dispute.key().as_ref()],
+        bump,
+        token::mint = grey_mint,
+        token::authority = dispute,
+    )]
+    pub reporter_bond_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint)]
+    pub reporter_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"service-provider", service_provider.owner.as_ref()], bump = service_provider.bump)]
+    pub service_provider: Account<'info, ServiceProvider>,
+    // Only present when the dispute concerns one of the provider's data pools
+    #[account(mut)]
+    pub data_pool: Option<Account<'info, DataPool>>,
+    #[account(mut)]
+    pub reporter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RespondToDispute<'info> {
+    #[account(mut, seeds = [b"dispute", dispute.reporter.as_ref(), dispute.reported_service.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(constraint = owner.key() == dispute.accused @ GreylockerError::NotAuthorized)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterJuror<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        init_if_needed,
+        payer = juror,
+        space = 8 + 4 + MAX_REGISTERED_JURORS * JurorEntry::LEN + 8 + 1,
+        seeds = [b"juror-registry"],
+        bump
+    )]
+    pub juror_registry: Account<'info, JurorRegistry>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", juror.key().as_ref(), juror_stake_account.position_id.to_le_bytes().as_ref()],
+        bump = juror_stake_account.bump
+    )]
+    pub juror_stake_account: Account<'info, StakeAccount>,
+    #[account(mut)]
+    pub juror: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeregisterJuror<'info> {
+    #[account(mut, seeds = [b"juror-registry"], bump = juror_registry.bump)]
+    pub juror_registry: Account<'info, JurorRegistry>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", juror.key().as_ref(), juror_stake_account.position_id.to_le_bytes().as_ref()],
+        bump = juror_stake_account.bump
+    )]
+    pub juror_stake_account: Account<'info, StakeAccount>,
+    pub juror: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DrawJury<'info> {
+    #[account(mut, seeds = [b"dispute", dispute.reporter.as_ref(), dispute.reported_service.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(seeds = [b"juror-registry"], bump = juror_registry.bump)]
+    pub juror_registry: Account<'info, JurorRegistry>,
+    /// CHECK: Read raw; the address pins it to the SlotHashes sysvar
+    #[account(address = solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct JoinJury<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"dispute", dispute.reporter.as_ref(), dispute.reported_service.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", juror.key().as_ref(), juror_stake_account.position_id.to_le_bytes().as_ref()],
+        bump = juror_stake_account.bump
+    )]
+    pub juror_stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = juror,
+        space = 8 + 32 + 32 + 32 + 1 + 2 + 1,
+        seeds = [b"juror", dispute.key().as_ref(), juror.key().as_ref(), dispute.round.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub juror_record: Account<'info, JurorRecord>,
+    #[account(mut)]
+    pub juror: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastJuryVote<'info> {
+    #[account(mut, seeds = [b"dispute", dispute.reporter.as_ref(), dispute.reported_service.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        mut,
+        seeds = [b"juror", dispute.key().as_ref(), juror.key().as_ref(), dispute.round.to_le_bytes().as_ref()],
+        bump = juror_record.bump
+    )]
+    pub juror_record: Account<'info, JurorRecord>,
+    pub juror: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseJuror<'info> {
+    #[account(seeds = [b"dispute", dispute.reporter.as_ref(), dispute.reported_service.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        mut,
+        close = juror,
+        seeds = [b"juror", dispute.key().as_ref(), juror_record.juror.as_ref(), juror_record.round.to_le_bytes().as_ref()],
+        bump = juror_record.bump
+    )]
+    pub juror_record: Account<'info, JurorRecord>,
+    #[account(mut, address = juror_record.stake_account)]
+    pub juror_stake_account: Account<'info, StakeAccount>,
+    /// CHECK: Rent refund destination; pinned to the seated juror
+    #[account(mut, address = juror_record.juror)]
+    pub juror: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AdvanceDispute<'info> {
+    #[account(mut, seeds = [b"dispute", dispute.reporter.as_ref(), dispute.reported_service.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+}
+
+#[derive(Accounts)]
+pub struct AppealDispute<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, seeds = [b"dispute", dispute.reporter.as_ref(), dispute.reported_service.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(
+        init_if_needed,
+        payer = appellant,
+        seeds = [b"dispute-bond", dispute.key().as_ref()],
+        bump,
+        token::mint = grey_mint,
+        token::authority = dispute,
+    )]
+    pub bond_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint)]
+    pub appellant_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub appellant: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct PostAppealBond<'info> {
+    #[account(mut, seeds = [b"dispute", dispute.reporter.as_ref(), dispute.reported_service.as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut, seeds = [b"dispute-bond", dispute.key().as_ref()], bump)]
+    pub bond_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = bond_vault.mint)]
+    pub respondent_token_account: Account<'info, TokenAccount>,
+    pub respondent: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDisputeRuling<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, seeds = [b"dispute", dispute.reporter.as_ref(), service_provider.key().as_ref()], bump = dispute.bump)]
+    pub dispute: Account<'info, Dispute>,
+    #[account(mut, seeds = [b"service-provider", service_provider.owner.as_ref()], bump = service_provider.bump)]
+    pub service_provider: Account<'info, ServiceProvider>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", service_provider.owner.as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.key() == service_provider.stake_account
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"stake-vault", stake_account.key().as_ref()], bump, token::mint = grey_mint, token::authority = stake_account)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint, constraint = reporter_token_account.owner == dispute.reporter)]
+    pub reporter_token_account: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint, constraint = accused_token_account.owner == dispute.accused)]
+    pub accused_token_account: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"reporter-bond", dispute.key().as_ref()], bump)]
+    pub reporter_bond_vault: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"reporter", dispute.reporter.as_ref()], bump = reporter_record.bump)]
+    pub reporter_record: Account<'info, ReporterRecord>,
+    // Only present when the dispute went to appeal
+    #[account(mut)]
+    pub bond_vault: Option<Account<'info, TokenAccount>>,
+    // Only present when a delegation pool backs the accused's stake
+    #[account(mut)]
+    pub delegation_pool: Option<Account<'info, DelegationPool>>,
+    #[account(mut)]
+    pub delegation_vault: Option<Account<'info, TokenAccount>>,
+    // Only present when the dispute was filed through a provider data pool
+    #[account(mut)]
+    pub data_pool: Option<Account<'info, DataPool>>,
+    pub executor: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreateDataPool<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        init,
+        payer = governance_authority,
+        space = DataPool::LEN,
+        seeds = [b"data-pool", governance_authority.key().as_ref(), pool_info.name.as_ref()],
+        bump
+    )]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(seeds = [b"governance-council"], bump = governance_council.bump)]
+    pub governance_council: Account<'info, GovernanceCouncil>,
+    #[account(mut, seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct JoinDataPool<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 32 + 8 + 8 + 1,
+        seeds = [b"participant", user.key().as_ref(), data_pool.key().as_ref()],
+        bump
+    )]
+    pub participant: Account<'info, PoolParticipant>,
+    #[account(mut, seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()], bump = data_pool.bump)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: only its key is used, as the data pool's PDA seed
+    pub data_pool_creator: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct PayDataReward<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, address = greylocker_state.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()], bump = data_pool.bump)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(mut, seeds = [b"participant", user.key().as_ref(), data_pool.key().as_ref()], bump = participant.bump)]
+    pub participant: Account<'info, PoolParticipant>,
+    #[account(
+        mut,
+        seeds = [b"contribution", participant.key().as_ref(), contribution.epoch.to_le_bytes().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(mut, seeds = [b"reward-reserve"], bump = reward_reserve.bump)]
+    pub reward_reserve: Account<'info, RewardReserve>,
+    #[account(mut, address = reward_reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    // Only present when the pool has a funded budget
+    #[account(mut)]
+    pub budget_vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = grey_mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    /// CHECK: the contributor; only its key is used, to seed its participant record
+    #[account(mut)]
+    pub user: AccountInfo<'info>,
+    /// CHECK: only its key is used, as the data pool's PDA seed
+    pub data_pool_creator: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(pool_info: DataPoolInfo)]
+pub struct CreateProviderDataPool<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = service_owner,
+        space = DataPool::LEN,
+        seeds = [b"data-pool", service_owner.key().as_ref(), pool_info.name.as_ref()],
+        bump
+    )]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(
+        init,
+        payer = service_owner,
+        seeds = [b"data-pool-vault", data_pool.key().as_ref()],
+        bump,
+        token::mint = grey_mint,
+        token::authority = data_pool
+    )]
+    pub budget_vault: Account<'info, TokenAccount>,
+    #[account(seeds = [b"service-provider", service_owner.key().as_ref()], bump = service_provider.bump)]
+    pub service_provider: Account<'info, ServiceProvider>,
+    #[account(constraint = service_stake_account.key() == service_provider.stake_account @ GreylockerError::NotAuthorized)]
+    pub service_stake_account: Account<'info, StakeAccount>,
+    #[account(mut, token::mint = grey_mint, token::authority = service_owner)]
+    pub service_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub service_owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDataPool<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()], bump = data_pool.bump)]
+    pub data_pool: Account<'info, DataPool>,
+    pub authority: Signer<'info>,
+    /// CHECK: only its key is used, as the data pool's PDA seed
+    pub data_pool_creator: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundDataPool<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()], bump = data_pool.bump)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(
+        init_if_needed,
+        payer = funder,
+        seeds = [b"data-pool-vault", data_pool.key().as_ref()],
+        bump,
+        token::mint = grey_mint,
+        token::authority = data_pool
+    )]
+    pub budget_vault: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint, token::authority = funder)]
+    pub funder_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    /// CHECK: only its key is used, as the data pool's PDA seed
+    pub data_pool_creator: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDataPool<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        mut,
+        seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()],
+        bump = data_pool.bump,
+        close = data_pool_creator
+    )]
+    pub data_pool: Account<'info, DataPool>,
+    // Only present when the pool was ever funded
+    #[account(mut)]
+    pub budget_vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = greylocker_state.grey_mint)]
+    pub refund_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+    /// CHECK: the data pool's PDA seed; it paid the pool's rent, so gets it back
+    #[account(mut)]
+    pub data_pool_creator: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct SubmitContribution<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()], bump = data_pool.bump)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(
+        seeds = [b"participant", user.key().as_ref(), data_pool.key().as_ref()],
+        bump = participant.bump,
+        constraint = participant.pool_id == data_pool.key() @ GreylockerError::UserNotInDataPool
+    )]
+    pub participant: Account<'info, PoolParticipant>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32 + 32 + 32 + 8 + 32 + 8 + 8 + 2 + 2 + 1 + 8 + 8 + 1 + 1,
+        seeds = [b"contribution", participant.key().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: only its key is used, as the data pool's PDA seed
+    pub data_pool_creator: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AttestContribution<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        mut,
+        seeds = [b"contribution", contribution.participant.as_ref(), contribution.epoch.to_le_bytes().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(address = contribution.pool)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(
+        mut,
+        seeds = [b"stake-account", validator.key().as_ref(), validator_stake_account.position_id.to_le_bytes().as_ref()],
+        bump = validator_stake_account.bump
+    )]
+    pub validator_stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = validator,
+        space = 8 + 32 + 32 + 32 + 1 + 1 + 1,
+        seeds = [b"attestation", contribution.key().as_ref(), validator.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, ContributionAttestation>,
+    #[account(mut)]
+    pub validator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseAttestation<'info> {
+    #[account(
+        seeds = [b"contribution", contribution.participant.as_ref(), contribution.epoch.to_le_bytes().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(
+        mut,
+        close = validator,
+        seeds = [b"attestation", contribution.key().as_ref(), attestation.validator.as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, ContributionAttestation>,
+    #[account(mut, address = attestation.stake_account)]
+    pub stake_account: Account<'info, StakeAccount>,
+    /// CHECK: Rent refund destination; pinned to the attesting validator
+    #[account(mut, address = attestation.validator)]
+    pub validator: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeContribution<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, address = contribution.pool)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(
+        mut,
+        seeds = [b"contribution", contribution.participant.as_ref(), contribution.epoch.to_le_bytes().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+}
+
+#[derive(Accounts)]
+pub struct InvalidateContribution<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, address = contribution.pool)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(
+        mut,
+        seeds = [b"contribution", contribution.participant.as_ref(), contribution.epoch.to_le_bytes().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    pub governance_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SlashAttester<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"contribution", contribution.participant.as_ref(), contribution.epoch.to_le_bytes().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    #[account(
+        mut,
+        seeds = [b"attestation", contribution.key().as_ref(), attestation.validator.as_ref()],
+        bump = attestation.bump
+    )]
+    pub attestation: Account<'info, ContributionAttestation>,
+    #[account(mut, address = attestation.stake_account)]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut, seeds = [b"stake-vault", stake_account.key().as_ref()], bump, token::mint = grey_mint, token::authority = stake_account)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RecordQuery<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, address = greylocker_state.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()], bump = data_pool.bump)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(mut, seeds = [b"participant", user.key().as_ref(), data_pool.key().as_ref()], bump = participant.bump)]
+    pub participant: Account<'info, PoolParticipant>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [b"privacy-ledger", data_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub privacy_ledger: Account<'info, PrivacyLedger>,
+    #[account(mut, token::mint = grey_mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = grey_mint, token::authority = authority)]
+    pub operator_token_account: Account<'info, TokenAccount>,
+    /// CHECK: the participant being queried; only its key is used, to seed its records
+    pub user: AccountInfo<'info>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// CHECK: only its key is used, as the data pool's PDA seed
+    pub data_pool_creator: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveDataPool<'info> {
+    #[account(mut, seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()], bump = data_pool.bump)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(
+        mut,
+        seeds = [b"participant", user.key().as_ref(), data_pool.key().as_ref()],
+        bump = participant.bump,
+        close = user
+    )]
+    pub participant: Account<'info, PoolParticipant>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [b"pool-settlement", data_pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub pool_settlement: Account<'info, PoolSettlement>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: only its key is used, as the data pool's PDA seed
+    pub data_pool_creator: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleContribution<'info> {
+    #[account(mut, address = contribution.pool)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(
+        mut,
+        seeds = [b"contribution", contribution.participant.as_ref(), contribution.epoch.to_le_bytes().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    /// CHECK: Closed when the contributor left; re-created if they rejoined
+    #[account(address = contribution.participant)]
+    pub participant: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + 32 + 32 + 8 + 1,
+        seeds = [b"pool-settlement", data_pool.key().as_ref(), contribution.contributor.as_ref()],
+        bump
+    )]
+    pub pool_settlement: Account<'info, PoolSettlement>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimPoolSettlement<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    #[account(mut, address = greylocker_state.treasury)]
+    pub treasury: Account<'info, TokenAccount>,
+    #[account(mut, seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()], bump = data_pool.bump)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(
+        mut,
+        seeds = [b"pool-settlement", data_pool.key().as_ref(), user.key().as_ref()],
+        bump = pool_settlement.bump
+    )]
+    pub pool_settlement: Account<'info, PoolSettlement>,
+    #[account(mut, seeds = [b"reward-reserve"], bump = reward_reserve.bump)]
+    pub reward_reserve: Account<'info, RewardReserve>,
+    #[account(mut, address = reward_reserve.vault)]
+    pub reserve_vault: Account<'info, TokenAccount>,
+    // Only present when the pool has a funded budget
+    #[account(mut)]
+    pub budget_vault: Option<Account<'info, TokenAccount>>,
+    #[account(mut, token::mint = grey_mint, token::authority = user)]
+    pub user_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: only its key is used, as the data pool's PDA seed
+    pub data_pool_creator: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ForfeitContribution<'info> {
+    #[account(mut, address = contribution.pool)]
+    pub data_pool: Account<'info, DataPool>,
+    #[account(
+        mut,
+        seeds = [b"contribution", contribution.participant.as_ref(), contribution.epoch.to_le_bytes().as_ref()],
+        bump = contribution.bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+    /// CHECK: Closed when the contributor left; re-created if they rejoined
+    #[account(address = contribution.participant)]
+    pub participant: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    pub pauser: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(
+        init,
+        payer = governance_authority,
+        space = 8 + 4 + 32 * MAX_COUNCIL_SIGNERS + 1 + 8 + 8 + 8 + 1,
+        seeds = [b"governance-council"],
+        bump
+    )]
+    pub governance_council: Account<'info, GovernanceCouncil>,
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut, seeds = [b"governance-council"], bump = governance_council.bump)]
+    pub governance_council: Account<'info, GovernanceCouncil>,
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::SPACE,
+        seeds = [b"proposal", governance_council.next_proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    #[account(seeds = [b"governance-council"], bump = governance_council.bump)]
+    pub governance_council: Account<'info, GovernanceCouncil>,
+    #[account(mut, seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"governance-council"], bump = governance_council.bump)]
+    pub governance_council: Account<'info, GovernanceCouncil>,
+    #[account(mut, seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut, address = greylocker_state.grey_mint)]
+    pub grey_mint: Account<'info, Mint>,
+    // Only needed by Mint proposals
+    #[account(mut)]
+    pub recipient: Option<Account<'info, TokenAccount>>,
+    // Only needed by ConfigureRewardReserve proposals
+    #[account(mut, seeds = [b"reward-reserve"], bump = reward_reserve.bump)]
+    pub reward_reserve: Option<Account<'info, RewardReserve>>,
+    pub reserve_vault: Option<Account<'info, TokenAccount>>,
+    // Only needed by RevokeVesting proposals
+    #[account(mut)]
+    pub vesting_schedule: Option<Account<'info, VestingSchedule>>,
+    #[account(mut)]
+    pub vesting_escrow: Option<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub treasury: Option<Account<'info, TokenAccount>>,
+    pub executor: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTokenProposal<'info> {
+    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
+    pub greylocker_state: Account<'info, GreylockerState>,
+    #[account(mut, seeds = [b"governance-council"], bump = governance_council.bump)]
+    pub governance_council: Account<'info, GovernanceCouncil>,
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::SPACE,
+        seeds = [b"proposal", governance_council.next_proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [b"stake-account", proposer.key().as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    #[account(mut, seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        seeds = [b"stake-account", voter.key().as_ref(), stake_account.position_id.to_le_bytes().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + 32 + 32 + 32 + 1 + 8 + 1,
+        seeds = [b"vote-record", proposal.key().as_ref(), stake_account.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeVote<'info> {
+    #[account(seeds = [b"governance-council"], bump = governance_council.bump)]
+    pub governance_council: Account<'info, GovernanceCouncil>,
+    #[account(mut, seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(seeds = [b"governance-council"], bump = governance_council.bump)]
+    pub governance_council: Account<'info, GovernanceCouncil>,
+    #[account(mut, seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()], bump = proposal.bump)]
+    pub proposal: Account<'info, Proposal>,
+    pub canceller: Signer<'info>,
+}
+
+// Error codes
+#[error_code]
+pub enum GreylockerError {
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Program is already paused")]
+    AlreadyPaused,
+    #[msg("Program is not paused")]
+    NotPaused,
+    #[msg("Not authorized")]
+    NotAuthorized,
+    #[msg("Arithmetic overflow or underflow")]
+    ArithmeticError,
+    #[msg("Insufficient stake amount")]
+    InsufficientStakeAmount,
+    #[msg("Invalid stake type")]
+    InvalidStakeType,
+    #[msg("Dispute already resolved")]
+    DisputeAlreadyResolved,
+    #[msg("User not in data pool")]
+    UserNotInDataPool,
+    #[msg("Data pool is inactive")]
+    DataPoolInactive,
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+    #[msg("Maximum supply reached")]
+    MaxSupplyReached,
+    #[msg("Invalid parameter value")]
+    InvalidParameterValue,
+    #[msg("Reward reserve or epoch emission budget exhausted")]
+    RewardReserveDepleted,
+    #[msg("Signer is not on the governance council")]
+    NotCouncilSigner,
+    #[msg("Invalid council signer set, threshold or timelock")]
+    InvalidCouncilConfig,
+    #[msg("Signer already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Proposal is not pending")]
+    ProposalNotPending,
+    #[msg("Proposal is not queued for execution")]
+    ProposalNotQueued,
+    #[msg("Proposal timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Proposal was created under a previous council")]
+    StaleProposal,
+    #[msg("Account required by this proposal is missing or wrong")]
+    MissingProposalAccount,
+    #[msg("Instruction does not apply to this kind of proposal")]
+    WrongProposalKind,
+    #[msg("Voting on this proposal has closed")]
+    VotingClosed,
+    #[msg("Voting on this proposal is still open")]
+    VotingStillOpen,
+    #[msg("Stake grew after the proposal opened and cannot vote on it")]
+    StakeNotEligible,
+    #[msg("Evidence URI is too long")]
+    EvidenceTooLong,
+    #[msg("Action not allowed in the dispute's current phase")]
+    InvalidDisputePhase,
+    #[msg("Dispute phase has not ended yet")]
+    DisputePhaseNotOver,
+    #[msg("Accused has already responded")]
+    AlreadyResponded,
+    #[msg("Jury is already full")]
+    JuryFull,
+    #[msg("Parties to a dispute cannot sit on its jury")]
+    ConflictedJuror,
+    #[msg("Juror has already voted")]
+    AlreadyVoted,
+    #[msg("Appeal window has closed")]
+    AppealWindowClosed,
+    #[msg("Appeal bond vault must be supplied")]
+    MissingBondVault,
+    #[msg("Reporter has filed too many disputes recently")]
+    ReporterRateLimited,
+    #[msg("Reporter has too many open disputes")]
+    TooManyOpenDisputes,
+    #[msg("Service provider is frozen until its stake is restored")]
+    ServiceFrozen,
+    #[msg("Service provider reputation is below the required minimum")]
+    ReputationTooLow,
+    #[msg("Access has not expired or was already recorded")]
+    AccessNotExpired,
+    #[msg("Unbonding cooldown has not finished")]
+    UnbondingNotComplete,
+    #[msg("An open dispute against this owner blocks withdrawal")]
+    OpenDisputeBlocksWithdrawal,
+    #[msg("Position has an unbonding request in progress")]
+    UnbondingInProgress,
+    #[msg("Position backs a delegation pool")]
+    DelegationPoolOpen,
+    #[msg("Delegation pool and vault must be supplied")]
+    MissingDelegationPool,
+    #[msg("Not enough delegation shares")]
+    InsufficientDelegationShares,
+    #[msg("Lock period is negative or exceeds the maximum")]
+    InvalidLockPeriod,
+    #[msg("New lock must end later and never lower the tier")]
+    LockNotExtended,
+    #[msg("Position has not opted into auto-compounding")]
+    AutoCompoundDisabled,
+    #[msg("Vesting schedule timestamps, amount or interval are invalid")]
+    InvalidVestingSchedule,
+    #[msg("Nothing has vested yet")]
+    NothingVested,
+    #[msg("Vesting schedule is not revocable or was already revoked")]
+    VestingNotRevocable,
+    #[msg("Airdrop parameters or claim index are invalid")]
+    InvalidAirdrop,
+    #[msg("Merkle proof does not match the airdrop root")]
+    InvalidMerkleProof,
+    #[msg("Airdrop allocation has already been claimed")]
+    AirdropAlreadyClaimed,
+    #[msg("Airdrop has expired")]
+    AirdropExpired,
+    #[msg("Airdrop has not expired yet")]
+    AirdropNotExpired,
+    #[msg("Spend would exceed the treasury's epoch cap")]
+    TreasuryCapExceeded,
+    #[msg("Contribution is malformed or not for the current epoch")]
+    InvalidContribution,
+    #[msg("Contribution is not in the right phase for this action")]
+    InvalidContributionPhase,
+    #[msg("Validators cannot attest to their own contributions")]
+    ConflictedValidator,
+    #[msg("Attestation is not slashable")]
+    AttesterNotSlashable,
+    #[msg("Data pool configuration is invalid")]
+    InvalidDataPoolConfig,
+    #[msg("Data pool has reached its participant cap")]
+    DataPoolFull,
+    #[msg("Data pool is still active or has contributions in flight")]
+    DataPoolStillActive,
+    #[msg("Funded data pool requires its budget vault")]
+    MissingBudgetVault,
+    #[msg("Data pool budget cannot cover this contribution")]
+    DataPoolBudgetExhausted,
+    #[msg("Dispute was filed through a data pool that was not provided")]
+    MissingDataPool,
+    #[msg("Joining a data pool requires a consent record")]
+    ConsentRequired,
+    #[msg("Query epsilon must be positive and within the pool's per-query cap")]
+    InvalidQueryEpsilon,
+    #[msg("Participant's privacy budget is exhausted")]
+    PrivacyBudgetExhausted,
+    #[msg("A service provider cannot grant access to itself")]
+    SelfDealing,
+    #[msg("Stake position is already in the juror registry")]
+    JurorAlreadyRegistered,
+    #[msg("Stake position is not in the juror registry")]
+    JurorNotRegistered,
+    #[msg("Juror registry is full")]
+    JurorRegistryFull,
+    #[msg("No eligible jurors are registered")]
+    JurorRegistryEmpty,
+    #[msg("Jury has already been drawn for this round")]
+    JuryAlreadyDrawn,
+    #[msg("Jury draw seed slot has not passed yet")]
+    JuryDrawNotReady,
+    #[msg("Juror was not drawn for this dispute")]
+    JurorNotDrawn,
+    #[msg("Stake is locked by the juror registry or an unresolved jury seat")]
+    JurorStakeLocked,
+    #[msg("Delegation pool has outstanding shares but no GREY backing them")]
+    DelegationPoolWiped,
+    #[msg("Operator self-bond would fall below the stake minimum")]
+    SelfBondTooLow,
+    #[msg("Stake backs attestations that can still be invalidated")]
+    AttestationStakeLocked,
+    #[msg("Validator stake is too new to attest to this contribution")]
+    AttesterStakeTooNew,
+    #[msg("Data pool still owes participants deferred rewards")]
+    DataPoolRewardsOwed,
+    #[msg("Stake backs a registered service and can't be closed or merged away")]
+    ServiceStakeLinked,
+    #[msg("Stake backs a service with open disputes")]
+    ServiceUnderDispute,
+    #[msg("Proposal does not authorize this action")]
+    ProposalActionMismatch,
+    #[msg("Proposal is carried out by its own instruction")]
+    ProposalRunsInline,
+}
+
+// Events
+#[event]
+pub struct InitializeEvent {
+    pub grey_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub initial_supply: u64,
+    pub max_supply: u64,
+    pub governance_authority: Pubkey,
+}
+
+#[event]
+pub struct TreasurySpendEvent {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub category: SpendCategory,
+    pub spent_this_epoch: u64,
+    pub epoch_started_at: i64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct MigrateTreasuryEvent {
+    pub legacy_treasury: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+    pub authority: Pubkey,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
-pub enum DisputeStatus {
-    Pending,
-    Resolved,
+#[event]
+pub struct StakeEvent {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub position_id: u64,
+    pub stake_type: StakeType,
+    pub amount: u64,
+    pub lock_until: i64,
+    pub lock_tier: u8,
+    pub reward_multiplier_bps: u16,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
-pub enum DisputeResolution {
-    Upheld,
-    Dismissed,
+#[event]
+pub struct TopUpStakeEvent {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub amount: u64,
+    pub new_amount: u64,
+    pub lock_until: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
-pub enum DataType {
-    Browsing,
-    Purchase,
-    Location,
-    Custom(String),
+#[event]
+pub struct ExtendLockEvent {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub lock_until: i64,
+    pub lock_tier: u8,
+    pub reward_multiplier_bps: u16,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
-pub enum GovernanceParameter {
-    SecurityStakeMinimum,
-    ServiceStakeMinimum,
-    DataValidatorStakeMinimum,
-    LiquidityStakeMinimum,
-    SecurityRewardRate,
-    DataValidatorRewardRate,
-    LiquidityRewardRate,
-    SlashingPercentage,
-    EarlyUnstakePenalty,
-    RewardUpdateInterval,
+#[event]
+pub struct LockTiersUpdatedEvent {
+    pub tiers: Vec<LockTier>,
+    pub authority: Pubkey,
 }
 
-// Context structs
-#[derive(Accounts)]
-#[instruction(bump: u8)]
-pub struct Initialize<'info> {
-    #[account(
-        init,
-        payer = initializer,
-        space = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 32 + 32 + GovernanceParameters::LEN + 1,
-        seeds = [b"greylocker-state"],
-        bump
-    )]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(
-        init,
-        payer = initializer,
-        mint::decimals = 9,
-        mint::authority = greylocker_state,
-    )]
-    pub grey_mint: Account<'info, Mint>,
-    #[account(
-        init,
-        payer = initializer,
-        token::mint = grey_mint,
-        token::authority = initializer,
-    )]
-    pub treasury: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub initializer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct SplitStakeEvent {
+    pub user: Pubkey,
+    pub source_stake_account: Pubkey,
+    pub new_stake_account: Pubkey,
+    pub amount: u64,
 }
 
-#[derive(Accounts)]
-pub struct Stake<'info> {
-    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(mut)]
-    pub grey_mint: Account<'info, Mint>,
-    #[account(
-        init,
-        payer = user,
-        space = 8 + 32 + 1 + 8 + 8 + 8 + 8 + 8 + 1,
-        seeds = [b"stake-account", user.key().as_ref(), user_token_account.key().as_ref()],
-        bump
-    )]
-    pub stake_account: Account<'info, StakeAccount>,
-    #[account(
-        init,
-        payer = user,
-        token::mint = grey_mint,
-        token::authority = stake_account,
-    )]
-    pub stake_vault: Account<'info, TokenAccount>,
-    #[account(mut, token::mint = grey_mint)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct MergeStakesEvent {
+    pub user: Pubkey,
+    pub source_stake_account: Pubkey,
+    pub destination_stake_account: Pubkey,
+    pub merged_amount: u64,
+    pub new_amount: u64,
+}
+
+#[event]
+pub struct RequestUnstakeEvent {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub amount: u64,
+    pub penalty_amount: u64,
+    pub return_amount: u64,
+    pub early_unstake: bool,
+    pub available_at: i64,
+    pub request_id: u64,
+}
+
+#[event]
+pub struct WithdrawUnbondedEvent {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub request_id: u64,
+    pub requested_amount: u64,
+    pub withdrawn_amount: u64,
+    pub position_closed: bool,
+}
+
+#[event]
+pub struct SetAutoCompoundEvent {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct CompoundEvent {
+    pub stake_account: Pubkey,
+    pub cranker: Pubkey,
+    pub compounded_amount: u64,
+    pub tip_amount: u64,
+    pub deferred_amount: u64,
+    pub forfeited_amount: u64,
+    pub new_amount: u64,
+}
+
+#[event]
+pub struct CreateAirdropEvent {
+    pub distributor: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub num_leaves: u32,
+    pub total_amount: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct ClaimAirdropEvent {
+    pub distributor: Pubkey,
+    pub claimant: Pubkey,
+    pub index: u32,
+    pub amount: u64,
+    pub stake_account: Option<Pubkey>, // Set when the claim was staked on arrival
+}
+
+#[event]
+pub struct ClawbackAirdropEvent {
+    pub distributor: Pubkey,
+    pub amount: u64,
+    pub claimed_count: u32,
+}
+
+#[event]
+pub struct CreateVestingEvent {
+    pub vesting_schedule: Pubkey,
+    pub schedule_id: u64,
+    pub beneficiary: Pubkey,
+    pub total_amount: u64,
+    pub start_ts: i64,
+    pub cliff_ts: i64,
+    pub end_ts: i64,
+    pub kind: VestingKind,
+    pub revocable: bool,
+}
+
+#[event]
+pub struct ClaimVestedEvent {
+    pub vesting_schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct RevokeVestingEvent {
+    pub vesting_schedule: Pubkey,
+    pub beneficiary: Pubkey,
+    pub vested_amount: u64,
+    pub returned_amount: u64,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct InitializeLiquidStakingEvent {
+    pub liquid_staking_pool: Pubkey,
+    pub st_mint: Pubkey,
+    pub vault: Pubkey,
+}
+
+#[event]
+pub struct StakeLiquidEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub st_amount: u64,
+    pub total_backing: u64,
+}
+
+#[event]
+pub struct HarvestLiquidRewardsEvent {
+    pub rewards_amount: u64,
+    pub deferred_amount: u64,
+    pub forfeited_amount: u64,
+    pub total_backing: u64,
+    pub st_supply: u64,
+}
+
+#[event]
+pub struct UnwrapLiquidStakeEvent {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub st_amount: u64,
+    pub amount: u64,
+    pub owed_rewards: u64, // Share of unharvested rewards carried onto the new position
+}
+
+#[event]
+pub struct OpenDelegationPoolEvent {
+    pub delegation_pool: Pubkey,
+    pub operator: Pubkey,
+    pub operator_stake_account: Pubkey,
+    pub stake_type: StakeType,
+    pub commission_bps: u16,
+}
+
+#[event]
+pub struct DelegateEvent {
+    pub delegation_pool: Pubkey,
+    pub delegator: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub total_delegated: u64,
+}
+
+#[event]
+pub struct UndelegateEvent {
+    pub delegation_pool: Pubkey,
+    pub delegator: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+    pub available_at: i64,
+}
+
+#[event]
+pub struct WithdrawUndelegatedEvent {
+    pub delegation_pool: Pubkey,
+    pub delegator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimDelegationRewardsEvent {
+    pub delegation_pool: Pubkey,
+    pub recipient: Pubkey,
+    pub commission: bool, // True when the operator claimed their commission
+    pub rewards_amount: u64,
+    pub deferred_amount: u64,
+    pub forfeited_amount: u64,
+}
+
+#[event]
+pub struct DelegationSlashEvent {
+    pub delegation_pool: Pubkey,
+    pub dispute_id: Pubkey,
+    pub slashed_delegated: u64,
+    pub slashed_unbonding: u64,
+    pub burn_amount: u64,
+    pub reporter_amount: u64,
+}
+
+#[event]
+pub struct ClaimRewardsEvent {
+    pub user: Pubkey,
+    pub stake_account: Pubkey,
+    pub rewards_amount: u64,
+    pub deferred_amount: u64,
+    pub forfeited_amount: u64,
+}
+
+#[event]
+pub struct InitializeRewardReserveEvent {
+    pub reward_reserve: Pubkey,
+    pub vault: Pubkey,
+    pub epoch_emission_budget: u64,
+    pub epoch_duration: i64,
+    pub shortfall_mode: ReserveShortfallMode,
+}
+
+#[event]
+pub struct FundRewardReserveEvent {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub reserve_balance: u64,
+    pub coverage_bps: u16,
+}
+
+#[event]
+pub struct ConfigureRewardReserveEvent {
+    pub epoch_emission_budget: u64,
+    pub epoch_duration: i64,
+    pub shortfall_mode: ReserveShortfallMode,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct RegisterServiceEvent {
+    pub service_provider: Pubkey,
+    pub owner: Pubkey,
+    pub stake_account: Pubkey,
+    pub service_name: String,
+}
+
+#[event]
+pub struct PayAccessFeeEvent {
+    pub service_provider: Pubkey,
+    pub user: Pubkey,
+    pub gross_amount: u64,    // Paid by the service
+    pub net_amount: u64,      // Received by the user
+    pub burned_amount: u64,
+    pub treasury_amount: u64,
+    pub data_type: DataType,
+    pub expires_at: i64,
+    pub access_grant: Pubkey,
+}
+
+#[event]
+pub struct CreateDisputeEvent {
+    pub dispute_id: Pubkey,
+    pub reporter: Pubkey,
+    pub reported_service: Pubkey,
+    pub dispute_type: DisputeType,
+    pub reporter_bond: u64,
+    pub data_pool: Option<Pubkey>,
+}
+
+#[event]
+pub struct ReporterBondSettledEvent {
+    pub dispute_id: Pubkey,
+    pub reporter: Pubkey,
+    pub refunded: u64,
+    pub burned: u64,
+    pub paid_to_accused: u64,
+}
+
+#[event]
+pub struct DisputeResponseEvent {
+    pub dispute_id: Pubkey,
+    pub accused: Pubkey,
+    pub counter_evidence: String,
+}
+
+#[event]
+pub struct JurorSeatedEvent {
+    pub dispute_id: Pubkey,
+    pub juror: Pubkey,
+    pub round: u8,
+    pub jurors_seated: u8,
+}
+
+#[event]
+pub struct JurorRegistryEvent {
+    pub juror: Pubkey,
+    pub stake_account: Pubkey,
+    pub weight: u64,
+    pub registered: bool,
 }
 
-#[derive(Accounts)]
-pub struct Unstake<'info> {
-    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(mut)]
-    pub grey_mint: Account<'info, Mint>,
-    #[account(mut, seeds = [b"stake-account", user.key().as_ref(), user_token_account.key().as_ref()], bump = stake_account.bump)]
-    pub stake_account: Account<'info, StakeAccount>,
-    #[account(mut, token::mint = grey_mint, token::authority = stake_account)]
-    pub stake_vault: Account<'info, TokenAccount>,
-    #[account(mut, token::mint = grey_mint)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct JuryDrawnEvent {
+    pub dispute_id: Pubkey,
+    pub round: u8,
+    pub jurors: Vec<Pubkey>,
 }
 
-#[derive(Accounts)]
-pub struct ClaimRewards<'info> {
-    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(mut)]
-    pub grey_mint: Account<'info, Mint>,
-    #[account(mut, seeds = [b"stake-account", user.key().as_ref(), user_token_account.key().as_ref()], bump = stake_account.bump)]
-    pub stake_account: Account<'info, StakeAccount>,
-    #[account(mut, token::mint = grey_mint)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct JuryDrawRearmedEvent {
+    pub dispute_id: Pubkey,
+    pub round: u8,
+    pub draw_slot: u64,
 }
 
-#[derive(Accounts)]
-pub struct RegisterService<'info> {
-    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(
-        init,
-        payer = user,
-        space = 8 + 32 + 32 + ServiceInfo::LEN + 8 + 8 + 1 + 1,
-        seeds = [b"service-provider", user.key().as_ref()],
-        bump
-    )]
-    pub service_provider: Account<'info, ServiceProvider>,
-    #[account(seeds = [b"stake-account", user.key().as_ref(), stake_account.key().as_ref()], bump = stake_account.bump)]
-    pub stake_account: Account<'info, StakeAccount>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct AttestationReleasedEvent {
+    pub contribution: Pubkey,
+    pub validator: Pubkey,
+    pub stake_account: Pubkey,
 }
 
-#[derive(Accounts)]
-pub struct PayAccessFee<'info> {
-    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(mut)]
-    pub grey_mint: Account<'info, Mint>,
-    #[account(mut, seeds = [b"service-provider", service_owner.key().as_ref()], bump = service_provider.bump)]
-    pub service_provider: Account<'info, ServiceProvider>,
-    #[account(
-        init_if_needed,
-        payer = service_owner,
-        space = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 1,
-        seeds = [b"access-record", service_provider.key().as_ref(), user.key().as_ref()],
-        bump
-    )]
-    pub access_record: Account<'info, AccessRecord>,
-    #[account(mut, token::mint = grey_mint)]
-    pub service_token_account: Account<'info, TokenAccount>,
-    #[account(mut, token::mint = grey_mint)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub service_owner: Signer<'info>,
-    pub user: AccountInfo<'info>,
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct JurorReleasedEvent {
+    pub dispute_id: Pubkey,
+    pub juror: Pubkey,
+    pub round: u8,
 }
 
-#[derive(Accounts)]
-pub struct CreateDispute<'info> {
-    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(
-        init,
-        payer = reporter,
-        space = 8 + 32 + 32 + 32 + 200 + 8 + 1 + 1 + 8 + 1 + 1, // Adjust size based on evidence string
-        seeds = [b"dispute", reporter.key().as_ref(), service_provider.key().as_ref()],
-        bump
-    )]
-    pub dispute: Account<'info, Dispute>,
-    #[account(seeds = [b"service-provider", service_provider.owner.as_ref()], bump = service_provider.bump)]
-    pub service_provider: Account<'info, ServiceProvider>,
-    #[account(mut)]
-    pub reporter: Signer<'info>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct JuryVoteEvent {
+    pub dispute_id: Pubkey,
+    pub juror: Pubkey,
+    pub round: u8,
+    pub resolution: DisputeResolution,
 }
 
-#[derive(Accounts)]
-pub struct ResolveDispute<'info> {
-    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(mut)]
-    pub grey_mint: Account<'info, Mint>,
-    #[account(mut, seeds = [b"dispute", dispute.reporter.as_ref(), service_provider.key().as_ref()], bump = dispute.bump)]
-    pub dispute: Account<'info, Dispute>,
-    #[account(mut, seeds = [b"service-provider", service_provider.owner.as_ref()], bump = service_provider.bump)]
-    pub service_provider: Account<'info, ServiceProvider>,
-    #[account(mut, seeds = [b"stake-account", service_provider.owner.as_ref(), stake_account.key().as_ref()], bump = stake_account.bump, constraint = stake_account.owner == service_provider.owner)]
-    pub stake_account: Account<'info, StakeAccount>,
-    #[account(mut, token::mint = grey_mint, token::authority = stake_account)]
-    pub stake_vault: Account<'info, TokenAccount>,
-    #[account(mut, token::mint = grey_mint)]
-    pub reporter_token_account: Account<'info, TokenAccount>,
-    pub resolver: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct AdvanceDisputeEvent {
+    pub dispute_id: Pubkey,
+    pub status: DisputeStatus,
+    pub resolution: Option<DisputeResolution>,
+    pub phase_deadline: i64,
 }
 
-#[derive(Accounts)]
-pub struct CreateDataPool<'info> {
-    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(
-        init,
-        payer = governance_authority,
-        space = 8 + 32 + 50 + 50 + 8 + 200 + 8 + 8 + 8 + 1 + 1,
-        seeds = [b"data-pool", governance_authority.key().as_ref(), pool_info.name.as_ref()],
-        bump
-    )]
-    pub data_pool: Account<'info, DataPool>,
-    #[account(mut)]
-    pub governance_authority: Signer<'info>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct AppealDisputeEvent {
+    pub dispute_id: Pubkey,
+    pub appellant: Pubkey,
+    pub appeal_bond: u64,
+    pub match_deadline: i64,
 }
 
-#[derive(Accounts)]
-pub struct JoinDataPool<'info> {
-    #[account(seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(
-        init,
-        payer = user,
-        space = 8 + 32 + 32 + 8 + 8 + 8 + 1,
-        seeds = [b"participant", user.key().as_ref(), data_pool.key().as_ref()],
-        bump
-    )]
-    pub participant: Account<'info, PoolParticipant>,
-    #[account(mut, seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()], bump = data_pool.bump)]
-    pub data_pool: Account<'info, DataPool>,
-    #[account(mut)]
-    pub user: Signer<'info>,
-    pub data_pool_creator: AccountInfo<'info>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct AppealBondPostedEvent {
+    pub dispute_id: Pubkey,
+    pub respondent: Pubkey,
+    pub appeal_bond: u64,
+    pub round: u8,
 }
 
-#[derive(Accounts)]
-pub struct PayDataReward<'info> {
-    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(mut)]
-    pub grey_mint: Account<'info, Mint>,
-    #[account(mut, seeds = [b"data-pool", data_pool_creator.key().as_ref(), data_pool.name.as_ref()], bump = data_pool.bump)]
-    pub data_pool: Account<'info, DataPool>,
-    #[account(mut, seeds = [b"participant", user.key().as_ref(), data_pool.key().as_ref()], bump = participant.bump)]
-    pub participant: Account<'info, PoolParticipant>,
-    #[account(mut, token::mint = grey_mint)]
-    pub user_token_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub user: AccountInfo<'info>,
-    pub caller: Signer<'info>,
-    pub data_pool_creator: AccountInfo<'info>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct ResolveDisputeEvent {
+    pub dispute_id: Pubkey,
+    pub resolver: Pubkey,
+    pub resolution: DisputeResolution,
+    pub service_provider: Pubkey,
+    pub reputation_score: u8,
 }
 
-#[derive(Accounts)]
-pub struct UpdateGovernanceParameter<'info> {
-    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    pub governance_authority: Signer<'info>,
+#[event]
+pub struct SlashEvent {
+    pub stake_account: Pubkey,
+    pub dispute_id: Pubkey,
+    pub slash_amount: u64,
+    pub burn_amount: u64,
+    pub reporter_amount: u64,
+    pub slash_percentage: u8,
+    pub prior_offenses: u32,
 }
 
-#[derive(Accounts)]
-pub struct Pause<'info> {
-    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    pub pauser: Signer<'info>,
+#[event]
+pub struct ReputationChangedEvent {
+    pub service_provider: Pubkey,
+    pub old_score: u8,
+    pub new_score: u8,
+    pub reason: ReputationReason,
 }
 
-#[derive(Accounts)]
-pub struct Unpause<'info> {
-    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    pub pauser: Signer<'info>,
+#[event]
+pub struct ServiceFrozenEvent {
+    pub service_provider: Pubkey,
+    pub stake_account: Pubkey,
+    pub remaining_stake: u64,
 }
 
-#[derive(Accounts)]
-pub struct GovernanceMint<'info> {
-    #[account(mut, seeds = [b"greylocker-state"], bump = greylocker_state.bump)]
-    pub greylocker_state: Account<'info, GreylockerState>,
-    #[account(mut)]
-    pub grey_mint: Account<'info, Mint>,
-    #[account(mut, token::mint = grey_mint)]
-    pub recipient: Account<'info, TokenAccount>,
-    pub governance_authority: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct ReinstateServiceEvent {
+    pub service_provider: Pubkey,
+    pub stake_account: Pubkey,
+    pub stake_amount: u64,
 }
 
-// Error codes
-#[error_code]
-pub enum GreylockerError {
-    #[msg("Program is paused")]
-    ProgramPaused,
-    #[msg("Program is already paused")]
-    AlreadyPaused,
-    #[msg("Program is not paused")]
-    NotPaused,
-    #[msg("Not authorized")]
-    NotAuthorized,
-    #[msg("Arithmetic overflow or underflow")]
-    ArithmeticError,
-    #[msg("Insufficient stake amount")]
-    InsufficientStakeAmount,
-    #[msg("Invalid stake type")]
-    InvalidStakeType,
-    #[msg("Dispute already resolved")]
-    DisputeAlreadyResolved,
-    #[msg("User not in data pool")]
-    UserNotInDataPool,
-    #[msg("Data pool is inactive")]
-    DataPoolInactive,
-    #[msg("No rewards to claim")]
-    NoRewardsToClaim,
-    #[msg("Maximum supply reached")]
-    MaxSupplyReached,
-    #[msg("Invalid parameter value")]
-    InvalidParameterValue,
+#[event]
+pub struct CreateDataPoolEvent {
+    pub pool_id: Pubkey,
+    pub name: String,
+    pub data_type: String,
+    pub reward_rate: u64,
+    pub max_participants: u64,
+    pub expires_at: i64,
+    pub epsilon_budget: u64,
+    pub max_query_epsilon: u64,
+    pub service_provider: Pubkey, // Default for governance pools
+    pub initial_budget: u64,
+}
+
+#[event]
+pub struct UpdateDataPoolEvent {
+    pub pool_id: Pubkey,
+    pub reward_rate: u64,
+    pub max_participants: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct DataPoolStatusEvent {
+    pub pool_id: Pubkey,
+    pub active: bool,
+    pub paused: bool,
 }
 
-// Events
 #[event]
-pub struct InitializeEvent {
-    pub grey_mint: Pubkey,
-    pub treasury: Pubkey,
-    pub initial_supply: u64,
-    pub max_supply: u64,
-    pub governance_authority: Pubkey,
+pub struct FundDataPoolEvent {
+    pub pool_id: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub budget_remaining: u64,
 }
 
 #[event]
-pub struct StakeEvent {
-    pub user: Pubkey,
-    pub stake_account: Pubkey,
-    pub stake_type: StakeType,
-    pub amount: u64,
-    pub lock_until: i64,
+pub struct CloseDataPoolEvent {
+    pub pool_id: Pubkey,
+    pub refund_account: Pubkey, // Treasury, or the sponsoring provider's token account
+    pub refunded: u64,
 }
 
 #[event]
-pub struct UnstakeEvent {
+pub struct JoinDataPoolEvent {
     pub user: Pubkey,
-    pub stake_account: Pubkey,
-    pub amount: u64,
-    pub penalty_amount: u64,
-    pub return_amount: u64,
-    pub early_unstake: bool,
+    pub pool_id: Pubkey,
+    pub pool_name: String,
+    pub consent_hash: [u8; 32],
+    pub consented_at: i64,
 }
 
+// Pool operators must stop processing the user's data for this pool once this is emitted
 #[event]
-pub struct ClaimRewardsEvent {
+pub struct ConsentRevokedEvent {
     pub user: Pubkey,
-    pub stake_account: Pubkey,
-    pub rewards_amount: u64,
-    pub new_total_supply: u64,
+    pub pool_id: Pubkey,
+    pub service_provider: Pubkey, // Sponsoring provider; default for governance pools
+    pub consent_hash: [u8; 32],   // The consent being withdrawn
+    pub consented_at: i64,
+    pub revoked_at: i64,
+    pub rewards_settled: u64, // Owed rewards moved to the user's settlement record
 }
 
 #[event]
-pub struct RegisterServiceEvent {
-    pub service_provider: Pubkey,
-    pub owner: Pubkey,
-    pub stake_account: Pubkey,
-    pub service_name: String,
+pub struct SettleContributionEvent {
+    pub contribution: Pubkey,
+    pub pool_id: Pubkey,
+    pub contributor: Pubkey,
+    pub reward: u64,
+    pub owed_rewards: u64,
 }
 
 #[event]
-pub struct PayAccessFeeEvent {
-    pub service_provider: Pubkey,
+pub struct ClaimPoolSettlementEvent {
     pub user: Pubkey,
+    pub pool_id: Pubkey,
     pub amount: u64,
-    pub data_type: DataType,
-    pub expires_at: i64,
+    pub burned_amount: u64,
+    pub treasury_amount: u64,
+    pub deferred_amount: u64,
+    pub forfeited_amount: u64,
 }
 
 #[event]
-pub struct CreateDisputeEvent {
-    pub dispute_id: Pubkey,
-    pub reporter: Pubkey,
-    pub reported_service: Pubkey,
-    pub dispute_type: DisputeType,
+pub struct PrivacyBudgetConsumedEvent {
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub operator: Pubkey,
+    pub query_hash: [u8; 32], // Commitment to the query as run off-chain
+    pub epsilon: u64,         // All epsilons in thousandths
+    pub epsilon_spent: u64,
+    pub epsilon_budget: u64,
+    pub fee_paid: u64,        // Net to the participant
 }
 
 #[event]
-pub struct ResolveDisputeEvent {
-    pub dispute_id: Pubkey,
-    pub resolver: Pubkey,
-    pub resolution: DisputeResolution,
-    pub service_provider: Pubkey,
-    pub reputation_score: u8,
+pub struct ForfeitContributionEvent {
+    pub contribution: Pubkey,
+    pub pool_id: Pubkey,
+    pub contributor: Pubkey,
+    pub reward_released: u64,
 }
 
 #[event]
-pub struct SlashEvent {
-    pub stake_account: Pubkey,
-    pub dispute_id: Pubkey,
-    pub slash_amount: u64,
-    pub burn_amount: u64,
-    pub reporter_amount: u64,
+pub struct SubmitContributionEvent {
+    pub contribution: Pubkey,
+    pub pool_id: Pubkey,
+    pub contributor: Pubkey,
+    pub epoch: u64,
+    pub data_hash: [u8; 32],
+    pub data_points: u64,
 }
 
 #[event]
-pub struct CreateDataPoolEvent {
+pub struct AttestContributionEvent {
+    pub contribution: Pubkey,
+    pub validator: Pubkey,
+    pub approve: bool,
+    pub approvals: u16,
+    pub rejections: u16,
+}
+
+#[event]
+pub struct FinalizeContributionEvent {
+    pub contribution: Pubkey,
     pub pool_id: Pubkey,
-    pub name: String,
-    pub data_type: String,
-    pub reward_rate: u64,
+    pub accepted: bool,
+    pub approvals: u16,
+    pub rejections: u16,
+    pub reward: u64,
 }
 
 #[event]
-pub struct JoinDataPoolEvent {
-    pub user: Pubkey,
+pub struct InvalidateContributionEvent {
+    pub contribution: Pubkey,
     pub pool_id: Pubkey,
-    pub pool_name: String,
+    pub contributor: Pubkey,
+    pub approvals: u16, // Attestations now slashable
+}
+
+#[event]
+pub struct AttesterSlashEvent {
+    pub contribution: Pubkey,
+    pub validator: Pubkey,
+    pub stake_account: Pubkey,
+    pub slash_amount: u64,
+    pub slash_percentage: u8,
 }
 
 #[event]
 pub struct PayDataRewardEvent {
     pub user: Pubkey,
     pub pool_id: Pubkey,
-    pub amount: u64,
-    pub new_total_supply: u64,
+    pub contribution: Pubkey,
+    pub amount: u64,          // Received by the participant, after any protocol fee
+    pub burned_amount: u64,
+    pub treasury_amount: u64,
+    pub deferred_amount: u64,
+    pub forfeited_amount: u64,
 }
 
 #[event]
@@ -1293,6 +8383,66 @@ pub struct UpdateGovernanceParameterEvent {
     pub authority: Pubkey,
 }
 
+#[event]
+pub struct InitializeGovernanceEvent {
+    pub council: Pubkey,
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock: i64,
+}
+
+#[event]
+pub struct CreateProposalEvent {
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ApproveProposalEvent {
+    pub proposal: Pubkey,
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ExecuteProposalEvent {
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub action: ProposalAction,
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct CastVoteEvent {
+    pub proposal: Pubkey,
+    pub stake_account: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+}
+
+#[event]
+pub struct FinalizeVoteEvent {
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub participating_stake: u64,
+    pub passed: bool,
+    pub eta: i64,
+}
+
+#[event]
+pub struct CancelProposalEvent {
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub canceller: Pubkey,
+}
+
 #[event]
 pub struct PauseEvent {
     pub authority: Pubkey,
@@ -1311,10 +8461,455 @@ pub struct GovernanceMintEvent {
 }
 
 // Helper implementations
+impl GreylockerState {
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 32 + 32 + GovernanceParameters::LEN + RewardIndex::LEN + 4 + MAX_LOCK_TIERS * LockTier::LEN + 8 * 4 + 8 * 3 + 1;
+
+    // Count a treasury outflow against the epoch cap, rolling the epoch forward first
+    pub fn charge_treasury(&mut self, amount: u64, current_time: i64) -> Result<u64> {
+        let epoch_duration = self.governance_parameters.treasury_epoch_duration;
+        let elapsed = current_time.saturating_sub(self.treasury_epoch_started_at);
+        if elapsed >= epoch_duration {
+            self.treasury_epoch_started_at += (elapsed / epoch_duration) * epoch_duration;
+            self.treasury_spent_this_epoch = 0;
+        }
+        let spent = self.treasury_spent_this_epoch
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        require!(spent <= self.governance_parameters.treasury_epoch_cap, GreylockerError::TreasuryCapExceeded);
+        self.treasury_spent_this_epoch = spent;
+        self.total_treasury_spent = self.total_treasury_spent
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        Ok(spent)
+    }
+}
+
 impl GovernanceParameters {
-    pub const LEN: usize = 8 * 9 + 1; // 9 u64 fields + 1 u8 field
+    pub const LEN: usize = 8 * 9 + 1 + 2 + 2 + 8 + 8 + 8 + 3 + 2 + 8 + 2 + 8 + 8 + 2 * 3 + 1 + 1; // 9 u64 fields + 1 u8 field + voting quorum/threshold/period + appeal/reporter bonds + slashing table + unbonding period + compound tip + treasury cap/epoch + protocol fees + attestation slash + pool creator reputation
+}
+
+impl StakeAccount {
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 8 + 8 + 1 + 2 + 8 + 8 + 16 + 8 + 8 + 8 + 8 + 1 + 1 + 1 + 4 + 4 + 8 + 4 + 1;
+}
+
+impl ReporterRecord {
+    // Enforce the rolling filing window and open-dispute cap, then count the new filing
+    pub fn record_filing(&mut self, current_time: i64) -> Result<()> {
+        if current_time.saturating_sub(self.window_started_at) >= REPORTER_RATE_WINDOW {
+            self.window_started_at = current_time;
+            self.disputes_in_window = 0;
+        }
+        require!(self.disputes_in_window < MAX_DISPUTES_PER_WINDOW, GreylockerError::ReporterRateLimited);
+        require!(self.open_disputes < MAX_OPEN_DISPUTES_PER_REPORTER, GreylockerError::TooManyOpenDisputes);
+        self.disputes_in_window += 1;
+        self.open_disputes += 1;
+        self.total_filed = self.total_filed
+            .checked_add(1)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        Ok(())
+    }
+}
+
+impl Dispute {
+    pub fn is_seating(&self) -> bool {
+        self.status == DisputeStatus::Pending
+            || (self.status == DisputeStatus::Appealed && self.appeal_bonds_posted == 2)
+    }
+
+    // The side that lost the standing ruling—only they may appeal
+    pub fn losing_party(&self) -> Pubkey {
+        match self.resolution {
+            Some(DisputeResolution::Upheld) => self.accused,
+            _ => self.reporter,
+        }
+    }
+
+    // Jury seated—first hearings open a response window, appeals go straight to the vote
+    pub fn close_seating(&mut self, current_time: i64) -> Result<()> {
+        let (status, period) = if self.round == 0 {
+            (DisputeStatus::ResponseWindow, DISPUTE_RESPONSE_PERIOD)
+        } else {
+            (DisputeStatus::Voting, DISPUTE_VOTING_PERIOD)
+        };
+        self.status = status;
+        self.phase_deadline = current_time
+            .checked_add(period)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        Ok(())
+    }
+
+    // Tally the jury; ties and empty juries leave the standing ruling (or dismissal) in place
+    pub fn record_ruling(&mut self, current_time: i64) -> Result<()> {
+        let ruling = if self.votes_upheld > self.votes_dismissed {
+            DisputeResolution::Upheld
+        } else if self.votes_dismissed > self.votes_upheld {
+            DisputeResolution::Dismissed
+        } else {
+            self.resolution.clone().unwrap_or(DisputeResolution::Dismissed)
+        };
+        self.resolution = Some(ruling);
+        self.status = DisputeStatus::Resolved;
+        self.phase_deadline = if self.round < MAX_APPEAL_ROUNDS {
+            current_time
+                .checked_add(DISPUTE_APPEAL_PERIOD)
+                .ok_or(GreylockerError::ArithmeticError)?
+        } else {
+            current_time
+        };
+        Ok(())
+    }
+}
+
+impl DataPoolInfo {
+    // Strings within the space a pool (and a proposal to create one) sets aside for them
+    pub fn fits(&self) -> bool {
+        self.name.len() <= MAX_POOL_NAME_LEN
+            && self.data_type.len() <= MAX_POOL_DATA_TYPE_LEN
+            && self.description.len() <= MAX_POOL_DESCRIPTION_LEN
+    }
+}
+
+impl DataPool {
+    pub const LEN: usize = 8 + 32 + 50 + 50 + 8 + 200 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 32 + 4 + 8 + 8 + 8 + 1 + 1 + 1;
+
+    // Shared by governance and provider pools
+    pub fn init(&mut self, pool_id: Pubkey, pool_info: DataPoolInfo, service_provider: Pubkey, current_time: i64, bump: u8) -> Result<()> {
+        require!(pool_info.fits(), GreylockerError::InvalidDataPoolConfig);
+        require!(
+            pool_info.expires_at == 0 || pool_info.expires_at > current_time,
+            GreylockerError::InvalidDataPoolConfig
+        );
+        // Fixed at creation so the privacy promise users consent to can't be loosened later
+        require!(
+            pool_info.max_query_epsilon > 0 && pool_info.max_query_epsilon <= pool_info.epsilon_budget,
+            GreylockerError::InvalidDataPoolConfig
+        );
+
+        self.pool_id = pool_id;
+        self.name = pool_info.name;
+        self.data_type = pool_info.data_type;
+        self.reward_rate = pool_info.reward_rate;
+        self.description = pool_info.description;
+        self.created_at = current_time;
+        self.total_participants = 0;
+        self.total_rewards_paid = 0;
+        self.total_contributions = 0;
+        self.accepted_data_points = 0;
+        self.max_participants = pool_info.max_participants;
+        self.expires_at = pool_info.expires_at;
+        self.budget_funded = 0;
+        self.budget_spent = 0;
+        self.open_contributions = 0;
+        self.budget_committed = 0;
+        self.owed_rewards = 0;
+        self.service_provider = service_provider;
+        self.open_disputes = 0;
+        self.epsilon_budget = pool_info.epsilon_budget;
+        self.max_query_epsilon = pool_info.max_query_epsilon;
+        self.total_queries = 0;
+        self.paused = false;
+        self.active = true;
+        self.bump = bump;
+        Ok(())
+    }
+
+    // Governance controls every pool; a sponsoring provider's owner (the pool's seed creator) controls its own.
+    // Running a pool day to day—recording queries, pausing, retuning terms—is the governance authority's
+    // operating role, not a protocol change, so it stays single-signer; rewards stay capped by the reserve's epoch budget
+    pub fn is_controller(&self, state: &GreylockerState, creator: &Pubkey, authority: &Pubkey) -> bool {
+        *authority == state.governance_authority
+            || (self.service_provider != Pubkey::default() && authority == creator)
+    }
+
+    // Open for joins and submissions: active, unpaused and unexpired
+    pub fn is_open(&self, current_time: i64) -> bool {
+        self.active && !self.paused && (self.expires_at == 0 || current_time < self.expires_at)
+    }
+
+    // Budget neither spent nor escrowed for contributions in flight
+    pub fn budget_remaining(&self) -> u64 {
+        self.budget_funded
+            .saturating_sub(self.budget_spent)
+            .saturating_sub(self.budget_committed)
+    }
+
+    // Escrows an accepted contribution's reward; false when the pool draws on the reward reserve, or
+    // when the budget can't cover it and the reward is paid as top-ups allow
+    pub fn commit_budget(&mut self, reward: u64) -> Result<bool> {
+        if self.budget_funded == 0 || reward > self.budget_remaining() {
+            return Ok(false);
+        }
+        self.budget_committed = self.budget_committed
+            .checked_add(reward)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        Ok(true)
+    }
+
+    pub fn release_budget(&mut self, reward: u64) {
+        self.budget_committed = self.budget_committed.saturating_sub(reward);
+    }
+
+    // Pays what the budget covers and leaves the rest owed until the pool is topped up
+    pub fn draw_budget(&mut self, vault_balance: u64, requested: u64) -> Result<ReserveDraw> {
+        let paid = requested.min(self.budget_remaining()).min(vault_balance);
+        self.budget_spent = self.budget_spent
+            .checked_add(paid)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        Ok(ReserveDraw { paid, deferred: requested - paid, forfeited: 0 })
+    }
+}
+
+impl MerkleDistributor {
+    // Verify a claim against the root and flip its bit; leaves are keccak(index || claimant || amount)
+    pub fn record_claim(&mut self, index: u32, claimant: &Pubkey, amount: u64, proof: &[[u8; 32]], current_time: i64) -> Result<()> {
+        require!(current_time < self.expires_at && !self.clawed_back, GreylockerError::AirdropExpired);
+        require!(index < self.num_leaves, GreylockerError::InvalidAirdrop);
+
+        let byte = (index / 8) as usize;
+        let mask = 1u8 << (index % 8);
+        require!(self.claimed_bitmap[byte] & mask == 0, GreylockerError::AirdropAlreadyClaimed);
+
+        let leaf = keccak::hashv(&[&index.to_le_bytes(), claimant.as_ref(), &amount.to_le_bytes()]).0;
+        require!(verify_merkle_proof(proof, self.merkle_root, leaf), GreylockerError::InvalidMerkleProof);
+
+        self.claimed_bitmap[byte] |= mask;
+        self.claimed_count += 1;
+        self.claimed_amount = self.claimed_amount
+            .checked_add(amount)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        require!(self.claimed_amount <= self.total_amount, GreylockerError::InvalidAirdrop);
+        Ok(())
+    }
+}
+
+impl VestingSchedule {
+    // Amount vested as of `current_time`; revoked schedules are capped at what had vested
+    pub fn vested_amount(&self, current_time: i64) -> Result<u64> {
+        if self.revoked || current_time >= self.end_ts {
+            return Ok(self.total_amount);
+        }
+        if current_time < self.cliff_ts {
+            return Ok(0);
+        }
+
+        let duration = (self.end_ts - self.start_ts) as u128;
+        let elapsed = (current_time - self.start_ts) as u128;
+        let (numerator, denominator) = match self.kind {
+            VestingKind::Linear => (elapsed, duration),
+            VestingKind::Step { interval } => {
+                let interval = interval as u128;
+                (elapsed / interval, duration.div_ceil(interval))
+            },
+        };
+        (self.total_amount as u128)
+            .checked_mul(numerator)
+            .and_then(|val| val.checked_div(denominator))
+            .and_then(|val| u64::try_from(val).ok())
+            .ok_or(GreylockerError::ArithmeticError.into())
+    }
+}
+
+impl LiquidStakingPool {
+    // Accrue Liquidity rewards on the pool's backing; they count toward the rate once harvested
+    pub fn accrue(&mut self, state: &GreylockerState, current_time: i64) -> Result<()> {
+        let index = current_reward_index(state, &StakeType::Liquidity, current_time)?;
+        let delta = index.saturating_sub(self.reward_index_snapshot);
+        self.reward_index_snapshot = index;
+        let earned = (self.total_backing as u128)
+            .checked_mul(delta)
+            .and_then(|val| val.checked_div(REWARD_INDEX_PRECISION))
+            .and_then(|val| u64::try_from(val).ok())
+            .ok_or(GreylockerError::ArithmeticError)?;
+        self.pending_rewards = self.pending_rewards
+            .checked_add(earned)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        Ok(())
+    }
+
+    // GREY each stGREY is worth on paper: harvested backing plus rewards accrued but not yet drawn
+    pub fn total_value(&self) -> Result<u64> {
+        self.total_backing
+            .checked_add(self.pending_rewards)
+            .ok_or(GreylockerError::ArithmeticError.into())
+    }
+}
+
+impl DelegationPool {
+    // Fold rewards since the last accrual into the per-share accumulator, less commission
+    pub fn accrue(&mut self, state: &GreylockerState, current_time: i64) -> Result<()> {
+        let index = current_reward_index(state, &delegation_reward_type(&self.stake_type), current_time)?;
+        let delta = index.saturating_sub(self.reward_index_snapshot);
+        self.reward_index_snapshot = index;
+        if delta == 0 || self.total_delegated == 0 || self.total_shares == 0 {
+            return Ok(());
+        }
+
+        let earned = (self.total_delegated as u128)
+            .checked_mul(delta)
+            .and_then(|val| val.checked_div(REWARD_INDEX_PRECISION))
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let commission = earned
+            .checked_mul(self.commission_bps as u128)
+            .and_then(|val| val.checked_div(10_000))
+            .ok_or(GreylockerError::ArithmeticError)?;
+        self.operator_rewards = u64::try_from(commission)
+            .ok()
+            .and_then(|val| self.operator_rewards.checked_add(val))
+            .ok_or(GreylockerError::ArithmeticError)?;
+        self.acc_reward_per_share = (earned - commission)
+            .checked_mul(REWARD_INDEX_PRECISION)
+            .and_then(|val| val.checked_div(self.total_shares as u128))
+            .and_then(|val| self.acc_reward_per_share.checked_add(val))
+            .ok_or(GreylockerError::ArithmeticError)?;
+        Ok(())
+    }
+
+    pub fn reward_debt_for(&self, shares: u64) -> Result<u128> {
+        (shares as u128)
+            .checked_mul(self.acc_reward_per_share)
+            .and_then(|val| val.checked_div(REWARD_INDEX_PRECISION))
+            .ok_or(GreylockerError::ArithmeticError.into())
+    }
+
+    // Shares minted for `amount` at the current GREY-per-share rate; a fully slashed pool has no rate
+    // left, and converting 1:1 would hand the new GREY to holders of the worthless shares
+    pub fn shares_for(&self, amount: u64) -> Result<u64> {
+        require!(self.total_shares == 0 || self.total_delegated > 0, GreylockerError::DelegationPoolWiped);
+        share_conversion(amount, self.total_shares, self.total_delegated)
+    }
+
+    pub fn value_of(&self, shares: u64) -> Result<u64> {
+        share_conversion(shares, self.total_delegated, self.total_shares)
+    }
+
+    pub fn unbonding_shares_for(&self, amount: u64) -> Result<u64> {
+        require!(self.unbonding_shares == 0 || self.total_unbonding > 0, GreylockerError::DelegationPoolWiped);
+        share_conversion(amount, self.unbonding_shares, self.total_unbonding)
+    }
+
+    pub fn unbonding_value_of(&self, shares: u64) -> Result<u64> {
+        share_conversion(shares, self.total_unbonding, self.unbonding_shares)
+    }
+
+    // Slash active and unbonding GREY alike; shares stay put so every delegator loses the same fraction
+    pub fn slash(&mut self, slash_percentage: u64) -> Result<(u64, u64)> {
+        let from_delegated = self.total_delegated
+            .checked_mul(slash_percentage)
+            .and_then(|val| val.checked_div(100))
+            .ok_or(GreylockerError::ArithmeticError)?;
+        let from_unbonding = self.total_unbonding
+            .checked_mul(slash_percentage)
+            .and_then(|val| val.checked_div(100))
+            .ok_or(GreylockerError::ArithmeticError)?;
+        self.total_delegated -= from_delegated;
+        self.total_unbonding -= from_unbonding;
+        Ok((from_delegated, from_unbonding))
+    }
+}
+
+// Helper function to convert between shares and GREY; an empty side converts 1:1
+pub fn share_conversion(value: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    if numerator == 0 || denominator == 0 {
+        return Ok(value);
+    }
+    (value as u128)
+        .checked_mul(numerator as u128)
+        .and_then(|val| val.checked_div(denominator as u128))
+        .and_then(|val| u64::try_from(val).ok())
+        .ok_or(GreylockerError::ArithmeticError.into())
+}
+
+impl Delegation {
+    // Move rewards earned since the last settlement into pending_rewards
+    pub fn settle(&mut self, pool: &DelegationPool) -> Result<()> {
+        let accumulated = pool.reward_debt_for(self.shares)?;
+        let earned = u64::try_from(accumulated.saturating_sub(self.reward_debt))
+            .map_err(|_| GreylockerError::ArithmeticError)?;
+        self.pending_rewards = self.pending_rewards
+            .checked_add(earned)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        self.reward_debt = accumulated;
+        Ok(())
+    }
+}
+
+impl GovernanceCouncil {
+    pub fn is_signer(&self, key: &Pubkey) -> bool {
+        self.signers.contains(key)
+    }
+}
+
+impl Proposal {
+    // Threshold met—start the timelock clock
+    pub fn queue(&mut self, timelock: i64, current_time: i64) -> Result<()> {
+        self.status = ProposalStatus::Queued;
+        self.eta = current_time
+            .checked_add(timelock)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        Ok(())
+    }
+}
+
+impl Proposal {
+    pub const SPACE: usize = 8 + 8 + 32 + ProposalAction::MAX_LEN + 4 + 32 * MAX_COUNCIL_SIGNERS + 8 + 1 + 8 + 8
+        + 1 + 8 + 8 + 8 + 8 + 8 + 2 + 2 + 1;
+}
+
+impl ProposalAction {
+    // Largest variant is UpdateCouncil: 4-byte vec prefix + signers + threshold + timelock.
+    // CreateDataPool comes next: its capped strings and prefixes plus five u64s total 327 bytes.
+    pub const MAX_LEN: usize = 1 + 4 + 32 * MAX_COUNCIL_SIGNERS + 1 + 8;
+}
+
+impl RewardReserve {
+    // Start a fresh epoch once the current one has run its course
+    pub fn roll_epoch(&mut self, reserve_balance: u64, current_time: i64) -> Result<()> {
+        let elapsed = current_time.saturating_sub(self.epoch_started_at);
+        if elapsed < self.epoch_duration {
+            return Ok(());
+        }
+        let epochs = elapsed / self.epoch_duration;
+        self.current_epoch = self.current_epoch
+            .checked_add(epochs as u64)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        self.epoch_started_at = self.epoch_started_at
+            .checked_add(epochs * self.epoch_duration)
+            .ok_or(GreylockerError::ArithmeticError)?;
+        self.emitted_this_epoch = 0;
+        self.refresh_coverage(reserve_balance);
+        Ok(())
+    }
+
+    // Coverage is the share of the remaining epoch budget the reserve can actually back; set once per epoch
+    pub fn refresh_coverage(&mut self, reserve_balance: u64) {
+        let budget_left = self.epoch_emission_budget.saturating_sub(self.emitted_this_epoch);
+        self.coverage_bps = if budget_left == 0 || reserve_balance >= budget_left {
+            10_000
+        } else {
+            ((reserve_balance as u128 * 10_000) / budget_left as u128) as u16
+        };
+    }
+
+    // Funding mid-epoch can only lift coverage—claims already paid this epoch keep their share
+    pub fn raise_coverage(&mut self, reserve_balance: u64) {
+        let previous = self.coverage_bps;
+        self.refresh_coverage(reserve_balance);
+        self.coverage_bps = self.coverage_bps.max(previous);
+    }
+}
+
+impl RewardIndex {
+    pub const LEN: usize = 16 * 3 + 8; // 3 u128 indices + 1 i64 timestamp
+}
+
+impl LockTier {
+    pub const LEN: usize = 8 + 2;
 }
 
 impl ServiceInfo {
     pub const LEN: usize = 50 + 200 + 200; // Rough estimate for strings
 }
+
+impl JurorEntry {
+    pub const LEN: usize = 32 + 32 + 8;
+}
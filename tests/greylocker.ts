@@ -0,0 +1,1584 @@
+import * as anchor from '@coral-xyz/anchor';
+import { assert } from 'chai';
+import { Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY, SYSVAR_SLOT_HASHES_PUBKEY } from '@solana/web3.js';
+import { ACCOUNT_SIZE, AccountLayout, TOKEN_PROGRAM_ID, getAssociatedTokenAddressSync } from '@solana/spl-token';
+import { keccak_256 } from '@noble/hashes/sha3';
+import { BankrunProvider } from 'anchor-bankrun';
+import { Clock, ProgramTestContext, startAnchor } from 'solana-bankrun';
+
+const DAY = 24 * 60 * 60;
+const GREY = (amount: number) => new anchor.BN(amount).mul(new anchor.BN(10).pow(new anchor.BN(9)));
+const u64 = (value: number) => new anchor.BN(value).toArrayLike(Buffer, 'le', 8);
+
+// Dispute phases last days, so these run on bankrun where the clock can be warped
+describe('Greylocker Staking Tests', () => {
+  const workspaceProgram = anchor.workspace.Greylocker;
+  let context: ProgramTestContext;
+  let program: anchor.Program;
+
+  const admin = Keypair.generate();
+  const operator = Keypair.generate();
+  const delegatorA = Keypair.generate();
+  const delegatorB = Keypair.generate();
+  const juror = Keypair.generate();
+  const reporter = Keypair.generate();
+  const staker = Keypair.generate();
+  const provider = Keypair.generate();
+  const greyMint = Keypair.generate();
+
+  const pda = (...seeds: Buffer[]) => PublicKey.findProgramAddressSync(seeds, workspaceProgram.programId)[0];
+  const statePda = pda(Buffer.from('greylocker-state'));
+  const tokenAccount = (owner: PublicKey) => getAssociatedTokenAddressSync(greyMint.publicKey, owner, true);
+  const stakeAccountFor = (owner: PublicKey, positionId: number) =>
+    pda(Buffer.from('stake-account'), owner.toBuffer(), u64(positionId));
+  const stakeVaultFor = (stakeAccount: PublicKey) => pda(Buffer.from('stake-vault'), stakeAccount.toBuffer());
+
+  const operatorStake = stakeAccountFor(operator.publicKey, 0);
+  const delegationPool = pda(Buffer.from('delegation-pool'), operatorStake.toBuffer());
+  const delegationVault = pda(Buffer.from('delegation-vault'), delegationPool.toBuffer());
+  const serviceProvider = pda(Buffer.from('service-provider'), operator.publicKey.toBuffer());
+  const jurorRegistry = pda(Buffer.from('juror-registry'));
+  const rewardReserve = pda(Buffer.from('reward-reserve'));
+  const reserveVault = pda(Buffer.from('reward-reserve-vault'));
+
+  // Token accounts are written straight into the bank; the mint authority is the program itself
+  const writeTokenAccount = (address: PublicKey, owner: PublicKey, amount: anchor.BN, mint = greyMint.publicKey) => {
+    const data = Buffer.alloc(ACCOUNT_SIZE);
+    AccountLayout.encode(
+      {
+        mint,
+        owner,
+        amount: BigInt(amount.toString()),
+        delegateOption: 0,
+        delegate: PublicKey.default,
+        state: 1,
+        isNativeOption: 0,
+        isNative: BigInt(0),
+        delegatedAmount: BigInt(0),
+        closeAuthorityOption: 0,
+        closeAuthority: PublicKey.default,
+      },
+      data
+    );
+    context.setAccount(address, {
+      lamports: 1_000_000_000,
+      data,
+      owner: TOKEN_PROGRAM_ID,
+      executable: false,
+    });
+  };
+  const fundGrey = (owner: PublicKey, amount: anchor.BN) => writeTokenAccount(tokenAccount(owner), owner, amount);
+
+  // A fresh wallet with SOL for fees and rent and the given GREY
+  const newActor = (grey: anchor.BN) => {
+    const actor = Keypair.generate();
+    context.setAccount(actor.publicKey, {
+      lamports: 100 * anchor.web3.LAMPORTS_PER_SOL,
+      data: Buffer.alloc(0),
+      owner: SystemProgram.programId,
+      executable: false,
+    });
+    fundGrey(actor.publicKey, grey);
+    return actor;
+  };
+
+  const tokenBalance = async (address: PublicKey) => {
+    const account = await context.banksClient.getAccount(address);
+    return new anchor.BN(AccountLayout.decode(account!.data).amount.toString());
+  };
+
+  const warpSeconds = async (seconds: number) => {
+    const clock = await context.banksClient.getClock();
+    context.setClock(
+      new Clock(
+        clock.slot,
+        clock.epochStartTimestamp,
+        clock.epoch,
+        clock.leaderScheduleEpoch,
+        clock.unixTimestamp + BigInt(seconds)
+      )
+    );
+  };
+
+  const expectError = async (promise: Promise<unknown>, name: string) => {
+    const code = program.idl.errors!.find((error) => error.name === name)!.code;
+    try {
+      await promise;
+    } catch (err: any) {
+      const text = `${err}\n${(err.logs ?? []).join('\n')}`;
+      assert.isTrue(text.includes(name) || text.includes(`0x${code.toString(16)}`), text);
+      return;
+    }
+    assert.fail(`expected ${name}`);
+  };
+
+  const stake = async (user: Keypair, amount: anchor.BN, stakeType: object, positionId = 0) => {
+    const stakeAccount = stakeAccountFor(user.publicKey, positionId);
+    await program.methods
+      .stake(amount, stakeType, new anchor.BN(0))
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        stakeRegistry: pda(Buffer.from('stake-registry'), user.publicKey.toBuffer()),
+        stakeAccount,
+        stakeVault: stakeVaultFor(stakeAccount),
+        userTokenAccount: tokenAccount(user.publicKey),
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+    return stakeAccount;
+  };
+
+  const requestUnstake = async (user: Keypair, stakeAccount: PublicKey, amount: anchor.BN) => {
+    const nonce = (await program.account.stakeAccount.fetch(stakeAccount)).nextUnbondingId;
+    const unbondingRequest = pda(Buffer.from('unbonding'), stakeAccount.toBuffer(), nonce.toArrayLike(Buffer, 'le', 8));
+    await program.methods
+      .requestUnstake(amount)
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        stakeAccount,
+        unbondingRequest,
+        stakeVault: stakeVaultFor(stakeAccount),
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+    return unbondingRequest;
+  };
+
+  // A one-signer council with the minimum timelock; proposals queue on creation and run a day later
+  const councilPda = pda(Buffer.from('governance-council'));
+  const propose = async (action: object) => {
+    if (!(await context.banksClient.getAccount(councilPda))) {
+      await program.methods
+        .initializeGovernance([admin.publicKey], 1, new anchor.BN(DAY))
+        .accounts({
+          greylockerState: statePda,
+          governanceCouncil: councilPda,
+          governanceAuthority: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    }
+    const proposalId = (await program.account.governanceCouncil.fetch(councilPda)).nextProposalId;
+    const proposal = pda(Buffer.from('proposal'), proposalId.toArrayLike(Buffer, 'le', 8));
+    await program.methods
+      .createProposal(action)
+      .accounts({
+        governanceCouncil: councilPda,
+        proposal,
+        proposer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+    return proposal;
+  };
+
+  const executeProposal = (proposal: PublicKey, accounts: Record<string, PublicKey | null> = {}) =>
+    program.methods
+      .executeProposal()
+      .accounts({
+        greylockerState: statePda,
+        governanceCouncil: councilPda,
+        proposal,
+        greyMint: greyMint.publicKey,
+        recipient: null,
+        rewardReserve: null,
+        reserveVault: null,
+        vestingSchedule: null,
+        vestingEscrow: null,
+        treasury: null,
+        executor: admin.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        ...accounts,
+      })
+      .signers([admin])
+      .rpc();
+
+  const runProposal = async (action: object, accounts: Record<string, PublicKey | null> = {}) => {
+    const proposal = await propose(action);
+    await warpSeconds(DAY);
+    await executeProposal(proposal, accounts);
+  };
+
+  // Governance pools are seeded by the governance authority and need a council proposal to open
+  const poolInfo = (name: string) => ({
+    name,
+    dataType: 'sleep',
+    rewardRate: GREY(10),
+    description: 'Nightly sleep summaries',
+    maxParticipants: new anchor.BN(0),
+    expiresAt: new anchor.BN(0),
+    epsilonBudget: new anchor.BN(10_000),
+    maxQueryEpsilon: new anchor.BN(1_000),
+  });
+  const governancePool = (name: string) => pda(Buffer.from('data-pool'), admin.publicKey.toBuffer(), Buffer.from(name));
+  const createDataPool = (info: ReturnType<typeof poolInfo>, proposal: PublicKey) =>
+    program.methods
+      .createDataPool(info)
+      .accounts({
+        greylockerState: statePda,
+        dataPool: governancePool(info.name),
+        governanceCouncil: councilPda,
+        proposal,
+        governanceAuthority: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([admin])
+      .rpc();
+
+  // Validators whose DataValidator stake is old enough to attest; the contribution tests add more
+  const attesters = [juror];
+  const participantFor = (user: PublicKey, pool: PublicKey) =>
+    pda(Buffer.from('participant'), user.toBuffer(), pool.toBuffer());
+  const attestationFor = (contribution: PublicKey, validator: PublicKey) =>
+    pda(Buffer.from('attestation'), contribution.toBuffer(), validator.toBuffer());
+  const budgetVaultFor = (pool: PublicKey) => pda(Buffer.from('data-pool-vault'), pool.toBuffer());
+  const randomHash = () => Array.from(Keypair.generate().publicKey.toBytes());
+
+  const joinPool = (user: Keypair, pool: PublicKey, creator: PublicKey, consentHash = randomHash()) =>
+    program.methods
+      .joinDataPool(consentHash)
+      .accounts({
+        greylockerState: statePda,
+        participant: participantFor(user.publicKey, pool),
+        dataPool: pool,
+        user: user.publicKey,
+        dataPoolCreator: creator,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+
+  // Contribution epochs are days counted from the pool's creation
+  const currentEpoch = async (pool: PublicKey) => {
+    const now = Number((await context.banksClient.getClock()).unixTimestamp);
+    const createdAt = (await program.account.dataPool.fetch(pool)).createdAt.toNumber();
+    return Math.floor((now - createdAt) / DAY);
+  };
+
+  const submitContribution = async (user: Keypair, pool: PublicKey, creator: PublicKey, dataPoints: number, epoch?: number) => {
+    const contributionEpoch = epoch ?? (await currentEpoch(pool));
+    const participant = participantFor(user.publicKey, pool);
+    const contribution = pda(Buffer.from('contribution'), participant.toBuffer(), u64(contributionEpoch));
+    await program.methods
+      .submitContribution(new anchor.BN(contributionEpoch), randomHash(), new anchor.BN(dataPoints))
+      .accounts({
+        greylockerState: statePda,
+        dataPool: pool,
+        participant,
+        contribution,
+        user: user.publicKey,
+        dataPoolCreator: creator,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+    return contribution;
+  };
+
+  const attest = (validator: Keypair, contribution: PublicKey, pool: PublicKey, approve = true) =>
+    program.methods
+      .attestContribution(approve)
+      .accounts({
+        greylockerState: statePda,
+        contribution,
+        dataPool: pool,
+        validatorStakeAccount: stakeAccountFor(validator.publicKey, 0),
+        attestation: attestationFor(contribution, validator.publicKey),
+        validator: validator.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([validator])
+      .rpc();
+
+  const finalizeContribution = (contribution: PublicKey, pool: PublicKey) =>
+    program.methods.finalizeContribution().accounts({ greylockerState: statePda, dataPool: pool, contribution }).rpc();
+
+  // Every attester approves, then the attestation window runs out
+  const acceptContribution = async (contribution: PublicKey, pool: PublicKey) => {
+    for (const attester of attesters) {
+      await attest(attester, contribution, pool);
+    }
+    await warpSeconds(2 * DAY);
+    await finalizeContribution(contribution, pool);
+  };
+
+  const releaseAttestation = (validator: Keypair, contribution: PublicKey) =>
+    program.methods
+      .releaseAttestation()
+      .accounts({
+        contribution,
+        attestation: attestationFor(contribution, validator.publicKey),
+        stakeAccount: stakeAccountFor(validator.publicKey, 0),
+        validator: validator.publicKey,
+      })
+      .rpc();
+
+  const payDataReward = (
+    user: Keypair,
+    pool: PublicKey,
+    creator: PublicKey,
+    contribution: PublicKey,
+    budgetVault: PublicKey | null = null
+  ) =>
+    program.methods
+      .payDataReward()
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        treasury: pda(Buffer.from('treasury')),
+        dataPool: pool,
+        participant: participantFor(user.publicKey, pool),
+        contribution,
+        rewardReserve,
+        reserveVault,
+        budgetVault,
+        userTokenAccount: tokenAccount(user.publicKey),
+        user: user.publicKey,
+        dataPoolCreator: creator,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  // A freshly registered service sits at the base reputation
+  const newSponsor = async () => {
+    const sponsor = newActor(GREY(11_000));
+    await registerService(sponsor, await stake(sponsor, GREY(10_000), { service: {} }));
+    return sponsor;
+  };
+
+  const createProviderPool = async (sponsor: Keypair, info: ReturnType<typeof poolInfo>, initialBudget: anchor.BN) => {
+    const pool = pda(Buffer.from('data-pool'), sponsor.publicKey.toBuffer(), Buffer.from(info.name));
+    await program.methods
+      .createProviderDataPool(info, initialBudget)
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        dataPool: pool,
+        budgetVault: budgetVaultFor(pool),
+        serviceProvider: pda(Buffer.from('service-provider'), sponsor.publicKey.toBuffer()),
+        serviceStakeAccount: stakeAccountFor(sponsor.publicKey, 0),
+        serviceTokenAccount: tokenAccount(sponsor.publicKey),
+        serviceOwner: sponsor.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([sponsor])
+      .rpc();
+    return pool;
+  };
+
+  const setPoolPaused = (pool: PublicKey, creator: PublicKey, authority: Keypair, paused: boolean) =>
+    program.methods
+      .setDataPoolPaused(paused)
+      .accounts({ greylockerState: statePda, dataPool: pool, authority: authority.publicKey, dataPoolCreator: creator })
+      .signers([authority])
+      .rpc();
+
+  const settlementFor = (pool: PublicKey, user: PublicKey) =>
+    pda(Buffer.from('pool-settlement'), pool.toBuffer(), user.toBuffer());
+  const leavePool = (user: Keypair, pool: PublicKey, creator: PublicKey) =>
+    program.methods
+      .leaveDataPool()
+      .accounts({
+        dataPool: pool,
+        participant: participantFor(user.publicKey, pool),
+        poolSettlement: settlementFor(pool, user.publicKey),
+        user: user.publicKey,
+        dataPoolCreator: creator,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+  const claimStakeRewards = (user: Keypair, stakeAccount: PublicKey) =>
+    program.methods
+      .claimStakeRewards()
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        stakeAccount,
+        rewardReserve,
+        reserveVault,
+        userTokenAccount: tokenAccount(user.publicKey),
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  const registerService = (user: Keypair, stakeAccount: PublicKey) =>
+    program.methods
+      .registerService({ name: 'Provider', description: 'Position test service', metadataUri: '' })
+      .accounts({
+        greylockerState: statePda,
+        serviceProvider: pda(Buffer.from('service-provider'), user.publicKey.toBuffer()),
+        stakeAccount,
+        user: user.publicKey,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([user])
+      .rpc();
+
+  const mergeStakes = (user: Keypair, source: PublicKey, destination: PublicKey) =>
+    program.methods
+      .mergeStakes()
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        stakeRegistry: pda(Buffer.from('stake-registry'), user.publicKey.toBuffer()),
+        destinationStakeAccount: destination,
+        destinationStakeVault: stakeVaultFor(destination),
+        sourceStakeAccount: source,
+        sourceStakeVault: stakeVaultFor(source),
+        serviceProvider: pda(Buffer.from('service-provider'), user.publicKey.toBuffer()),
+        user: user.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  const fileDispute = async (filer: Keypair, service: PublicKey, evidence: string) => {
+    const dispute = pda(Buffer.from('dispute'), filer.publicKey.toBuffer(), service.toBuffer());
+    await program.methods
+      .createDispute(evidence, { other: {} })
+      .accounts({
+        greylockerState: statePda,
+        dispute,
+        reporterRecord: pda(Buffer.from('reporter'), filer.publicKey.toBuffer()),
+        greyMint: greyMint.publicKey,
+        reporterBondVault: pda(Buffer.from('reporter-bond'), dispute.toBuffer()),
+        reporterTokenAccount: tokenAccount(filer.publicKey),
+        serviceProvider: service,
+        dataPool: null,
+        reporter: filer.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([filer])
+      .rpc();
+    return dispute;
+  };
+
+  // The only registered juror is always the one drawn
+  const seatJuror = async (dispute: PublicKey, round: number) => {
+    // Land a bank on the seed slot so its hash is in SlotHashes, then move past it
+    const drawSlot = BigInt((await program.account.dispute.fetch(dispute)).drawSlot.toString());
+    context.warpToSlot(drawSlot);
+    context.warpToSlot(drawSlot + BigInt(1));
+    await program.methods
+      .drawJury()
+      .accounts({ dispute, jurorRegistry, slotHashes: SYSVAR_SLOT_HASHES_PUBKEY })
+      .rpc();
+
+    const jurorRecord = pda(Buffer.from('juror'), dispute.toBuffer(), juror.publicKey.toBuffer(), Buffer.from([round]));
+    await program.methods
+      .joinJury()
+      .accounts({
+        greylockerState: statePda,
+        dispute,
+        jurorStakeAccount: stakeAccountFor(juror.publicKey, 0),
+        jurorRecord,
+        juror: juror.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([juror])
+      .rpc();
+    return jurorRecord;
+  };
+
+  const advanceDispute = (dispute: PublicKey) => program.methods.advanceDispute().accounts({ dispute }).rpc();
+
+  const delegate = async (delegator: Keypair, amount: anchor.BN) => {
+    const delegation = pda(Buffer.from('delegation'), delegationPool.toBuffer(), delegator.publicKey.toBuffer());
+    await program.methods
+      .delegate(amount)
+      .accounts({
+        greylockerState: statePda,
+        delegationPool,
+        operatorStakeAccount: operatorStake,
+        delegation,
+        delegationVault,
+        delegatorTokenAccount: tokenAccount(delegator.publicKey),
+        delegator: delegator.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([delegator])
+      .rpc();
+    return delegation;
+  };
+
+  before(async () => {
+    const actors = [admin, operator, delegatorA, delegatorB, juror, reporter, staker, provider];
+    context = await startAnchor('.', [], []);
+    for (const actor of actors) {
+      context.setAccount(actor.publicKey, {
+        lamports: 100 * anchor.web3.LAMPORTS_PER_SOL,
+        data: Buffer.alloc(0),
+        owner: SystemProgram.programId,
+        executable: false,
+      });
+    }
+    const provider = new BankrunProvider(context);
+    program = new anchor.Program(workspaceProgram.idl, workspaceProgram.programId, provider);
+
+    const [, bump] = PublicKey.findProgramAddressSync([Buffer.from('greylocker-state')], program.programId);
+    await program.methods
+      .initialize(bump)
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        treasury: pda(Buffer.from('treasury')),
+        initializer: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([admin, greyMint])
+      .rpc();
+
+    for (const actor of actors) {
+      fundGrey(actor.publicKey, GREY(20_000));
+    }
+  });
+
+  it('Mints delegation shares in proportion to GREY delegated', async () => {
+    await stake(operator, GREY(10_000), { service: {} });
+    await program.methods
+      .registerService({ name: 'Operator', description: 'Delegation test service', metadataUri: '' })
+      .accounts({
+        greylockerState: statePda,
+        serviceProvider,
+        stakeAccount: operatorStake,
+        user: operator.publicKey,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([operator])
+      .rpc();
+    await program.methods
+      .openDelegationPool(500)
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        operatorStakeAccount: operatorStake,
+        delegationPool,
+        delegationVault,
+        operator: operator.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([operator])
+      .rpc();
+
+    const delegationA = await delegate(delegatorA, GREY(3_000));
+    const delegationB = await delegate(delegatorB, GREY(1_000));
+
+    const pool = await program.account.delegationPool.fetch(delegationPool);
+    const sharesA = (await program.account.delegation.fetch(delegationA)).shares;
+    const sharesB = (await program.account.delegation.fetch(delegationB)).shares;
+    assert.equal(pool.totalDelegated.toString(), GREY(4_000).toString());
+    assert.equal(pool.totalShares.toString(), sharesA.add(sharesB).toString());
+    assert.equal(sharesA.toString(), sharesB.muln(3).toString());
+    const operatorAccount = await program.account.stakeAccount.fetch(operatorStake);
+    assert.equal(operatorAccount.delegatedAmount.toString(), GREY(4_000).toString());
+  });
+
+  it('Keeps the operator above the minimum self-bond', async () => {
+    // Own stake sits exactly at the service minimum; delegated GREY doesn't count toward it
+    await expectError(requestUnstake(operator, operatorStake, GREY(1)), 'SelfBondTooLow');
+  });
+
+  it('Slashes delegators at the operator rate without touching shares', async () => {
+    const jurorStake = await stake(juror, GREY(5_000), { dataValidator: {} });
+    await program.methods
+      .registerJuror()
+      .accounts({
+        greylockerState: statePda,
+        jurorRegistry,
+        jurorStakeAccount: jurorStake,
+        juror: juror.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([juror])
+      .rpc();
+
+    const dispute = await fileDispute(reporter, serviceProvider, 'Service went dark');
+    const reporterRecord = pda(Buffer.from('reporter'), reporter.publicKey.toBuffer());
+    const reporterBondVault = pda(Buffer.from('reporter-bond'), dispute.toBuffer());
+
+    const jurorRecord = await seatJuror(dispute, 0);
+    await warpSeconds(3 * DAY + 1);
+    await advanceDispute(dispute);
+    await program.methods
+      .castJuryVote({ upheld: {} })
+      .accounts({ dispute, jurorRecord, juror: juror.publicKey })
+      .signers([juror])
+      .rpc();
+    await warpSeconds(3 * DAY + 1);
+    await advanceDispute(dispute);
+    await warpSeconds(3 * DAY + 1);
+
+    const sharesBefore = (await program.account.delegationPool.fetch(delegationPool)).totalShares;
+    await program.methods
+      .executeDisputeRuling()
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        dispute,
+        serviceProvider,
+        stakeAccount: operatorStake,
+        stakeVault: stakeVaultFor(operatorStake),
+        reporterTokenAccount: tokenAccount(reporter.publicKey),
+        accusedTokenAccount: tokenAccount(operator.publicKey),
+        reporterBondVault,
+        reporterRecord,
+        bondVault: null,
+        delegationPool,
+        delegationVault,
+        dataPool: null,
+        executor: reporter.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([reporter])
+      .rpc();
+
+    // DisputeType::Other slashes 50%: operator and delegators lose the same fraction
+    const operatorAccount = await program.account.stakeAccount.fetch(operatorStake);
+    const pool = await program.account.delegationPool.fetch(delegationPool);
+    assert.equal(operatorAccount.amount.toString(), GREY(5_000).toString());
+    assert.equal(operatorAccount.delegatedAmount.toString(), GREY(2_000).toString());
+    assert.equal(pool.totalDelegated.toString(), GREY(2_000).toString());
+    assert.equal(pool.totalShares.toString(), sharesBefore.toString());
+    assert.equal((await tokenBalance(delegationVault)).toString(), GREY(2_000).toString());
+
+    // Each delegation is now worth half its original GREY
+    const delegationA = pda(Buffer.from('delegation'), delegationPool.toBuffer(), delegatorA.publicKey.toBuffer());
+    const sharesA = (await program.account.delegation.fetch(delegationA)).shares;
+    const valueA = sharesA.mul(pool.totalDelegated).div(pool.totalShares);
+    assert.equal(valueA.toString(), GREY(1_500).toString());
+  });
+
+  it('Defers queued rewards while the reserve is empty and pays them once funded', async () => {
+    await program.methods
+      .initializeRewardReserve(GREY(1_000), new anchor.BN(30 * DAY), { queued: {} })
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        rewardReserve,
+        reserveVault,
+        governanceAuthority: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([admin])
+      .rpc();
+
+    const stakerAccount = await stake(staker, GREY(1_000), { security: {} });
+    await warpSeconds(30 * DAY);
+
+    const claim = () => claimStakeRewards(staker, stakerAccount);
+
+    // Nothing in the vault: the claim goes through, pays nothing and keeps the whole amount owed
+    const balanceBefore = await tokenBalance(tokenAccount(staker.publicKey));
+    await claim();
+    const owed = (await program.account.stakeAccount.fetch(stakerAccount)).accumulatedRewards;
+    assert.isTrue(owed.gtn(0));
+    assert.equal((await tokenBalance(tokenAccount(staker.publicKey))).toString(), balanceBefore.toString());
+
+    await program.methods
+      .fundRewardReserve(GREY(1_000))
+      .accounts({
+        rewardReserve,
+        reserveVault,
+        funderTokenAccount: tokenAccount(admin.publicKey),
+        funder: admin.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+    const reserve = await program.account.rewardReserve.fetch(rewardReserve);
+    assert.equal(reserve.coverageBps, 10_000);
+
+    await claim();
+    assert.equal((await program.account.stakeAccount.fetch(stakerAccount)).accumulatedRewards.toString(), '0');
+    assert.equal(
+      (await tokenBalance(tokenAccount(staker.publicKey))).toString(),
+      balanceBefore.add(owed).toString()
+    );
+  });
+
+  it('Scales pro-rata claims by one coverage per epoch and defers them while the reserve is empty', async () => {
+    // A budget four times what the reserve holds leaves roughly 25% coverage
+    await runProposal(
+      {
+        configureRewardReserve: {
+          epochEmissionBudget: GREY(4_000),
+          epochDuration: new anchor.BN(30 * DAY),
+          shortfallMode: { proRata: {} },
+        },
+      },
+      { rewardReserve, reserveVault }
+    );
+    await warpSeconds(30 * DAY);
+
+    // Each claim's paid share of what it asked for must match the epoch's coverage, whoever claims first
+    const stakerAccount = stakeAccountFor(staker.publicKey, 0);
+    const jurorAccount = stakeAccountFor(juror.publicKey, 0);
+    let coverageBps: number | undefined;
+    for (const [user, stakeAccount] of [[staker, stakerAccount], [juror, jurorAccount]] as const) {
+      const reserveBefore = await program.account.rewardReserve.fetch(rewardReserve);
+      const balanceBefore = await tokenBalance(tokenAccount(user.publicKey));
+      await claimStakeRewards(user, stakeAccount);
+      const reserveAfter = await program.account.rewardReserve.fetch(rewardReserve);
+      const paid = (await tokenBalance(tokenAccount(user.publicKey))).sub(balanceBefore);
+      const forfeited = reserveAfter.totalForfeited.sub(reserveBefore.totalForfeited);
+      assert.isTrue(paid.gtn(0) && forfeited.gtn(0));
+      if (coverageBps === undefined) coverageBps = reserveAfter.coverageBps;
+      assert.isBelow(coverageBps!, 10_000);
+      assert.equal(reserveAfter.coverageBps, coverageBps);
+      const paidBps = paid.muln(10_000).div(paid.add(forfeited)).toNumber();
+      assert.approximately(paidBps, coverageBps!, 1);
+    }
+
+    // Drain the vault: the next claim writes nothing off and keeps the whole amount owed
+    writeTokenAccount(reserveVault, statePda, new anchor.BN(0));
+    await warpSeconds(DAY);
+    const forfeitedBefore = (await program.account.rewardReserve.fetch(rewardReserve)).totalForfeited;
+    const balanceBefore = await tokenBalance(tokenAccount(staker.publicKey));
+    await claimStakeRewards(staker, stakerAccount);
+    assert.isTrue((await program.account.stakeAccount.fetch(stakerAccount)).accumulatedRewards.gtn(0));
+    assert.equal((await program.account.rewardReserve.fetch(rewardReserve)).totalForfeited.toString(), forfeitedBefore.toString());
+    assert.equal((await tokenBalance(tokenAccount(staker.publicKey))).toString(), balanceBefore.toString());
+  });
+
+  it('Closes an emptied position on its last withdrawal and frees its registry slot', async () => {
+    const stakeRegistry = pda(Buffer.from('stake-registry'), staker.publicKey.toBuffer());
+    const openBefore = (await program.account.stakeRegistry.fetch(stakeRegistry)).openPositions;
+    const position = await stake(staker, GREY(500), { security: {} }, 1);
+    const unbondingRequest = await requestUnstake(staker, position, GREY(500));
+
+    await warpSeconds(7 * DAY);
+    await program.methods
+      .withdrawUnbonded()
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        stakeRegistry,
+        stakeAccount: position,
+        unbondingRequest,
+        stakeVault: stakeVaultFor(position),
+        serviceProvider: pda(Buffer.from('service-provider'), staker.publicKey.toBuffer()),
+        userTokenAccount: tokenAccount(staker.publicKey),
+        user: staker.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([staker])
+      .rpc();
+
+    assert.isNull(await context.banksClient.getAccount(position));
+    assert.isNull(await context.banksClient.getAccount(stakeVaultFor(position)));
+    assert.equal((await program.account.stakeRegistry.fetch(stakeRegistry)).openPositions, openBefore);
+  });
+
+  it('Refuses to merge away the position backing a service', async () => {
+    fundGrey(provider.publicKey, GREY(40_000));
+    const linkedStake = await stake(provider, GREY(10_000), { service: {} }, 0);
+    await registerService(provider, linkedStake);
+    const spareStake = await stake(provider, GREY(10_000), { service: {} }, 1);
+
+    // Disputes execute against the linked position, so it has to outlive every other one
+    await expectError(mergeStakes(provider, linkedStake, spareStake), 'ServiceStakeLinked');
+
+    await mergeStakes(provider, spareStake, linkedStake);
+    assert.isNull(await context.banksClient.getAccount(spareStake));
+    assert.equal((await program.account.stakeAccount.fetch(linkedStake)).amount.toString(), GREY(20_000).toString());
+    const stakeRegistry = pda(Buffer.from('stake-registry'), provider.publicKey.toBuffer());
+    assert.equal((await program.account.stakeRegistry.fetch(stakeRegistry)).openPositions, 1);
+  });
+
+  it('Locks the linked service stake against splits and merges while a dispute is open', async () => {
+    const providerService = pda(Buffer.from('service-provider'), provider.publicKey.toBuffer());
+    await fileDispute(reporter, providerService, 'Provider sold the data on');
+
+    // Splitting would move stake out of reach of the slash, since only the linked position is slashed
+    const linkedStake = stakeAccountFor(provider.publicKey, 0);
+    const newStake = stakeAccountFor(provider.publicKey, 2);
+    await expectError(
+      program.methods
+        .splitStake(GREY(10_000))
+        .accounts({
+          greylockerState: statePda,
+          greyMint: greyMint.publicKey,
+          stakeRegistry: pda(Buffer.from('stake-registry'), provider.publicKey.toBuffer()),
+          sourceStakeAccount: linkedStake,
+          sourceStakeVault: stakeVaultFor(linkedStake),
+          newStakeAccount: newStake,
+          newStakeVault: stakeVaultFor(newStake),
+          serviceProvider: providerService,
+          user: provider.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([provider])
+        .rpc(),
+      'ServiceUnderDispute'
+    );
+
+    const otherStake = await stake(provider, GREY(10_000), { service: {} }, 2);
+    await expectError(mergeStakes(provider, otherStake, linkedStake), 'ServiceUnderDispute');
+  });
+
+  it('Opens a governance data pool only on a council proposal past its timelock', async () => {
+    const info = poolInfo('Sleep study');
+    const proposal = await propose({ createDataPool: { poolInfo: info } });
+    await expectError(createDataPool(info, proposal), 'TimelockNotElapsed');
+
+    await warpSeconds(DAY);
+    await expectError(createDataPool({ ...info, rewardRate: GREY(1_000) }, proposal), 'ProposalActionMismatch');
+    await createDataPool(info, proposal);
+    assert.property((await program.account.proposal.fetch(proposal)).status, 'executed');
+    assert.equal((await program.account.dataPool.fetch(governancePool(info.name))).name, info.name);
+
+    // The pool account can't be created from inside execute_proposal, so that path refuses it
+    const other = await propose({ createDataPool: { poolInfo: poolInfo('Step counts') } });
+    await warpSeconds(DAY);
+    await expectError(executeProposal(other), 'ProposalRunsInline');
+  });
+
+  it('Hears counter-evidence and keeps a ruling whose appeal nobody matched', async () => {
+    const providerService = pda(Buffer.from('service-provider'), provider.publicKey.toBuffer());
+    const dispute = pda(Buffer.from('dispute'), reporter.publicKey.toBuffer(), providerService.toBuffer());
+    const respond = (owner: Keypair, counterEvidence: string) =>
+      program.methods
+        .respondToDispute(counterEvidence)
+        .accounts({ dispute, owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+    // Only the accused answers, and only once
+    await expectError(respond(reporter, 'Hearsay'), 'NotAuthorized');
+    await respond(provider, 'Every export is logged and signed');
+    await expectError(respond(provider, 'And another thing'), 'AlreadyResponded');
+    assert.equal((await program.account.dispute.fetch(dispute)).counterEvidence, 'Every export is logged and signed');
+
+    const jurorRecord = await seatJuror(dispute, 0);
+    await warpSeconds(3 * DAY + 1);
+    await advanceDispute(dispute);
+    await program.methods
+      .castJuryVote({ dismissed: {} })
+      .accounts({ dispute, jurorRecord, juror: juror.publicKey })
+      .signers([juror])
+      .rpc();
+    await warpSeconds(3 * DAY + 1);
+    await advanceDispute(dispute);
+    assert.property((await program.account.dispute.fetch(dispute)).resolution, 'dismissed');
+
+    const bondVault = pda(Buffer.from('dispute-bond'), dispute.toBuffer());
+    const appeal = (appellant: Keypair) =>
+      program.methods
+        .appealDispute()
+        .accounts({
+          greylockerState: statePda,
+          greyMint: greyMint.publicKey,
+          dispute,
+          bondVault,
+          appellantTokenAccount: tokenAccount(appellant.publicKey),
+          appellant: appellant.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([appellant])
+        .rpc();
+
+    // The winner has nothing to appeal, and the appellant can't match their own bond
+    await expectError(appeal(provider), 'NotAuthorized');
+    const reporterBefore = await tokenBalance(tokenAccount(reporter.publicKey));
+    const providerBefore = await tokenBalance(tokenAccount(provider.publicKey));
+    await appeal(reporter);
+    await expectError(
+      program.methods
+        .postAppealBond()
+        .accounts({
+          dispute,
+          bondVault,
+          respondentTokenAccount: tokenAccount(reporter.publicKey),
+          respondent: reporter.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([reporter])
+        .rpc(),
+      'NotAuthorized'
+    );
+
+    // Unmatched, the appeal never reaches a jury and the dismissal stands with no further window
+    await warpSeconds(3 * DAY + 1);
+    await advanceDispute(dispute);
+    const resolved = await program.account.dispute.fetch(dispute);
+    assert.property(resolved.status, 'resolved');
+    assert.property(resolved.resolution, 'dismissed');
+    await expectError(appeal(reporter), 'AppealWindowClosed');
+
+    await program.methods
+      .executeDisputeRuling()
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        dispute,
+        serviceProvider: providerService,
+        stakeAccount: stakeAccountFor(provider.publicKey, 0),
+        stakeVault: stakeVaultFor(stakeAccountFor(provider.publicKey, 0)),
+        reporterTokenAccount: tokenAccount(reporter.publicKey),
+        accusedTokenAccount: tokenAccount(provider.publicKey),
+        reporterBondVault: pda(Buffer.from('reporter-bond'), dispute.toBuffer()),
+        reporterRecord: pda(Buffer.from('reporter'), reporter.publicKey.toBuffer()),
+        bondVault,
+        delegationPool: null,
+        delegationVault: null,
+        dataPool: null,
+        executor: reporter.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([reporter])
+      .rpc();
+
+    // The lone appeal bond comes back; half the rejected reporter bond goes to the accused, half is burned
+    assert.equal((await tokenBalance(tokenAccount(reporter.publicKey))).toString(), reporterBefore.toString());
+    assert.equal(
+      (await tokenBalance(tokenAccount(provider.publicKey))).toString(),
+      providerBefore.add(GREY(250)).toString()
+    );
+    const service = await program.account.serviceProvider.fetch(providerService);
+    assert.equal(service.openDisputes, 0);
+    assert.equal(service.dismissedDisputes, 1);
+  });
+
+  it('Takes a bond per filing and rate-limits reporters over a rolling window', async () => {
+    // Each dispute is keyed by reporter and service, so spreading filings needs several services
+    const services = [serviceProvider];
+    for (let i = 0; i < 3; i++) {
+      const owner = newActor(GREY(10_000));
+      await registerService(owner, await stake(owner, GREY(10_000), { service: {} }));
+      services.push(pda(Buffer.from('service-provider'), owner.publicKey.toBuffer()));
+    }
+
+    // Enough GREY for exactly four bonds
+    const filer = newActor(GREY(2_000));
+    const filerRecord = pda(Buffer.from('reporter'), filer.publicKey.toBuffer());
+    await expectError(fileDispute(filer, services[0], 'x'.repeat(201)), 'EvidenceTooLong');
+
+    for (const service of services.slice(0, 3)) {
+      const dispute = await fileDispute(filer, service, 'Logged a data leak');
+      assert.equal((await tokenBalance(pda(Buffer.from('reporter-bond'), dispute.toBuffer()))).toString(), GREY(500).toString());
+      assert.equal((await program.account.dispute.fetch(dispute)).reporterBond.toString(), GREY(500).toString());
+    }
+    assert.equal((await tokenBalance(tokenAccount(filer.publicKey))).toString(), GREY(500).toString());
+    await expectError(fileDispute(filer, services[3], 'One more'), 'ReporterRateLimited');
+
+    // A new window opens a week after the first filing in the last one
+    await warpSeconds(7 * DAY);
+    await fileDispute(filer, services[3], 'One more');
+    const record = await program.account.reporterRecord.fetch(filerRecord);
+    assert.equal(record.disputesInWindow, 1);
+    assert.equal(record.openDisputes, 4);
+    assert.equal((await tokenBalance(tokenAccount(filer.publicKey))).toString(), '0');
+  });
+
+  it('Prices stGREY on backing plus unharvested rewards', async () => {
+    const liquidStakingPool = pda(Buffer.from('liquid-staking'));
+    const stMint = pda(Buffer.from('st-grey-mint'));
+    const liquidVault = pda(Buffer.from('liquid-staking-vault'));
+    await program.methods
+      .initializeLiquidStaking()
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        liquidStakingPool,
+        stGreyMint: stMint,
+        liquidVault,
+        governanceAuthority: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([admin])
+      .rpc();
+
+    const stTokenAccount = (owner: PublicKey) => getAssociatedTokenAddressSync(stMint, owner, true);
+    const stakeLiquid = (user: Keypair, amount: anchor.BN) =>
+      program.methods
+        .stakeLiquid(amount)
+        .accounts({
+          greylockerState: statePda,
+          liquidStakingPool,
+          stGreyMint: stMint,
+          liquidVault,
+          treasury: pda(Buffer.from('treasury')),
+          userTokenAccount: tokenAccount(user.publicKey),
+          userStTokenAccount: stTokenAccount(user.publicKey),
+          user: user.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([user])
+        .rpc();
+    const early = newActor(GREY(2_000));
+    const late = newActor(GREY(2_000));
+    for (const user of [early, late]) {
+      writeTokenAccount(stTokenAccount(user.publicKey), user.publicKey, new anchor.BN(0), stMint);
+    }
+
+    await expectError(stakeLiquid(early, GREY(999)), 'InsufficientStakeAmount');
+    await stakeLiquid(early, GREY(1_000));
+    assert.equal((await tokenBalance(stTokenAccount(early.publicKey))).toString(), GREY(1_000).toString());
+
+    // A month of unharvested rewards belongs to the first holder, so the same GREY buys less stGREY later
+    await warpSeconds(30 * DAY);
+    await stakeLiquid(late, GREY(1_000));
+    const pool = await program.account.liquidStakingPool.fetch(liquidStakingPool);
+    assert.isTrue(pool.pendingRewards.gtn(0));
+    const lateShares = await tokenBalance(stTokenAccount(late.publicKey));
+    assert.equal(lateShares.toString(), GREY(1_000).mul(GREY(1_000)).div(GREY(1_000).add(pool.pendingRewards)).toString());
+
+    // Unwrapping carries the holder's cut of unharvested rewards onto the new position
+    const supply = GREY(1_000).add(lateShares);
+    const unwrap = (st: anchor.BN) =>
+      program.methods
+        .unwrapLiquidStake(st)
+        .accounts({
+          greylockerState: statePda,
+          greyMint: greyMint.publicKey,
+          liquidStakingPool,
+          stGreyMint: stMint,
+          liquidVault,
+          userStTokenAccount: stTokenAccount(early.publicKey),
+          stakeRegistry: pda(Buffer.from('stake-registry'), early.publicKey.toBuffer()),
+          stakeAccount: stakeAccountFor(early.publicKey, 0),
+          stakeVault: stakeVaultFor(stakeAccountFor(early.publicKey, 0)),
+          user: early.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([early])
+        .rpc();
+    await expectError(unwrap(new anchor.BN(0)), 'InvalidParameterValue');
+    await unwrap(GREY(1_000));
+    const position = await program.account.stakeAccount.fetch(stakeAccountFor(early.publicKey, 0));
+    assert.property(position.stakeType, 'liquidity');
+    assert.equal(position.amount.toString(), GREY(1_000).mul(pool.totalBacking).div(supply).toString());
+    assert.equal(position.accumulatedRewards.toString(), GREY(1_000).mul(pool.pendingRewards).div(supply).toString());
+    assert.isTrue(position.amount.add(position.accumulatedRewards).gt(GREY(1_000)));
+  });
+
+  it('Vests in whole tranches, pays nothing early and returns the unvested rest on revocation', async () => {
+    const beneficiary = newActor(GREY(0));
+    const treasury = pda(Buffer.from('treasury'));
+    const vestingEscrow = pda(Buffer.from('vesting-escrow'));
+    const now = Number((await context.banksClient.getClock()).unixTimestamp);
+    // Three tranches over 40 days: 15, 30 and the remainder at the end
+    const schedule = {
+      beneficiary: beneficiary.publicKey,
+      totalAmount: GREY(3_000),
+      startTs: new anchor.BN(now + 2 * DAY),
+      cliffTs: new anchor.BN(now + 12 * DAY),
+      endTs: new anchor.BN(now + 42 * DAY),
+      kind: { step: { interval: new anchor.BN(15 * DAY) } },
+      revocable: true,
+    };
+    const vestingId = (await program.account.greylockerState.fetch(statePda)).nextVestingId;
+    const vestingSchedule = pda(Buffer.from('vesting'), vestingId.toArrayLike(Buffer, 'le', 8));
+    const createVesting = (proposal: PublicKey, startTs: anchor.BN) =>
+      program.methods
+        .createVestingSchedule(
+          schedule.beneficiary,
+          schedule.totalAmount,
+          startTs,
+          schedule.cliffTs,
+          schedule.endTs,
+          schedule.kind,
+          schedule.revocable
+        )
+        .accounts({
+          greylockerState: statePda,
+          greyMint: greyMint.publicKey,
+          vestingSchedule,
+          vestingEscrow,
+          treasury,
+          governanceCouncil: councilPda,
+          proposal,
+          governanceAuthority: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([admin])
+        .rpc();
+    const claim = () =>
+      program.methods
+        .claimVested()
+        .accounts({
+          greylockerState: statePda,
+          vestingSchedule,
+          vestingEscrow,
+          beneficiaryTokenAccount: tokenAccount(beneficiary.publicKey),
+          beneficiary: beneficiary.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([beneficiary])
+        .rpc();
+
+    const proposal = await propose({ createVestingSchedule: schedule });
+    await warpSeconds(DAY);
+    await expectError(createVesting(proposal, new anchor.BN(now)), 'InvalidVestingSchedule');
+    await createVesting(proposal, schedule.startTs);
+    assert.equal((await tokenBalance(vestingEscrow)).toString(), GREY(3_000).toString());
+
+    // Past the cliff but short of the first tranche
+    await warpSeconds(15 * DAY);
+    await expectError(claim(), 'NothingVested');
+    await warpSeconds(16 * DAY);
+    await claim();
+    assert.equal((await tokenBalance(tokenAccount(beneficiary.publicKey))).toString(), GREY(2_000).toString());
+
+    const treasuryBefore = await tokenBalance(treasury);
+    const revoke = { revokeVesting: { schedule: vestingSchedule } };
+    await runProposal(revoke, { vestingSchedule, vestingEscrow, treasury });
+    const revoked = await program.account.vestingSchedule.fetch(vestingSchedule);
+    assert.isTrue(revoked.revoked);
+    assert.equal(revoked.totalAmount.toString(), GREY(2_000).toString());
+    assert.equal((await tokenBalance(treasury)).toString(), treasuryBefore.add(GREY(1_000)).toString());
+
+    // Nothing left to claim, and a schedule is only revoked once
+    await expectError(claim(), 'NothingVested');
+    await expectError(runProposal(revoke, { vestingSchedule, vestingEscrow, treasury }), 'VestingNotRevocable');
+  });
+
+  it('Pays each airdrop leaf once, stakes on claim and claws back the rest at expiry', async () => {
+    const first = newActor(GREY(0));
+    const second = newActor(GREY(0));
+    const treasury = pda(Buffer.from('treasury'));
+
+    // Leaves are keccak(index || claimant || amount) and pairs hash in sorted order
+    const leaf = (index: number, claimant: PublicKey, amount: anchor.BN) => {
+      const indexBytes = Buffer.alloc(4);
+      indexBytes.writeUInt32LE(index);
+      return Buffer.from(keccak_256(Buffer.concat([indexBytes, claimant.toBuffer(), amount.toArrayLike(Buffer, 'le', 8)])));
+    };
+    // The second leaf sits at index 8, in the bitmap's second byte
+    const firstLeaf = leaf(0, first.publicKey, GREY(600));
+    const secondLeaf = leaf(8, second.publicKey, GREY(1_500));
+    const [low, high] = Buffer.compare(firstLeaf, secondLeaf) <= 0 ? [firstLeaf, secondLeaf] : [secondLeaf, firstLeaf];
+    const merkleRoot = Array.from(keccak_256(Buffer.concat([low, high])));
+
+    const distributor = pda(Buffer.from('distributor'), Buffer.from(merkleRoot));
+    const airdropVault = pda(Buffer.from('airdrop-vault'), distributor.toBuffer());
+    const now = Number((await context.banksClient.getClock()).unixTimestamp);
+    const airdrop = {
+      merkleRoot,
+      numLeaves: 9,
+      totalAmount: GREY(2_500),
+      expiresAt: new anchor.BN(now + 10 * DAY),
+    };
+    const proposal = await propose({ createAirdrop: airdrop });
+    await warpSeconds(DAY);
+    await program.methods
+      .createAirdrop(airdrop.merkleRoot, airdrop.numLeaves, airdrop.totalAmount, airdrop.expiresAt)
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        distributor,
+        airdropVault,
+        treasury,
+        governanceCouncil: councilPda,
+        proposal,
+        governanceAuthority: admin.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([admin])
+      .rpc();
+
+    const claim = (index: number, amount: anchor.BN, proof: Buffer[]) =>
+      program.methods
+        .claimAirdrop(index, amount, proof.map((node) => Array.from(node)))
+        .accounts({
+          greylockerState: statePda,
+          distributor,
+          airdropVault,
+          claimantTokenAccount: tokenAccount(first.publicKey),
+          claimant: first.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([first])
+        .rpc();
+    await expectError(claim(0, GREY(6_000), [secondLeaf]), 'InvalidMerkleProof');
+    await expectError(claim(9, GREY(600), [secondLeaf]), 'InvalidAirdrop');
+    await claim(0, GREY(600), [secondLeaf]);
+    await expectError(claim(0, GREY(600), [secondLeaf]), 'AirdropAlreadyClaimed');
+    assert.equal((await tokenBalance(tokenAccount(first.publicKey))).toString(), GREY(600).toString());
+
+    const secondStake = stakeAccountFor(second.publicKey, 0);
+    await program.methods
+      .claimAirdropAndStake(8, GREY(1_500), [Array.from(firstLeaf)], { liquidity: {} }, new anchor.BN(0))
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        distributor,
+        airdropVault,
+        stakeRegistry: pda(Buffer.from('stake-registry'), second.publicKey.toBuffer()),
+        stakeAccount: secondStake,
+        stakeVault: stakeVaultFor(secondStake),
+        claimant: second.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([second])
+      .rpc();
+    assert.equal((await program.account.stakeAccount.fetch(secondStake)).amount.toString(), GREY(1_500).toString());
+    assert.equal((await tokenBalance(stakeVaultFor(secondStake))).toString(), GREY(1_500).toString());
+
+    const clawback = () =>
+      program.methods
+        .clawbackAirdrop()
+        .accounts({ greylockerState: statePda, distributor, airdropVault, treasury, tokenProgram: TOKEN_PROGRAM_ID })
+        .rpc();
+    await expectError(clawback(), 'AirdropNotExpired');
+    await warpSeconds(10 * DAY);
+    await expectError(claim(0, GREY(600), [secondLeaf]), 'AirdropExpired');
+    const treasuryBefore = await tokenBalance(treasury);
+    await clawback();
+    assert.equal((await tokenBalance(treasury)).toString(), treasuryBefore.add(GREY(400)).toString());
+    await expectError(clawback(), 'AirdropExpired');
+  });
+
+  it('Pays a contribution only once a quorum of validators with aged stake accepts it', async () => {
+    const pool = governancePool('Sleep study');
+    const contributor = newActor(GREY(0));
+    await joinPool(contributor, pool, admin.publicKey);
+
+    // Stake opened in the week before a submission can't vouch for it
+    for (let i = 0; i < 2; i++) {
+      const validator = newActor(GREY(10_000));
+      await stake(validator, GREY(5_000), { dataValidator: {} });
+      attesters.push(validator);
+    }
+    const epoch = await currentEpoch(pool);
+    await expectError(submitContribution(contributor, pool, admin.publicKey, 5, epoch + 1), 'InvalidContribution');
+    const unvetted = await submitContribution(contributor, pool, admin.publicKey, 5);
+    await expectError(attest(attesters[1], unvetted, pool), 'AttesterStakeTooNew');
+
+    // A single approval is short of quorum, so the data goes unpaid
+    await attest(juror, unvetted, pool);
+    await warpSeconds(2 * DAY);
+    await finalizeContribution(unvetted, pool);
+    const rejected = await program.account.contribution.fetch(unvetted);
+    assert.property(rejected.status, 'rejected');
+    assert.equal(rejected.reward.toString(), '0');
+    await releaseAttestation(juror, unvetted);
+
+    await warpSeconds(5 * DAY);
+    const contribution = await submitContribution(contributor, pool, admin.publicKey, 5);
+    for (const attester of attesters) {
+      await attest(attester, contribution, pool);
+    }
+    await expectError(finalizeContribution(contribution, pool), 'InvalidContributionPhase');
+    await warpSeconds(2 * DAY);
+    await finalizeContribution(contribution, pool);
+    assert.property((await program.account.contribution.fetch(contribution)).status, 'accepted');
+
+    // Attesting stake stays put until the challenge period is over and the attestation released
+    const attesterStake = stakeAccountFor(attesters[1].publicKey, 0);
+    await expectError(requestUnstake(attesters[1], attesterStake, GREY(1)), 'AttestationStakeLocked');
+    await expectError(payDataReward(contributor, pool, admin.publicKey, contribution), 'InvalidContributionPhase');
+    await expectError(releaseAttestation(attesters[1], contribution), 'InvalidContributionPhase');
+
+    await warpSeconds(3 * DAY);
+    await program.methods
+      .fundRewardReserve(GREY(10_000))
+      .accounts({
+        rewardReserve,
+        reserveVault,
+        funderTokenAccount: tokenAccount(admin.publicKey),
+        funder: admin.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+    await payDataReward(contributor, pool, admin.publicKey, contribution);
+    assert.equal((await tokenBalance(tokenAccount(contributor.publicKey))).toString(), GREY(50).toString());
+    await releaseAttestation(attesters[1], contribution);
+    assert.equal((await program.account.stakeAccount.fetch(attesterStake)).openAttestations, 0);
+  });
+
+  it('Caps a pool by participants and budget and only closes it once nothing is in flight', async () => {
+    const info = { ...poolInfo('Heart rate'), maxParticipants: new anchor.BN(1) };
+    const proposal = await propose({ createDataPool: { poolInfo: info } });
+    await warpSeconds(DAY);
+    await createDataPool(info, proposal);
+    const pool = governancePool(info.name);
+    const budgetVault = budgetVaultFor(pool);
+    const treasury = pda(Buffer.from('treasury'));
+
+    const fundPool = (funder: Keypair, amount: anchor.BN) =>
+      program.methods
+        .fundDataPool(amount)
+        .accounts({
+          greylockerState: statePda,
+          greyMint: greyMint.publicKey,
+          dataPool: pool,
+          budgetVault,
+          funderTokenAccount: tokenAccount(funder.publicKey),
+          funder: funder.publicKey,
+          dataPoolCreator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([funder])
+        .rpc();
+    const deactivatePool = (authority: Keypair) =>
+      program.methods
+        .deactivateDataPool()
+        .accounts({ greylockerState: statePda, dataPool: pool, authority: authority.publicKey, dataPoolCreator: admin.publicKey })
+        .signers([authority])
+        .rpc();
+    const closePool = () =>
+      program.methods
+        .closeDataPool()
+        .accounts({
+          greylockerState: statePda,
+          dataPool: pool,
+          budgetVault,
+          refundAccount: treasury,
+          authority: admin.publicKey,
+          dataPoolCreator: admin.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+
+    // Funding moves the pool onto its own budget, so only its controllers may do it
+    const stranger = newActor(GREY(1_000));
+    await expectError(fundPool(stranger, GREY(1)), 'NotAuthorized');
+    await fundPool(admin, GREY(60));
+
+    const contributor = newActor(GREY(0));
+    await joinPool(contributor, pool, admin.publicKey);
+    await expectError(joinPool(stranger, pool, admin.publicKey), 'DataPoolFull');
+
+    // Ten points at 10 GREY is more than the budget holds; five fit and are escrowed on acceptance
+    await expectError(submitContribution(contributor, pool, admin.publicKey, 10), 'DataPoolBudgetExhausted');
+    const contribution = await submitContribution(contributor, pool, admin.publicKey, 5);
+    await acceptContribution(contribution, pool);
+    assert.isTrue((await program.account.contribution.fetch(contribution)).escrowed);
+    assert.equal((await program.account.dataPool.fetch(pool)).budgetCommitted.toString(), GREY(50).toString());
+
+    await expectError(deactivatePool(stranger), 'NotAuthorized');
+    await deactivatePool(admin);
+    await expectError(closePool(), 'DataPoolStillActive');
+
+    // Deactivation stops new work, not payment for work already accepted
+    await warpSeconds(3 * DAY);
+    await payDataReward(contributor, pool, admin.publicKey, contribution, budgetVault);
+    assert.equal((await tokenBalance(tokenAccount(contributor.publicKey))).toString(), GREY(50).toString());
+
+    const treasuryBefore = await tokenBalance(treasury);
+    await closePool();
+    assert.equal((await tokenBalance(treasury)).toString(), treasuryBefore.add(GREY(10)).toString());
+    assert.isNull(await context.banksClient.getAccount(pool));
+    assert.isNull(await context.banksClient.getAccount(budgetVault));
+  });
+
+  it('Lets a reputable provider open an escrowed pool that keeps paying accepted work while paused', async () => {
+    const sponsor = await newSponsor();
+    const info = poolInfo('Sensor feed');
+    await expectError(createProviderPool(sponsor, info, GREY(100)), 'ReputationTooLow');
+    await runProposal({ updateParameter: { parameter: { poolCreatorMinReputation: {} }, value: new anchor.BN(50) } });
+    const pool = await createProviderPool(sponsor, info, GREY(100));
+    assert.equal((await tokenBalance(budgetVaultFor(pool))).toString(), GREY(100).toString());
+
+    const contributor = newActor(GREY(0));
+    await joinPool(contributor, pool, sponsor.publicKey);
+    const contribution = await submitContribution(contributor, pool, sponsor.publicKey, 5);
+    await acceptContribution(contribution, pool);
+    assert.isTrue((await program.account.contribution.fetch(contribution)).escrowed);
+
+    // Only the sponsor (or governance) runs the pool; pausing holds new work only
+    const stranger = newActor(GREY(0));
+    await expectError(setPoolPaused(pool, sponsor.publicKey, stranger, true), 'NotAuthorized');
+    await setPoolPaused(pool, sponsor.publicKey, sponsor, true);
+    await expectError(joinPool(stranger, pool, sponsor.publicKey), 'DataPoolInactive');
+
+    // Provider pools are data purchases, so the 2% access-fee rate comes off the top
+    await warpSeconds(3 * DAY);
+    await payDataReward(contributor, pool, sponsor.publicKey, contribution, budgetVaultFor(pool));
+    assert.equal((await tokenBalance(tokenAccount(contributor.publicKey))).toString(), GREY(49).toString());
+    assert.equal((await tokenBalance(budgetVaultFor(pool))).toString(), GREY(50).toString());
+  });
+
+  it('Settles accepted work for participants who leave and holds the pool open until they are paid', async () => {
+    const sponsor = await newSponsor();
+    const pool = await createProviderPool(sponsor, poolInfo('Glucose log'), GREY(100));
+    const budgetVault = budgetVaultFor(pool);
+
+    const contributor = newActor(GREY(0));
+    const quitter = newActor(GREY(0));
+    await expectError(joinPool(contributor, pool, sponsor.publicKey, Array(32).fill(0)), 'ConsentRequired');
+    await joinPool(contributor, pool, sponsor.publicKey);
+    await joinPool(quitter, pool, sponsor.publicKey);
+    const contribution = await submitContribution(contributor, pool, sponsor.publicKey, 5);
+    const unvetted = await submitContribution(quitter, pool, sponsor.publicKey, 3);
+    await acceptContribution(contribution, pool);
+
+    // Withdrawing consent works even on a paused pool
+    await setPoolPaused(pool, sponsor.publicKey, sponsor, true);
+    await leavePool(contributor, pool, sponsor.publicKey);
+    await leavePool(quitter, pool, sponsor.publicKey);
+    assert.isNull(await context.banksClient.getAccount(participantFor(contributor.publicKey, pool)));
+    assert.equal((await program.account.dataPool.fetch(pool)).totalParticipants.toString(), '0');
+
+    // Unvetted work from someone who left is cleared; accepted work becomes a settlement
+    await program.methods
+      .forfeitContribution()
+      .accounts({ dataPool: pool, contribution: unvetted, participant: participantFor(quitter.publicKey, pool) })
+      .rpc();
+    assert.property((await program.account.contribution.fetch(unvetted)).status, 'forfeited');
+    const settle = () =>
+      program.methods
+        .settleContribution()
+        .accounts({
+          dataPool: pool,
+          contribution,
+          participant: participantFor(contributor.publicKey, pool),
+          poolSettlement: settlementFor(pool, contributor.publicKey),
+          payer: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    await expectError(settle(), 'InvalidContributionPhase');
+    await warpSeconds(3 * DAY);
+    await settle();
+    assert.equal(
+      (await program.account.poolSettlement.fetch(settlementFor(pool, contributor.publicKey))).owedRewards.toString(),
+      GREY(50).toString()
+    );
+
+    const closePool = () =>
+      program.methods
+        .closeDataPool()
+        .accounts({
+          greylockerState: statePda,
+          dataPool: pool,
+          budgetVault,
+          refundAccount: tokenAccount(sponsor.publicKey),
+          authority: sponsor.publicKey,
+          dataPoolCreator: sponsor.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([sponsor])
+        .rpc();
+    await program.methods
+      .deactivateDataPool()
+      .accounts({ greylockerState: statePda, dataPool: pool, authority: sponsor.publicKey, dataPoolCreator: sponsor.publicKey })
+      .signers([sponsor])
+      .rpc();
+    await expectError(closePool(), 'DataPoolRewardsOwed');
+
+    await program.methods
+      .claimPoolSettlement()
+      .accounts({
+        greylockerState: statePda,
+        greyMint: greyMint.publicKey,
+        treasury: pda(Buffer.from('treasury')),
+        dataPool: pool,
+        poolSettlement: settlementFor(pool, contributor.publicKey),
+        rewardReserve,
+        reserveVault,
+        budgetVault,
+        userTokenAccount: tokenAccount(contributor.publicKey),
+        user: contributor.publicKey,
+        dataPoolCreator: sponsor.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([contributor])
+      .rpc();
+    assert.equal((await tokenBalance(tokenAccount(contributor.publicKey))).toString(), GREY(49).toString());
+    assert.isNull(await context.banksClient.getAccount(settlementFor(pool, contributor.publicKey)));
+
+    // Paid up, the pool closes and the unspent escrow goes back to the sponsor
+    const sponsorBefore = await tokenBalance(tokenAccount(sponsor.publicKey));
+    await closePool();
+    assert.equal((await tokenBalance(tokenAccount(sponsor.publicKey))).toString(), sponsorBefore.add(GREY(50)).toString());
+  });
+
+  it('Charges queries against a privacy budget that survives leaving and rejoining', async () => {
+    const info = { ...poolInfo('Step queries'), epsilonBudget: new anchor.BN(1_500) };
+    const proposal = await propose({ createDataPool: { poolInfo: info } });
+    await warpSeconds(DAY);
+    await createDataPool(info, proposal);
+    const pool = governancePool(info.name);
+    const member = newActor(GREY(0));
+    const ledger = pda(Buffer.from('privacy-ledger'), pool.toBuffer(), member.publicKey.toBuffer());
+    const record = (authority: Keypair, epsilon: number, fee = GREY(0)) =>
+      program.methods
+        .recordQuery(randomHash(), new anchor.BN(epsilon), fee)
+        .accounts({
+          greylockerState: statePda,
+          greyMint: greyMint.publicKey,
+          treasury: pda(Buffer.from('treasury')),
+          dataPool: pool,
+          participant: participantFor(member.publicKey, pool),
+          privacyLedger: ledger,
+          userTokenAccount: tokenAccount(member.publicKey),
+          operatorTokenAccount: tokenAccount(authority.publicKey),
+          user: member.publicKey,
+          authority: authority.publicKey,
+          dataPoolCreator: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority])
+        .rpc();
+    await joinPool(member, pool, admin.publicKey);
+
+    const stranger = newActor(GREY(100));
+    await expectError(record(stranger, 100), 'NotAuthorized');
+    await expectError(record(admin, 1_001), 'InvalidQueryEpsilon');
+    await expectError(record(admin, 0), 'InvalidQueryEpsilon');
+
+    // The fee is a data sale, so the member receives it net of the protocol fee
+    fundGrey(admin.publicKey, GREY(100));
+    await record(admin, 1_000, GREY(10));
+    assert.equal((await tokenBalance(tokenAccount(member.publicKey))).toString(), GREY(10).muln(98).divn(100).toString());
+
+    // Rejoining doesn't reset what has already been spent
+    await leavePool(member, pool, admin.publicKey);
+    await joinPool(member, pool, admin.publicKey);
+    await expectError(record(admin, 1_000), 'PrivacyBudgetExhausted');
+    await record(admin, 500);
+    assert.equal((await program.account.privacyLedger.fetch(ledger)).epsilonSpent.toString(), '1500');
+  });
+});
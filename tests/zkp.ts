@@ -1,27 +1,105 @@
 import * as anchor from '@coral-xyz/anchor';
 import { assert } from 'chai';
+import * as fs from 'fs';
+import * as path from 'path';
+
+const cube = JSON.parse(
+  fs.readFileSync(path.join(__dirname, 'fixtures', 'groth16-cube.json'), 'utf8')
+);
 
 describe('Greylocker ZKP Tests', () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
   const program = anchor.workspace.GreylockerZkp;
+  const user = provider.wallet.publicKey;
 
-  it('Initializes ZKP registry', async () => {
-    const [registryPda] = anchor.web3.PublicKey.findProgramAddressSync(
-      [Buffer.from('zkp-registry'), provider.wallet.publicKey.toBuffer()],
+  const [registryPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('zkp-registry'), user.toBuffer()],
+    program.programId
+  );
+  const [vkPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('verification-key'), registryPda.toBuffer(), Buffer.from([0])],
+    program.programId
+  );
+  // Pairing plus point decompression needs more than the default compute budget
+  const computeBudget = anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 });
+
+  const submitAndVerify = async (index: number, inputs: string[]) => {
+    const [submissionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('proof-submission'), vkPda.toBuffer(), Buffer.from([index])],
       program.programId
     );
+    await program.methods
+      .submitProof(
+        Buffer.from(cube.proof, 'hex'),
+        inputs.map((input) => Buffer.from(input, 'hex')),
+        null
+      )
+      .accounts({
+        user,
+        zkpRegistry: registryPda,
+        verificationKeyAccount: vkPda,
+        proofSubmission: submissionPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .verifyProof()
+      .accounts({
+        user,
+        proofSubmission: submissionPda,
+        verificationKeyAccount: vkPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .preInstructions([computeBudget])
+      .rpc();
+    return submissionPda;
+  };
+
+  it('Initializes ZKP registry', async () => {
     await program.methods
       .initializeRegistry()
       .accounts({
-        user: provider.wallet.publicKey,
+        user,
         zkpRegistry: registryPda,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .rpc();
     const registry = await program.account.zkpRegistry.fetch(registryPda);
-    assert.equal(registry.owner.toBase58(), provider.wallet.publicKey.toBase58());
+    assert.equal(registry.owner.toBase58(), user.toBase58());
   });
 
-  // Add more tests: submit_proof, verify_proof, etc.
+  it('Registers a Groth16 verification key', async () => {
+    await program.methods
+      .registerVerificationKey(
+        { rangeProof: {} },
+        'cube-v1',
+        Buffer.from(cube.verificationKey, 'hex'),
+        cube.circuit
+      )
+      .accounts({
+        user,
+        zkpRegistry: registryPda,
+        verificationKeyAccount: vkPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    const vk = await program.account.verificationKeyAccount.fetch(vkPda);
+    assert.isTrue(vk.isActive);
+  });
+
+  it('Verifies a valid Groth16 proof', async () => {
+    const submissionPda = await submitAndVerify(0, cube.publicInputs);
+    const submission = await program.account.proofSubmission.fetch(submissionPda);
+    assert.isTrue(submission.verified);
+  });
+
+  it('Rejects a proof against the wrong public inputs', async () => {
+    try {
+      await submitAndVerify(1, cube.invalidPublicInputs);
+      assert.fail('verify_proof should have failed');
+    } catch (err: any) {
+      assert.include(err.toString(), 'InvalidProof');
+    }
+  });
 });